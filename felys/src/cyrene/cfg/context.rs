@@ -43,6 +43,11 @@ impl Context {
         fragment.instructions.push(instruction);
     }
 
+    pub fn push_at(&mut self, span: usize, instruction: Instruction) {
+        self.f.spans.insert(instruction.dst(), span);
+        self.push(instruction);
+    }
+
     fn dead(&self) -> bool {
         if self.cursor == Label::Entry {
             return false;
@@ -58,6 +63,10 @@ impl Context {
         }
     }
 
+    pub fn terminated(&self) -> bool {
+        self.dead() || self.f.get(self.cursor).unwrap().terminator.is_some()
+    }
+
     pub fn jump(&mut self, to: Label) -> bool {
         if self.dead() {
             return false;
@@ -72,7 +81,7 @@ impl Context {
         true
     }
 
-    pub fn branch(&mut self, cond: Var, to: Label, or: Label) {
+    pub fn branch(&mut self, span: usize, cond: Var, to: Label, or: Label) {
         if self.dead() {
             return;
         }
@@ -82,6 +91,7 @@ impl Context {
         }
         fragment.terminator = Some(Terminator::Branch(cond, to, or));
         let cursor = self.cursor;
+        self.f.branches.insert(cursor, span);
         self.f.get_mut(to).unwrap().predecessors.push(cursor);
         self.f.get_mut(or).unwrap().predecessors.push(cursor);
     }
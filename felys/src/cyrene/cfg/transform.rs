@@ -1,9 +1,11 @@
 use crate::cyrene::cfg::context::{Context, Id};
 use crate::cyrene::error::Error;
-use crate::cyrene::resolver::Map;
+use crate::cyrene::resolver::{Link, Map};
 use crate::philia093::Interner;
-use crate::utils::ast::{AssOp, BinOp, Block, Bool, Chunk, Expr, Lit, Pat, Stmt};
-use crate::utils::function::{Const, Function, Instruction, Label, Var};
+use crate::utils::ast::{
+    Arm, AssOp, BinOp, Block, Bool, Case, Chunk, Expr, Lit, Pat, Segment, Stmt, UnaOp,
+};
+use crate::utils::function::{Const, Function, Instruction, Label, Pointer, Shape, Var};
 
 type Stack = Vec<(Label, Label, Option<(Id, Option<bool>)>)>;
 
@@ -48,6 +50,9 @@ impl Block {
         let mut result = Ok(None);
         let mut i = 1;
         while let Some(stmt) = iter.next() {
+            if ctx.terminated() {
+                break;
+            }
             let ret = stmt.transform(map, interner, ctx, stk)?;
             if ret.is_some() {
                 if iter.peek().is_none() {
@@ -75,7 +80,7 @@ impl Stmt {
             Stmt::Empty => Ok(None),
             Stmt::Expr(expr) => expr.transform(map, interner, ctx, stk).map(From::from),
             Stmt::Semi(expr) => expr.transform(map, interner, ctx, stk).and(Ok(None)),
-            Stmt::Assign(pat, op, expr) => {
+            Stmt::Assign(span, pat, op, expr) => {
                 let op = match op {
                     AssOp::AddEq => Some(BinOp::Add),
                     AssOp::SubEq => Some(BinOp::Sub),
@@ -85,7 +90,7 @@ impl Stmt {
                     AssOp::Eq => None,
                 };
                 let var = expr.transform(map, interner, ctx, stk)?.var()?;
-                pat.transform(ctx, &op, var)?;
+                pat.transform(ctx, *span, &op, var)?;
                 Ok(None)
             }
         }
@@ -93,14 +98,20 @@ impl Stmt {
 }
 
 impl Pat {
-    fn transform(&self, ctx: &mut Context, op: &Option<BinOp>, mut rhs: Var) -> Result<(), Error> {
+    fn transform(
+        &self,
+        ctx: &mut Context,
+        span: usize,
+        op: &Option<BinOp>,
+        mut rhs: Var,
+    ) -> Result<(), Error> {
         match self {
             Pat::Any => {}
             Pat::Tuple(pats) => {
                 for (i, pat) in pats.iter().enumerate() {
                     let field = ctx.var();
-                    ctx.push(Instruction::Unpack(field, rhs, i));
-                    pat.transform(ctx, op, field)?
+                    ctx.push_at(span, Instruction::Unpack(field, rhs, i));
+                    pat.transform(ctx, span, op, field)?
                 }
             }
             Pat::Ident(x) => {
@@ -108,7 +119,7 @@ impl Pat {
                 if let Some(bop) = op {
                     let lhs = ctx.lookup(ctx.cursor, id).unwrap();
                     let var = ctx.var();
-                    ctx.push(Instruction::Binary(var, lhs, *bop, rhs));
+                    ctx.push_at(span, Instruction::Binary(var, lhs, *bop, rhs));
                     rhs = var;
                 }
                 ctx.define(ctx.cursor, id, rhs)
@@ -118,6 +129,99 @@ impl Pat {
     }
 }
 
+impl Case {
+    fn transform(
+        &self,
+        map: &Map,
+        interner: &Interner,
+        ctx: &mut Context,
+        span: usize,
+        src: Var,
+        next: Label,
+    ) -> Result<Vec<(usize, Var)>, Error> {
+        let mut bindings = Vec::new();
+        match self {
+            Case::Any => {}
+            Case::Ident(id) => bindings.push((*id, src)),
+            Case::Lit(lit) | Case::Neg(lit) => {
+                let shape = match lit {
+                    Lit::Str(_) => Shape::Str,
+                    Lit::Bool(_) => Shape::Bool,
+                    _ => Shape::Number,
+                };
+                let test = ctx.var();
+                ctx.push_at(span, Instruction::Test(test, src, shape, 0));
+                guard(ctx, span, test, next);
+                let mut var = lit.transform(span, interner, ctx)?.unwrap();
+                if let Case::Neg(_) = self {
+                    let neg = ctx.var();
+                    ctx.push_at(span, Instruction::Unary(neg, UnaOp::Neg, var));
+                    var = neg;
+                }
+                let cond = ctx.var();
+                ctx.push_at(span, Instruction::Binary(cond, src, BinOp::Eq, var));
+                guard(ctx, span, cond, next);
+            }
+            Case::Tuple(cases) => {
+                let cond = ctx.var();
+                let instruction = Instruction::Test(cond, src, Shape::Tuple, cases.len());
+                ctx.push_at(span, instruction);
+                guard(ctx, span, cond, next);
+                for (i, case) in cases.iter().enumerate() {
+                    let field = ctx.var();
+                    ctx.push_at(span, Instruction::Unpack(field, src, i));
+                    bindings.extend(case.transform(map, interner, ctx, span, field, next)?);
+                }
+            }
+            Case::Group(at, _, cases) => {
+                let Link::Item(Pointer::Group, gp) = map.get(at).unwrap() else {
+                    unreachable!()
+                };
+                let cond = ctx.var();
+                ctx.push_at(span, Instruction::Test(cond, src, Shape::Group, *gp));
+                guard(ctx, span, cond, next);
+                for (i, case) in cases.iter().enumerate() {
+                    let index = ctx.var();
+                    ctx.push(Instruction::Load(index, Const::Int(i as i32)));
+                    let field = ctx.var();
+                    ctx.push_at(span, Instruction::Nth(field, src, index));
+                    bindings.extend(case.transform(map, interner, ctx, span, field, next)?);
+                }
+            }
+            Case::List(cases, rest) => {
+                let shape = if rest.is_some() {
+                    Shape::Rest
+                } else {
+                    Shape::List
+                };
+                let cond = ctx.var();
+                ctx.push_at(span, Instruction::Test(cond, src, shape, cases.len()));
+                guard(ctx, span, cond, next);
+                for (i, case) in cases.iter().enumerate() {
+                    let index = ctx.var();
+                    ctx.push(Instruction::Load(index, Const::Int(i as i32)));
+                    let element = ctx.var();
+                    ctx.push_at(span, Instruction::Nth(element, src, index));
+                    bindings.extend(case.transform(map, interner, ctx, span, element, next)?);
+                }
+                if let Some(Some(id)) = rest {
+                    let var = ctx.var();
+                    ctx.push_at(span, Instruction::Tail(var, src, cases.len()));
+                    bindings.push((*id, var));
+                }
+            }
+        }
+        Ok(bindings)
+    }
+}
+
+fn guard(ctx: &mut Context, span: usize, cond: Var, next: Label) {
+    let pass = ctx.label();
+    ctx.branch(span, cond, pass, next);
+    ctx.seal(pass);
+    ctx.cursor = pass;
+}
+
 enum Tmp<'a> {
     Var(Var),
     Caller(&'a Expr),
@@ -150,11 +254,11 @@ impl Expr {
         stk: &mut Stack,
     ) -> Result<Tmp<'_>, Error> {
         match self {
-            Expr::Block(block) => block.transform(map, interner, ctx, stk).map(|x| match x {
+            Expr::Block(_, block) => block.transform(map, interner, ctx, stk).map(|x| match x {
                 Some(var) => Tmp::Var(var),
                 None => Tmp::Caller(self),
             }),
-            Expr::Break(expr) => {
+            Expr::Break(_, expr) => {
                 let (_, end, wb) = stk
                     .last()
                     .cloned()
@@ -174,59 +278,36 @@ impl Expr {
                 ctx.jump(end);
                 Ok(Tmp::Caller(self))
             }
-            Expr::Continue => {
+            Expr::Continue(_) => {
                 let (start, _, _) = stk.last().ok_or(Error::OutsideLoop(self.clone()))?;
                 ctx.jump(*start);
                 Ok(Tmp::Caller(self))
             }
-            Expr::For(pat, expr, block) => {
+            Expr::For(span, pat, expr, block) => {
                 let header = ctx.label();
                 let body = ctx.label();
                 let end = ctx.label();
 
                 let iterable = expr.transform(map, interner, ctx, stk)?.var()?;
 
-                let length = ctx.var();
-                ctx.push(Instruction::Unpack(length, iterable, 0));
-
-                let i = {
-                    let var = ctx.var();
-                    ctx.push(Instruction::Load(var, Const::Int(0)));
-                    let id = ctx.id();
-                    ctx.define(ctx.cursor, id, var);
-                    id
-                };
-
-                let one = ctx.var();
-                ctx.push(Instruction::Load(one, Const::Int(1)));
+                let iter = ctx.var();
+                ctx.push_at(*span, Instruction::Iter(iter, iterable));
 
                 ctx.jump(header);
 
                 ctx.cursor = header;
                 let cond = ctx.var();
-                let instruction = Instruction::Binary(
-                    cond,
-                    ctx.lookup(ctx.cursor, i).unwrap(),
-                    BinOp::Lt,
-                    length,
-                );
-                ctx.push(instruction);
-                ctx.branch(cond, body, end);
+                ctx.push_at(*span, Instruction::Has(cond, iter));
+                ctx.branch(*span, cond, body, end);
                 ctx.seal(body);
 
                 ctx.cursor = body;
                 stk.push((header, end, None));
 
                 let element = ctx.var();
-                let index = ctx.lookup(ctx.cursor, i).unwrap();
-                let instruction = Instruction::Index(element, iterable, index);
-                ctx.push(instruction);
+                ctx.push_at(*span, Instruction::Next(element, iter));
 
-                let var = ctx.var();
-                ctx.push(Instruction::Binary(var, index, BinOp::Add, one));
-                ctx.define(ctx.cursor, i, var);
-
-                pat.transform(ctx, &None, element)?;
+                pat.transform(ctx, *span, &None, element)?;
                 block.transform(map, interner, ctx, stk)?;
 
                 stk.pop();
@@ -237,14 +318,14 @@ impl Expr {
                 ctx.cursor = end;
                 Ok(Tmp::Caller(self))
             }
-            Expr::If(expr, block, alter) => {
+            Expr::If(span, expr, block, alter) => {
                 let then = ctx.label();
                 let otherwise = ctx.label();
                 let join = ctx.label();
                 let ret = ctx.id();
 
                 let cond = expr.transform(map, interner, ctx, stk)?.var()?;
-                ctx.branch(cond, then, otherwise);
+                ctx.branch(*span, cond, then, otherwise);
                 ctx.seal(then);
                 ctx.seal(otherwise);
 
@@ -277,7 +358,7 @@ impl Expr {
                     Ok(Tmp::Caller(self))
                 }
             }
-            Expr::Loop(block) => {
+            Expr::Loop(_, block) => {
                 let body = ctx.label();
                 let end = ctx.label();
                 let ret = ctx.id();
@@ -301,13 +382,83 @@ impl Expr {
                     Ok(Tmp::Caller(self))
                 }
             }
-            Expr::Return(expr) => {
+            Expr::Match(_, expr, arms) => {
+                let join = ctx.label();
+                let ret = ctx.id();
+
+                let src = expr.transform(map, interner, ctx, stk)?.var()?;
+
+                let mut returned = false;
+                let mut complete = true;
+                for Arm(span, case, guard, expr) in arms.iter() {
+                    let Link::Shadow(shadows) = map.get(span).unwrap() else {
+                        unreachable!()
+                    };
+                    let next = ctx.label();
+
+                    let mut priors = Vec::new();
+                    for id in shadows {
+                        let id = Id::Interned(*id);
+                        priors.push((id, ctx.lookup(ctx.cursor, id).unwrap()));
+                    }
+
+                    let bindings = case.transform(map, interner, ctx, *span, src, next)?;
+                    for (id, var) in bindings {
+                        ctx.define(ctx.cursor, Id::Interned(id), var);
+                    }
+
+                    if let Some(guard) = guard {
+                        let pass = ctx.label();
+                        let reject = ctx.label();
+                        let cond = guard.transform(map, interner, ctx, stk)?.var()?;
+                        ctx.branch(*span, cond, pass, reject);
+                        ctx.seal(pass);
+                        ctx.seal(reject);
+
+                        ctx.cursor = reject;
+                        for (id, var) in priors.iter() {
+                            ctx.define(ctx.cursor, *id, *var);
+                        }
+                        ctx.jump(next);
+
+                        ctx.cursor = pass;
+                    }
+
+                    let tmp = expr.transform(map, interner, ctx, stk)?;
+                    if let Tmp::Var(var) = tmp {
+                        ctx.define(ctx.cursor, ret, var);
+                    }
+                    for (id, var) in priors.iter() {
+                        ctx.define(ctx.cursor, *id, *var);
+                    }
+                    if ctx.jump(join) {
+                        returned |= matches!(tmp, Tmp::Var(_));
+                        complete &= matches!(tmp, Tmp::Var(_));
+                    }
+                    ctx.seal(next);
+
+                    ctx.cursor = next;
+                }
+                if ctx.jump(join) {
+                    complete = false;
+                }
+                ctx.seal(join);
+
+                ctx.cursor = join;
+                if returned && complete {
+                    let var = ctx.lookup(ctx.cursor, ret).unwrap();
+                    Ok(Tmp::Var(var))
+                } else {
+                    Ok(Tmp::Caller(self))
+                }
+            }
+            Expr::Return(_, expr) => {
                 let var = expr.transform(map, interner, ctx, stk)?.var()?;
                 ctx.define(ctx.cursor, Id::Ret, var);
                 ctx.jump(Label::Exit);
                 Ok(Tmp::Caller(self))
             }
-            Expr::While(expr, block) => {
+            Expr::While(span, expr, block) => {
                 let header = ctx.label();
                 let body = ctx.label();
                 let end = ctx.label();
@@ -316,7 +467,7 @@ impl Expr {
 
                 ctx.cursor = header;
                 let cond = expr.transform(map, interner, ctx, stk)?.var()?;
-                ctx.branch(cond, body, end);
+                ctx.branch(*span, cond, body, end);
                 ctx.seal(body);
 
                 ctx.cursor = body;
@@ -330,14 +481,43 @@ impl Expr {
                 ctx.cursor = end;
                 Ok(Tmp::Caller(self))
             }
-            Expr::Binary(lhs, op, rhs) => {
+            Expr::Binary(span, lhs, op @ (BinOp::And | BinOp::Or), rhs) => {
+                let eager = ctx.label();
+                let short = ctx.label();
+                let join = ctx.label();
+                let ret = ctx.id();
+
                 let l = lhs.transform(map, interner, ctx, stk)?.var()?;
+                match op {
+                    BinOp::And => ctx.branch(*span, l, eager, short),
+                    _ => ctx.branch(*span, l, short, eager),
+                }
+                ctx.seal(eager);
+                ctx.seal(short);
+
+                ctx.cursor = eager;
                 let r = rhs.transform(map, interner, ctx, stk)?.var()?;
                 let var = ctx.var();
-                ctx.push(Instruction::Binary(var, l, *op, r));
+                ctx.push_at(*span, Instruction::Binary(var, l, *op, r));
+                ctx.define(ctx.cursor, ret, var);
+                ctx.jump(join);
+
+                ctx.cursor = short;
+                ctx.define(ctx.cursor, ret, l);
+                ctx.jump(join);
+                ctx.seal(join);
+
+                ctx.cursor = join;
+                Ok(Tmp::Var(ctx.lookup(ctx.cursor, ret).unwrap()))
+            }
+            Expr::Binary(span, lhs, op, rhs) => {
+                let l = lhs.transform(map, interner, ctx, stk)?.var()?;
+                let r = rhs.transform(map, interner, ctx, stk)?.var()?;
+                let var = ctx.var();
+                ctx.push_at(*span, Instruction::Binary(var, l, *op, r));
                 Ok(Tmp::Var(var))
             }
-            Expr::Call(expr, args) => {
+            Expr::Call(span, expr, args) => {
                 let callable = expr.transform(map, interner, ctx, stk)?.var()?;
                 let mut params = Vec::new();
                 if let Some(args) = args {
@@ -347,16 +527,26 @@ impl Expr {
                     }
                 }
                 let var = ctx.var();
-                ctx.push(Instruction::Call(var, callable, params));
+                ctx.push_at(*span, Instruction::Call(var, callable, params));
                 Ok(Tmp::Var(var))
             }
-            Expr::Field(expr, id) => {
+            Expr::Field(span, expr, id) => {
                 let src = expr.transform(map, interner, ctx, stk)?.var()?;
                 let var = ctx.var();
-                ctx.push(Instruction::Field(var, src, *id));
+                ctx.push_at(*span, Instruction::Field(var, src, *id));
                 Ok(Tmp::Var(var))
             }
-            Expr::Method(expr, id, args) => {
+            Expr::With(span, expr, updates) => {
+                let mut src = expr.transform(map, interner, ctx, stk)?.var()?;
+                for (id, value) in updates.iter() {
+                    let value = value.transform(map, interner, ctx, stk)?.var()?;
+                    let var = ctx.var();
+                    ctx.push_at(*span, Instruction::Update(var, src, *id, value));
+                    src = var;
+                }
+                Ok(Tmp::Var(src))
+            }
+            Expr::Method(span, expr, id, args) => {
                 let src = expr.transform(map, interner, ctx, stk)?.var()?;
                 let mut params = Vec::new();
                 if let Some(args) = args {
@@ -366,27 +556,45 @@ impl Expr {
                     }
                 }
                 let var = ctx.var();
-                ctx.push(Instruction::Method(var, src, *id, params));
+                ctx.push_at(*span, Instruction::Method(var, src, *id, params));
                 Ok(Tmp::Var(var))
             }
-            Expr::Index(expr, index) => {
+            Expr::Index(span, expr, index) => {
                 let src = expr.transform(map, interner, ctx, stk)?.var()?;
                 let idx = index.transform(map, interner, ctx, stk)?.var()?;
                 let var = ctx.var();
-                ctx.push(Instruction::Index(var, src, idx));
+                ctx.push_at(*span, Instruction::Index(var, src, idx));
                 Ok(Tmp::Var(var))
             }
-            Expr::Tuple(args) => {
+            Expr::Try(span, expr) => {
+                let fail = ctx.label();
+                let pass = ctx.label();
+
+                let src = expr.transform(map, interner, ctx, stk)?.var()?;
+                let cond = ctx.var();
+                ctx.push_at(*span, Instruction::Check(cond, src));
+                ctx.branch(*span, cond, fail, pass);
+                ctx.seal(fail);
+                ctx.seal(pass);
+
+                ctx.cursor = fail;
+                ctx.define(ctx.cursor, Id::Ret, src);
+                ctx.jump(Label::Exit);
+
+                ctx.cursor = pass;
+                Ok(Tmp::Var(src))
+            }
+            Expr::Tuple(span, args) => {
                 let mut params = Vec::new();
                 for arg in args.iter() {
                     let param = arg.transform(map, interner, ctx, stk)?.var()?;
                     params.push(param);
                 }
                 let var = ctx.var();
-                ctx.push(Instruction::Tuple(var, params));
+                ctx.push_at(*span, Instruction::Tuple(var, params));
                 Ok(Tmp::Var(var))
             }
-            Expr::List(args) => {
+            Expr::List(span, args) => {
                 let mut params = Vec::new();
                 if let Some(args) = args {
                     for arg in args.iter() {
@@ -395,24 +603,68 @@ impl Expr {
                     }
                 }
                 let var = ctx.var();
-                ctx.push(Instruction::List(var, params));
+                ctx.push_at(*span, Instruction::List(var, params));
                 Ok(Tmp::Var(var))
             }
-            Expr::Lit(lit) => lit.transform(interner, ctx).map(|x| match x {
+            Expr::Map(span, entries) => {
+                let mut params = Vec::new();
+                if let Some(entries) = entries {
+                    for (key, value) in entries.iter() {
+                        let key = key.transform(map, interner, ctx, stk)?.var()?;
+                        let value = value.transform(map, interner, ctx, stk)?.var()?;
+                        params.extend([key, value]);
+                    }
+                }
+                let var = ctx.var();
+                ctx.push_at(*span, Instruction::Map(var, params));
+                Ok(Tmp::Var(var))
+            }
+            Expr::Lit(span, lit) => lit.transform(*span, interner, ctx).map(|x| match x {
                 Some(var) => Tmp::Var(var),
                 None => Tmp::Caller(self),
             }),
-            Expr::Paren(expr) => expr.transform(map, interner, ctx, stk),
-            Expr::Unary(op, expr) => {
+            Expr::Format(span, segments) => {
+                let mut parts = Vec::new();
+                let mut chunks = Vec::new();
+                for segment in segments {
+                    match segment {
+                        Segment::Chunk(chunk) => chunks.push(chunk.clone()),
+                        Segment::Expr(expr) => {
+                            if !chunks.is_empty() {
+                                let lit = Lit::Str(std::mem::take(&mut chunks));
+                                parts.push(lit.transform(*span, interner, ctx)?.unwrap());
+                            }
+                            let src = expr.transform(map, interner, ctx, stk)?.var()?;
+                            let var = ctx.var();
+                            ctx.push_at(*span, Instruction::Unary(var, UnaOp::Fmt, src));
+                            parts.push(var);
+                        }
+                    }
+                }
+                if !chunks.is_empty() || parts.is_empty() {
+                    let lit = Lit::Str(chunks);
+                    parts.push(lit.transform(*span, interner, ctx)?.unwrap());
+                }
+                let mut iter = parts.into_iter();
+                let mut acc = iter.next().unwrap();
+                for part in iter {
+                    let var = ctx.var();
+                    ctx.push_at(*span, Instruction::Binary(var, acc, BinOp::Add, part));
+                    acc = var;
+                }
+                Ok(Tmp::Var(acc))
+            }
+            Expr::Paren(_, expr) => expr.transform(map, interner, ctx, stk),
+            Expr::Unary(span, op, expr) => {
                 let i = expr.transform(map, interner, ctx, stk)?.var()?;
                 let var = ctx.var();
-                ctx.push(Instruction::Unary(var, *op, i));
+                ctx.push_at(*span, Instruction::Unary(var, *op, i));
                 Ok(Tmp::Var(var))
             }
-            Expr::Path(i, path) => {
-                let var = if let Some((pt, ptr)) = map.get(i).unwrap() {
+            Expr::Path(span, path) => {
+                let var = if let Link::Item(pt, ptr) = map.get(span).unwrap() {
                     let var = ctx.var();
-                    ctx.push(Instruction::Pointer(var, *pt, *ptr));
+                    ctx.push_at(*span, Instruction::Pointer(var, *pt, *ptr));
                     var
                 } else {
                     let id = Id::Interned(path.buffer()[0]);
@@ -420,15 +672,33 @@ impl Expr {
                 };
                 Ok(Tmp::Var(var))
             }
+            Expr::Closure(span, _, _) => {
+                let Link::Closure(ptr, captures) = map.get(span).unwrap() else {
+                    unreachable!()
+                };
+                let mut params = Vec::new();
+                for id in captures {
+                    let param = ctx.lookup(ctx.cursor, Id::Interned(*id)).unwrap();
+                    params.push(param);
+                }
+                let var = ctx.var();
+                ctx.push_at(*span, Instruction::Closure(var, *ptr, params));
+                Ok(Tmp::Var(var))
+            }
         }
     }
 }
 
 impl Lit {
-    fn transform(&self, interner: &Interner, ctx: &mut Context) -> Result<Option<Var>, Error> {
+    fn transform(
+        &self,
+        span: usize,
+        interner: &Interner,
+        ctx: &mut Context,
+    ) -> Result<Option<Var>, Error> {
         let var = ctx.var();
         if let Some(c) = ctx.consts.get(self) {
-            ctx.push(Instruction::Load(var, c.clone()));
+            ctx.push_at(span, Instruction::Load(var, c.clone()));
             return Ok(var.into());
         }
         let c = match self {
@@ -449,6 +719,23 @@ impl Lit {
                     .to_bits();
                 Const::Float(value)
             }
+            Lit::I64(x) => {
+                let value = interner
+                    .resolve(x)
+                    .unwrap()
+                    .parse()
+                    .map_err(|_| Error::InvalidInt(self.clone()))?;
+                Const::I64(value)
+            }
+            Lit::F64(x) => {
+                let value = interner
+                    .resolve(x)
+                    .unwrap()
+                    .parse::<f64>()
+                    .map_err(|_| Error::InvalidFloat(self.clone()))?
+                    .to_bits();
+                Const::F64(value)
+            }
             Lit::Bool(x) => match x {
                 Bool::True => Const::Bool(true),
                 Bool::False => Const::Bool(false),
@@ -478,6 +765,8 @@ impl Lit {
                                 "t" => '\t',
                                 "r" => '\r',
                                 "\\" => '\\',
+                                "{" => '{',
+                                "}" => '}',
                                 _ => return Err(Error::InvalidStrChunk(chunk.clone())),
                             };
                             value.push(c)
@@ -488,7 +777,7 @@ impl Lit {
             }
         };
         ctx.consts.insert(self.clone(), c.clone());
-        ctx.push(Instruction::Load(var, c));
+        ctx.push_at(span, Instruction::Load(var, c));
         Ok(var.into())
     }
 }
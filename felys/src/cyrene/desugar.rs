@@ -1,41 +1,103 @@
 use crate::cyrene::error::Error;
 use crate::philia093::Interner;
-use crate::utils::ast::{Block, Impl, Item};
+use crate::utils::ast::{Block, Expr, Impl, Item};
+use crate::utils::function::Pointer;
 use crate::utils::group::Group;
 use crate::utils::namespace::Namespace;
-use crate::utils::stages::{I, II};
+use crate::utils::stages::{I, II, III};
+use crate::utils::stdlib::registry::Registry;
 use std::collections::HashMap;
 
 impl I {
-    pub fn desugar(self) -> Result<II, String> {
+    pub fn declare(mut self, unit: &III) -> I {
+        for (symbol, pt, _) in unit.exports.iter() {
+            self.externs.push((symbol.clone(), *pt));
+        }
+        self
+    }
+
+    pub fn desugar(self, registry: &Registry) -> Result<II, String> {
         let mut interner = self.interner;
-        let mut namespace = Namespace::init(&mut interner);
+        let mut namespace =
+            Namespace::init(&mut interner, registry).map_err(|e| e.recover(&interner))?;
         let mut functions = HashMap::new();
         let mut groups = HashMap::new();
+        let mut uses = Vec::new();
+        let mut exports = Vec::new();
+
+        let mut externs = self.externs;
+        externs.sort_by_key(|(_, pt)| *pt != Pointer::Group);
+        let mut imports = Vec::new();
+        for (symbol, pt) in externs {
+            let mut path = symbol
+                .split("::")
+                .map(|x| interner.intern(x))
+                .collect::<Vec<_>>();
+            let name = path.pop().unwrap();
+            let id = match pt {
+                Pointer::Group => namespace.allocate(&path, name, None),
+                _ => namespace.attach(&path, name),
+            };
+            let Some(id) = id else {
+                return Err(Error::RedeclaredSymbol(symbol).recover(&interner));
+            };
+            imports.push((symbol, pt, id));
+        }
+
+        let mut modules = vec![(0, self.root)];
+        for (path, root) in self.modules {
+            modules.push((namespace.scope(path), root));
+        }
+
+        for (scope, root) in modules.iter() {
+            for item in root.0.iter() {
+                item.allocate(
+                    *scope,
+                    &interner,
+                    &mut namespace,
+                    &mut groups,
+                    &mut uses,
+                    &mut exports,
+                )
+                .map_err(|e| e.recover(&interner))?;
+            }
+        }
+
+        let mut main = None;
+        for (scope, root) in modules {
+            for item in root.0.into_iter() {
+                item.attach(
+                    scope,
+                    &mut interner,
+                    &mut namespace,
+                    &mut functions,
+                    &mut groups,
+                    &mut main,
+                )
+                .map_err(|e| e.recover(&interner))?;
+            }
+        }
 
-        for item in self.root.0.iter() {
-            item.allocate(&mut namespace, &mut groups)
+        for (scope, item) in uses {
+            item.verify(scope, &namespace)
                 .map_err(|e| e.recover(&interner))?;
         }
 
-        let mut main = Err(Error::MainNotFound);
-        for item in self.root.0.into_iter() {
-            item.attach(
-                &mut interner,
-                &mut namespace,
-                &mut functions,
-                &mut groups,
-                &mut main,
-            )
-            .map_err(|e| e.recover(&interner))?;
+        for (ptr, (name, _, _, _)) in functions.iter() {
+            exports.push((name.clone(), Pointer::Function, *ptr));
         }
+        exports.sort_by(|(x, _, _), (y, _, _)| x.cmp(y));
 
         Ok(II {
             namespace,
             groups,
             functions,
-            main: main.map_err(|e| e.recover(&interner))?,
+            main,
+            exports,
+            imports,
             interner,
+            registry: registry.clone(),
+            source: self.source,
         })
     }
 }
@@ -43,57 +105,83 @@ impl I {
 impl Item {
     fn allocate(
         &self,
+        scope: usize,
+        interner: &Interner,
         namespace: &mut Namespace,
         groups: &mut HashMap<usize, Group>,
+        uses: &mut Vec<(usize, Item)>,
+        exports: &mut Vec<(String, Pointer, usize)>,
     ) -> Result<(), Error> {
-        if let Item::Group(id, fields) = self {
-            let gp = namespace
-                .allocate(&[], *id)
-                .ok_or(Error::RedeclaredItem(self.clone()))?;
-            let group = Group::new(fields.iter());
-            groups.insert(gp, group);
+        match self {
+            Item::Group(id, fields) => {
+                let prefix = namespace.prefix(scope).to_vec();
+                let gp = namespace
+                    .allocate(&prefix, *id, Some(fields.iter().count()))
+                    .ok_or(Error::RedeclaredItem(self.clone()))?;
+                let name = qualify(&prefix, *id, interner);
+                groups.insert(gp, Group::new(name.clone(), fields.iter()));
+                exports.push((name, Pointer::Group, gp));
+            }
+            Item::Use(path) => {
+                if !namespace.import(scope, path.vec()) {
+                    return Err(Error::RedeclaredItem(self.clone()));
+                }
+                uses.push((scope, self.clone()));
+            }
+            Item::Mod(path) => {
+                if !namespace.loaded(&path.vec()) {
+                    return Err(Error::ModuleNotFound(path.vec()));
+                }
+            }
+            Item::Impl(_, _) | Item::Fn(_, _, _) | Item::Main(_, _) => {}
         }
         Ok(())
     }
 
     fn attach(
         self,
+        scope: usize,
         interner: &mut Interner,
         namespace: &mut Namespace,
-        functions: &mut HashMap<usize, (Vec<usize>, Block)>,
+        functions: &mut HashMap<usize, (String, usize, Vec<usize>, Block)>,
         groups: &mut HashMap<usize, Group>,
-        main: &mut Result<(usize, Block), Error>,
+        main: &mut Option<(usize, Block)>,
     ) -> Result<(), Error> {
-        let ptr = self.ptr(namespace, main)?;
+        let ptr = self.ptr(scope, namespace, main)?;
         match self {
-            Item::Group(_, _) => {}
+            Item::Group(_, _) | Item::Use(_) | Item::Mod(_) => {}
             Item::Impl(id, impls) => {
                 for implementation in impls.into_iter() {
-                    implementation.attach(id, interner, namespace, functions, groups)?;
+                    implementation.attach(id, scope, interner, namespace, functions, groups)?;
                 }
             }
-            Item::Fn(_, args, block) => {
+            Item::Fn(id, args, block) => {
+                let name = qualify(namespace.prefix(scope), id, interner);
                 let args = args.map(|x| x.vec()).unwrap_or_default();
-                functions.insert(ptr.unwrap(), (args, block));
+                functions.insert(ptr.unwrap(), (name, scope, args, block));
             }
-            Item::Main(args, block) => *main = Ok((args, block)),
+            Item::Main(args, block) => *main = Some((args, block)),
         }
         Ok(())
     }
 
     fn ptr(
         &self,
+        scope: usize,
         namespace: &mut Namespace,
-        main: &mut Result<(usize, Block), Error>,
+        main: &mut Option<(usize, Block)>,
     ) -> Result<Option<usize>, Error> {
         match self {
-            Item::Group(_, _) | Item::Impl(_, _) => Ok(None),
-            Item::Fn(id, _, _) => namespace
-                .attach(&[], *id)
-                .ok_or(Error::RedeclaredItem(self.clone()))
-                .map(Some),
+            Item::Group(_, _) | Item::Impl(_, _) | Item::Use(_) | Item::Mod(_) => Ok(None),
+            Item::Fn(id, _, _) => {
+                let prefix = namespace.prefix(scope).to_vec();
+                namespace
+                    .attach(&prefix, *id)
+                    .ok_or(Error::RedeclaredItem(self.clone()))
+                    .map(Some)
+            }
             Item::Main(_, _) => {
-                if main.is_ok() {
+                if main.is_some() || scope != 0 {
                     Err(Error::RedeclaredItem(self.clone()))
                 } else {
                     Ok(None)
@@ -101,38 +189,75 @@ impl Item {
             }
         }
     }
+
+    fn verify(&self, scope: usize, namespace: &Namespace) -> Result<(), Error> {
+        let Item::Use(path) = self else {
+            return Ok(());
+        };
+        let target = path.vec();
+        if !namespace.exists(&target) {
+            return Err(Error::InvalidPath(Expr::Path(0, path.clone())));
+        }
+        let mut local = namespace.prefix(scope).to_vec();
+        local.push(*path.iter().last().unwrap());
+        if local != target && namespace.exists(&local) {
+            return Err(Error::RedeclaredItem(self.clone()));
+        }
+        Ok(())
+    }
 }
 
 impl Impl {
     fn attach(
         self,
         id: usize,
+        scope: usize,
         interner: &mut Interner,
         namespace: &mut Namespace,
-        functions: &mut HashMap<usize, (Vec<usize>, Block)>,
+        functions: &mut HashMap<usize, (String, usize, Vec<usize>, Block)>,
         groups: &mut HashMap<usize, Group>,
     ) -> Result<(), Error> {
-        let ptr = self.ptr(id, namespace)?;
+        let mut path = namespace.prefix(scope).to_vec();
+        path.push(id);
+        let ptr = self.ptr(&path, namespace)?;
+        let gp = namespace
+            .get(0, path.iter())
+            .map(|(_, gp)| gp)
+            .filter(|gp| groups.contains_key(gp));
+        if matches!(self, Impl::Method(_, _, _)) && gp.is_none() {
+            return Err(Error::ExternalImpl(self));
+        }
+        let name = match &self {
+            Impl::Associated(x, _, _) | Impl::Method(x, _, _) => qualify(&path, *x, interner),
+        };
         match self {
             Impl::Associated(_, args, block) => {
                 let args = args.map(|x| x.vec()).unwrap_or_default();
-                functions.insert(ptr, (args, block));
+                functions.insert(ptr, (name, scope, args, block));
             }
             Impl::Method(secondary, mut args, block) => {
                 args.insert(0, interner.intern("self"));
-                functions.insert(ptr, (args, block));
-                let (_, gp) = namespace.get([id].iter()).unwrap();
-                groups.get_mut(&gp).unwrap().attach(secondary, ptr);
+                functions.insert(ptr, (name, scope, args, block));
+                groups.get_mut(&gp.unwrap()).unwrap().attach(secondary, ptr);
             }
         }
         Ok(())
     }
 
-    fn ptr(&self, id: usize, namespace: &mut Namespace) -> Result<usize, Error> {
+    fn ptr(&self, path: &[usize], namespace: &mut Namespace) -> Result<usize, Error> {
         match self {
             Impl::Associated(x, _, _) | Impl::Method(x, _, _) => namespace
-                .attach(&[id], *x)
+                .attach(path, *x)
                 .ok_or(Error::RedeclaredImpl(self.clone())),
         }
     }
 }
+
+fn qualify(prefix: &[usize], id: usize, interner: &Interner) -> String {
+    prefix
+        .iter()
+        .chain([id].iter())
+        .map(|x| interner.resolve(x).unwrap())
+        .collect::<Vec<_>>()
+        .join("::")
+}
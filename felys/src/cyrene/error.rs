@@ -1,5 +1,5 @@
 use crate::philia093::Interner;
-use crate::utils::ast::{Block, Chunk, Expr, Impl, Item, Lit};
+use crate::utils::ast::{Block, Case, Chunk, Expr, Impl, Item, Lit};
 
 pub enum Error {
     MainNotFound,
@@ -12,8 +12,16 @@ pub enum Error {
     NoReturnValue(Expr),
     RedeclaredItem(Item),
     RedeclaredImpl(Impl),
+    ExternalImpl(Impl),
     VariableNotDefined(usize),
+    RedeclaredVariable(usize),
+    RedeclaredField(Expr),
+    PatternArity(Case),
     InvalidPath(Expr),
+    ModuleNotFound(Vec<usize>),
+    CyclicModule(Vec<Vec<usize>>),
+    RedeclaredSymbol(String),
+    ConflictingNative(String),
 }
 
 impl Error {
@@ -77,16 +85,67 @@ impl Error {
                     .recover(&mut msg, ERROR, 0, interner)
                     .unwrap();
             }
+            Error::ExternalImpl(implementation) => {
+                msg.push_str("this method cannot be attached to an external group\n");
+                msg.push_str(ERROR);
+                implementation
+                    .recover(&mut msg, ERROR, 0, interner)
+                    .unwrap();
+            }
             Error::VariableNotDefined(id) => {
                 msg.push_str("this variable is not defined\n");
                 msg.push_str(ERROR);
                 msg.push_str(interner.resolve(&id).unwrap())
             }
+            Error::RedeclaredVariable(id) => {
+                msg.push_str("this variable is bound more than once in a pattern\n");
+                msg.push_str(ERROR);
+                msg.push_str(interner.resolve(&id).unwrap())
+            }
+            Error::RedeclaredField(expr) => {
+                msg.push_str("this field is updated more than once\n");
+                msg.push_str(ERROR);
+                expr.recover(&mut msg, ERROR, 0, interner).unwrap();
+            }
+            Error::PatternArity(case) => {
+                msg.push_str("this pattern does not match the number of fields in the group\n");
+                msg.push_str(ERROR);
+                case.recover(&mut msg, interner).unwrap();
+            }
             Error::InvalidPath(path) => {
                 msg.push_str("this path does not lead to anywhere\n");
                 msg.push_str(ERROR);
                 path.recover(&mut msg, ERROR, 0, interner).unwrap();
             }
+            Error::ModuleNotFound(path) => {
+                msg.push_str("this module cannot be loaded\n");
+                msg.push_str(ERROR);
+                msg.push_str(&join(&path, interner));
+                msg.push('\n');
+                tailing = false;
+            }
+            Error::RedeclaredSymbol(symbol) => {
+                msg.push_str("this external symbol is redeclared\n");
+                msg.push_str(ERROR);
+                msg.push_str(&symbol);
+                msg.push('\n');
+                tailing = false;
+            }
+            Error::ConflictingNative(symbol) => {
+                msg.push_str("this native symbol conflicts with another native\n");
+                msg.push_str(ERROR);
+                msg.push_str(&symbol);
+                msg.push('\n');
+                tailing = false;
+            }
+            Error::CyclicModule(cycle) => {
+                msg.push_str("these modules import each other in a cycle\n");
+                msg.push_str(ERROR);
+                let cycle = cycle.iter().map(|x| join(x, interner)).collect::<Vec<_>>();
+                msg.push_str(&cycle.join(" -> "));
+                msg.push('\n');
+                tailing = false;
+            }
         }
         if tailing {
             msg.push_str("\nNote: ast recovery does not reflect the raw code\n");
@@ -94,3 +153,10 @@ impl Error {
         msg
     }
 }
+
+fn join(path: &[usize], interner: &Interner) -> String {
+    path.iter()
+        .map(|x| interner.resolve(x).unwrap())
+        .collect::<Vec<_>>()
+        .join("::")
+}
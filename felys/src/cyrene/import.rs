@@ -0,0 +1,90 @@
+use crate::cyrene::error::Error;
+use crate::philia093::{Interner, PhiLia093};
+use crate::utils::ast::{Item, Root};
+use crate::utils::stages::I;
+use std::collections::HashMap;
+
+pub trait Loader {
+    fn load(&mut self, path: &str) -> Option<String>;
+}
+
+impl Loader for HashMap<String, String> {
+    fn load(&mut self, path: &str) -> Option<String> {
+        self.get(path).cloned()
+    }
+}
+
+struct Importer<'a, L: Loader> {
+    loader: &'a mut L,
+    interner: Interner,
+    source: String,
+    stack: Vec<Vec<usize>>,
+    modules: Vec<(Vec<usize>, Root)>,
+}
+
+impl I {
+    pub fn import(self, loader: &mut impl Loader) -> Result<I, String> {
+        let mut importer = Importer {
+            loader,
+            interner: self.interner,
+            source: self.source,
+            stack: Vec::new(),
+            modules: self.modules,
+        };
+        for item in self.root.0.iter() {
+            if let Item::Mod(path) = item {
+                importer.visit(path.vec())?;
+            }
+        }
+        Ok(I {
+            root: self.root,
+            modules: importer.modules,
+            externs: self.externs,
+            interner: importer.interner,
+            source: importer.source,
+        })
+    }
+}
+
+impl<L: Loader> Importer<'_, L> {
+    fn visit(&mut self, path: Vec<usize>) -> Result<(), String> {
+        if self.modules.iter().any(|(x, _)| *x == path) {
+            return Ok(());
+        }
+        if let Some(i) = self.stack.iter().position(|x| *x == path) {
+            let mut cycle = self.stack.split_off(i);
+            cycle.push(path);
+            return Err(Error::CyclicModule(cycle).recover(&self.interner));
+        }
+
+        let name = path
+            .iter()
+            .map(|x| self.interner.resolve(x).unwrap())
+            .collect::<Vec<_>>()
+            .join("::");
+        let code = self
+            .loader
+            .load(&name)
+            .ok_or_else(|| Error::ModuleNotFound(path.clone()).recover(&self.interner))?;
+
+        let cursor = self.source.len() + 1;
+        self.source.push('\n');
+        self.source.push_str(&code);
+        let interner = std::mem::replace(&mut self.interner, Interner::new(0));
+        let i = PhiLia093::from(self.source.clone())
+            .resume(interner, cursor)
+            .parse()
+            .map_err(|e| format!("{name}: {e}"))?;
+        self.interner = i.interner;
+
+        self.stack.push(path.clone());
+        for item in i.root.0.iter() {
+            if let Item::Mod(next) = item {
+                self.visit(next.vec())?;
+            }
+        }
+        self.stack.pop();
+        self.modules.push((path, i.root));
+        Ok(())
+    }
+}
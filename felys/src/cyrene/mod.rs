@@ -1,4 +1,5 @@
 mod cfg;
 mod desugar;
-mod error;
+pub mod error;
+pub mod import;
 mod resolver;
@@ -1,15 +1,27 @@
 use crate::cyrene::error::Error;
-use crate::utils::ast::{AssOp, Block, Expr, Pat, Stmt};
+use crate::utils::ast::{Arm, AssOp, Block, Case, Expr, Pat, Segment, Stmt};
 use crate::utils::function::Pointer;
 use crate::utils::namespace::Namespace;
 use std::collections::{HashMap, HashSet};
 
-pub type Map = HashMap<usize, Option<(Pointer, usize)>>;
+pub type Map = HashMap<usize, Link>;
+
+pub type Lambdas = Vec<(usize, Vec<usize>, Block)>;
+
+pub enum Link {
+    Local,
+    Item(Pointer, usize),
+    Closure(usize, Vec<usize>),
+    Shadow(Vec<usize>),
+}
 
 #[derive(Default)]
 struct Resolver {
+    module: usize,
     scope: Vec<HashSet<usize>>,
+    frames: Vec<(usize, Vec<usize>)>,
     map: Map,
+    lambdas: Lambdas,
 }
 
 impl Resolver {
@@ -25,37 +37,56 @@ impl Resolver {
         self.scope.last_mut().unwrap().insert(id);
     }
 
-    fn contains(&self, id: usize) -> bool {
-        for set in self.scope.iter().rev() {
+    fn contains(&mut self, id: usize) -> bool {
+        for (depth, set) in self.scope.iter().enumerate().rev() {
             if set.contains(&id) {
+                for (base, captures) in self.frames.iter_mut() {
+                    if depth < *base && !captures.contains(&id) {
+                        captures.push(id);
+                    }
+                }
                 return true;
             }
         }
         false
     }
 
-    fn link(&mut self, id: usize, ptr: Option<(Pointer, usize)>) {
-        self.map.insert(id, ptr);
+    fn enclose(&mut self) {
+        self.frames.push((self.scope.len(), Vec::new()));
+        self.stack();
+    }
+
+    fn disclose(&mut self) -> Vec<usize> {
+        self.unstack();
+        self.frames.pop().unwrap().1
+    }
+
+    fn link(&mut self, id: usize, link: Link) {
+        self.map.insert(id, link);
     }
 }
 
 impl Block {
     pub fn semantic<'a>(
         &self,
+        module: usize,
         args: impl Iterator<Item = &'a usize>,
-        namespace: &Namespace,
-    ) -> Result<Map, Error> {
-        let mut resolver = Resolver::default();
+        namespace: &mut Namespace,
+    ) -> Result<(Map, Lambdas), Error> {
+        let mut resolver = Resolver {
+            module,
+            ..Resolver::default()
+        };
         resolver.stack();
         for arg in args {
             resolver.define(*arg);
         }
         self.resolve(namespace, &mut resolver)?;
         resolver.unstack();
-        Ok(resolver.map)
+        Ok((resolver.map, resolver.lambdas))
     }
 
-    fn resolve(&self, namespace: &Namespace, resolver: &mut Resolver) -> Result<(), Error> {
+    fn resolve(&self, namespace: &mut Namespace, resolver: &mut Resolver) -> Result<(), Error> {
         for stmt in self.0.iter() {
             stmt.resolve(namespace, resolver)?;
         }
@@ -95,12 +126,60 @@ impl Pat {
     }
 }
 
+impl Case {
+    fn resolve(
+        &self,
+        namespace: &mut Namespace,
+        resolver: &mut Resolver,
+        bindings: &mut Vec<usize>,
+    ) -> Result<(), Error> {
+        match self {
+            Case::Any | Case::Lit(_) | Case::Neg(_) => {}
+            Case::Ident(id) => bind(bindings, *id)?,
+            Case::Tuple(cases) => {
+                for case in cases.iter() {
+                    case.resolve(namespace, resolver, bindings)?;
+                }
+            }
+            Case::Group(span, path, cases) => {
+                let Some((Pointer::Group, gp)) = namespace.get(resolver.module, path.iter()) else {
+                    return Err(Error::InvalidPath(Expr::Path(*span, path.clone())));
+                };
+                if namespace.arity(gp).is_some_and(|x| x != cases.len()) {
+                    return Err(Error::PatternArity(self.clone()));
+                }
+                resolver.link(*span, Link::Item(Pointer::Group, gp));
+                for case in cases.iter() {
+                    case.resolve(namespace, resolver, bindings)?;
+                }
+            }
+            Case::List(cases, rest) => {
+                for case in cases.iter() {
+                    case.resolve(namespace, resolver, bindings)?;
+                }
+                if let Some(Some(id)) = rest {
+                    bind(bindings, *id)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn bind(bindings: &mut Vec<usize>, id: usize) -> Result<(), Error> {
+    if bindings.contains(&id) {
+        return Err(Error::RedeclaredVariable(id));
+    }
+    bindings.push(id);
+    Ok(())
+}
+
 impl Stmt {
-    fn resolve(&self, namespace: &Namespace, resolver: &mut Resolver) -> Result<(), Error> {
+    fn resolve(&self, namespace: &mut Namespace, resolver: &mut Resolver) -> Result<(), Error> {
         match self {
             Stmt::Empty => {}
             Stmt::Expr(expr) | Stmt::Semi(expr) => expr.resolve(namespace, resolver)?,
-            Stmt::Assign(pat, op, expr) => {
+            Stmt::Assign(_, pat, op, expr) => {
                 expr.resolve(namespace, resolver)?;
                 if !matches!(op, AssOp::Eq) {
                     pat.unpack(resolver)?
@@ -113,27 +192,27 @@ impl Stmt {
 }
 
 impl Expr {
-    fn resolve(&self, namespace: &Namespace, resolver: &mut Resolver) -> Result<(), Error> {
+    fn resolve(&self, namespace: &mut Namespace, resolver: &mut Resolver) -> Result<(), Error> {
         match self {
-            Expr::Block(block) | Expr::Loop(block) => {
+            Expr::Block(_, block) | Expr::Loop(_, block) => {
                 resolver.stack();
                 block.resolve(namespace, resolver)?;
                 resolver.unstack();
             }
-            Expr::Break(expr) => {
+            Expr::Break(_, expr) => {
                 if let Some(expr) = expr {
                     expr.resolve(namespace, resolver)?;
                 }
             }
-            Expr::Continue => {}
-            Expr::For(pat, expr, block) => {
+            Expr::Continue(_) => {}
+            Expr::For(_, pat, expr, block) => {
                 expr.resolve(namespace, resolver)?;
                 resolver.stack();
                 pat.resolve(resolver)?;
                 block.resolve(namespace, resolver)?;
                 resolver.unstack();
             }
-            Expr::If(expr, block, otherwise) => {
+            Expr::If(_, expr, block, otherwise) => {
                 expr.resolve(namespace, resolver)?;
                 resolver.stack();
                 block.resolve(namespace, resolver)?;
@@ -142,21 +221,44 @@ impl Expr {
                     otherwise.resolve(namespace, resolver)?;
                 }
             }
-            Expr::Return(expr)
-            | Expr::Field(expr, _)
-            | Expr::Paren(expr)
-            | Expr::Unary(_, expr) => expr.resolve(namespace, resolver)?,
-            Expr::While(expr, block) => {
+            Expr::Match(_, expr, arms) => {
+                expr.resolve(namespace, resolver)?;
+                for Arm(span, case, guard, expr) in arms.iter() {
+                    let mut bindings = Vec::new();
+                    case.resolve(namespace, resolver, &mut bindings)?;
+                    let shadows = bindings
+                        .iter()
+                        .filter(|x| resolver.contains(**x))
+                        .copied()
+                        .collect();
+                    resolver.link(*span, Link::Shadow(shadows));
+                    resolver.stack();
+                    for id in bindings {
+                        resolver.define(id);
+                    }
+                    if let Some(guard) = guard {
+                        guard.resolve(namespace, resolver)?;
+                    }
+                    expr.resolve(namespace, resolver)?;
+                    resolver.unstack();
+                }
+            }
+            Expr::Return(_, expr)
+            | Expr::Field(_, expr, _)
+            | Expr::Paren(_, expr)
+            | Expr::Try(_, expr)
+            | Expr::Unary(_, _, expr) => expr.resolve(namespace, resolver)?,
+            Expr::While(_, expr, block) => {
                 expr.resolve(namespace, resolver)?;
                 resolver.stack();
                 block.resolve(namespace, resolver)?;
                 resolver.unstack();
             }
-            Expr::Binary(expr, _, other) | Expr::Index(expr, other) => {
+            Expr::Binary(_, expr, _, other) | Expr::Index(_, expr, other) => {
                 expr.resolve(namespace, resolver)?;
                 other.resolve(namespace, resolver)?;
             }
-            Expr::Call(expr, args) | Expr::Method(expr, _, args) => {
+            Expr::Call(_, expr, args) | Expr::Method(_, expr, _, args) => {
                 expr.resolve(namespace, resolver)?;
                 if let Some(args) = args {
                     for arg in args.iter() {
@@ -164,24 +266,66 @@ impl Expr {
                     }
                 }
             }
-            Expr::Tuple(args) => {
+            Expr::With(_, expr, updates) => {
+                expr.resolve(namespace, resolver)?;
+                let mut fields = Vec::new();
+                for (field, value) in updates.iter() {
+                    if fields.contains(field) {
+                        return Err(Error::RedeclaredField(self.clone()));
+                    }
+                    fields.push(*field);
+                    value.resolve(namespace, resolver)?;
+                }
+            }
+            Expr::Tuple(_, args) => {
                 for arg in args.iter() {
                     arg.resolve(namespace, resolver)?;
                 }
             }
-            Expr::List(args) => {
+            Expr::List(_, args) => {
                 if let Some(args) = args {
                     for arg in args.iter() {
                         arg.resolve(namespace, resolver)?;
                     }
                 }
             }
-            Expr::Lit(_) => {}
+            Expr::Map(_, entries) => {
+                if let Some(entries) = entries {
+                    for (key, value) in entries.iter() {
+                        key.resolve(namespace, resolver)?;
+                        value.resolve(namespace, resolver)?;
+                    }
+                }
+            }
+            Expr::Closure(span, params, expr) => {
+                let params = params.as_ref().map(|x| x.vec()).unwrap_or_default();
+                resolver.enclose();
+                for param in params.iter() {
+                    resolver.define(*param);
+                }
+                expr.resolve(namespace, resolver)?;
+                let captures = resolver.disclose();
+                if resolver.frames.is_empty() {
+                    let id = namespace.reserve();
+                    let args = captures.iter().chain(params.iter()).copied().collect();
+                    let block = Block(vec![Stmt::Expr(Expr::clone(expr))]);
+                    resolver.lambdas.push((id, args, block));
+                    resolver.link(*span, Link::Closure(id, captures));
+                }
+            }
+            Expr::Lit(_, _) => {}
+            Expr::Format(_, segments) => {
+                for segment in segments {
+                    if let Segment::Expr(expr) = segment {
+                        expr.resolve(namespace, resolver)?;
+                    }
+                }
+            }
             Expr::Path(i, path) => {
                 if path.len() == 1 && resolver.contains(path.buffer()[0]) {
-                    resolver.link(*i, None)
-                } else if let Some(ptr) = namespace.get(path.iter()) {
-                    resolver.link(*i, Some(ptr))
+                    resolver.link(*i, Link::Local)
+                } else if let Some((pt, ptr)) = namespace.get(resolver.module, path.iter()) {
+                    resolver.link(*i, Link::Item(pt, ptr))
                 } else {
                     return Err(Error::InvalidPath(self.clone()));
                 }
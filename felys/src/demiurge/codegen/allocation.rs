@@ -128,12 +128,21 @@ impl Instruction {
         match self {
             Instruction::Field(dst, src, _)
             | Instruction::Unpack(dst, src, _)
-            | Instruction::Unary(dst, _, src) => {
+            | Instruction::Unary(dst, _, src)
+            | Instruction::Check(dst, src)
+            | Instruction::Test(dst, src, _, _)
+            | Instruction::Tail(dst, src, _)
+            | Instruction::Iter(dst, src)
+            | Instruction::Has(dst, src)
+            | Instruction::Next(dst, src) => {
                 ctx.define(dst, index);
                 ctx.extend(src, index);
             }
 
-            Instruction::Binary(dst, lhs, _, rhs) | Instruction::Index(dst, lhs, rhs) => {
+            Instruction::Binary(dst, lhs, _, rhs)
+            | Instruction::Index(dst, lhs, rhs)
+            | Instruction::Nth(dst, lhs, rhs)
+            | Instruction::Update(dst, lhs, _, rhs) => {
                 ctx.define(dst, index);
                 ctx.extend(lhs, index);
                 ctx.extend(rhs, index);
@@ -145,7 +154,10 @@ impl Instruction {
                     ctx.extend(arg, index);
                 }
             }
-            Instruction::List(dst, args) | Instruction::Tuple(dst, args) => {
+            Instruction::List(dst, args)
+            | Instruction::Tuple(dst, args)
+            | Instruction::Map(dst, args)
+            | Instruction::Closure(dst, _, args) => {
                 ctx.define(dst, index);
                 for arg in args {
                     ctx.extend(arg, index);
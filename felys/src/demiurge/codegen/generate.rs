@@ -1,26 +1,40 @@
+use crate::cyrene::error::Error;
 use crate::demiurge::codegen::copies::Copy;
 use crate::philia093::Interner;
 use crate::utils::ast::Block;
 use crate::utils::bytecode::{Bytecode, Id, Index, Reg};
-use crate::utils::function::{Const, Function, Instruction, Label, Pointer, Terminator, Var};
+use crate::utils::function::{
+    Const, Function, Instruction, Label, Pointer, Shape, Terminator, Var,
+};
 use crate::utils::group::Group;
 use crate::utils::namespace::Namespace;
 use crate::utils::stages::{Callable, II, III};
+use crate::utils::stdlib::registry::{Native, Registry};
 use std::collections::HashMap;
 
 struct Context {
     data: Data,
+    natives: Natives,
     groups: Worker<Group>,
-    functions: Worker<(Vec<usize>, Block)>,
+    functions: Worker<(String, usize, Vec<usize>, Block)>,
 }
 
 impl Context {
-    fn new(groups: HashMap<usize, Group>, functions: HashMap<usize, (Vec<usize>, Block)>) -> Self {
+    fn new(
+        groups: HashMap<usize, Group>,
+        functions: HashMap<usize, (String, usize, Vec<usize>, Block)>,
+        registry: Registry,
+    ) -> Self {
         Self {
             data: Data {
                 pool: vec![],
                 fast: Default::default(),
             },
+            natives: Natives {
+                registry,
+                pool: vec![],
+                fast: Default::default(),
+            },
             groups: Worker {
                 indices: Default::default(),
                 source: groups,
@@ -34,6 +48,22 @@ impl Context {
         }
     }
 
+    fn eager(&mut self) {
+        for ptr in 0..self.natives.registry.iter().count() {
+            self.natives.index(ptr);
+        }
+        let mut groups = self.groups.source.keys().copied().collect::<Vec<_>>();
+        groups.sort();
+        for id in groups {
+            self.groups.get(id);
+        }
+        let mut functions = self.functions.source.keys().copied().collect::<Vec<_>>();
+        functions.sort();
+        for id in functions {
+            self.functions.get(id);
+        }
+    }
+
     fn done(&self) -> bool {
         self.groups.worklist.is_empty() && self.functions.worklist.is_empty()
     }
@@ -56,6 +86,24 @@ impl Data {
     }
 }
 
+struct Natives {
+    registry: Registry,
+    pool: Vec<(String, Native)>,
+    fast: HashMap<usize, usize>,
+}
+
+impl Natives {
+    fn index(&mut self, ptr: usize) -> usize {
+        if let Some(&id) = self.fast.get(&ptr) {
+            return id;
+        }
+        let id = self.pool.len();
+        self.fast.insert(ptr, id);
+        self.pool.push(self.registry.get(ptr).cloned().unwrap());
+        id
+    }
+}
+
 struct Worker<T> {
     indices: HashMap<usize, Index>,
     source: HashMap<usize, T>,
@@ -74,6 +122,12 @@ impl<T> Worker<T> {
         index
     }
 
+    fn reserve(&mut self, id: usize) -> Index {
+        let index = Index::try_from(self.indices.len()).unwrap();
+        self.indices.insert(id, index);
+        index
+    }
+
     fn pop(&mut self) -> Option<(Index, T)> {
         self.worklist.pop()
     }
@@ -81,18 +135,56 @@ impl<T> Worker<T> {
 
 impl II {
     pub fn codegen(self, limit: usize) -> Result<III, String> {
-        let mut context = Context::new(self.groups, self.functions);
+        self.generate(limit, Mode::Lazy)
+    }
+
+    pub fn object(self, limit: usize) -> Result<III, String> {
+        self.generate(limit, Mode::Object)
+    }
+
+    /// Link every item and native in declaration order, so that indices held by
+    /// objects stay valid across programs that share a prefix of items.
+    pub(crate) fn codegen_all(self, limit: usize) -> Result<III, String> {
+        self.generate(limit, Mode::Eager)
+    }
+
+    fn generate(self, limit: usize, mode: Mode) -> Result<III, String> {
+        let mut context = Context::new(self.groups, self.functions, self.registry);
         let mut groups = HashMap::new();
         let mut callables = HashMap::new();
+        let mut names = HashMap::new();
 
-        let main = compile(
-            vec![self.main.0],
-            self.main.1,
-            limit,
-            &self.interner,
-            &self.namespace,
-            &mut context,
-        )?;
+        let mut imports = Vec::new();
+        for (symbol, pt, id) in self.imports {
+            let index = if pt == Pointer::Group {
+                let index = context.groups.reserve(id);
+                groups.insert(index, Group::new(symbol.clone(), [].iter()));
+                index
+            } else {
+                let index = context.functions.reserve(id);
+                callables.insert(index, Callable::placeholder());
+                names.insert(index, symbol.clone());
+                index
+            };
+            imports.push((symbol, pt, index));
+        }
+        if !matches!(mode, Mode::Lazy) {
+            context.eager();
+        }
+
+        let mut namespace = self.namespace;
+        let main = match self.main {
+            Some((args, block)) => compile(
+                "main",
+                (0, vec![args], block),
+                limit,
+                &self.interner,
+                &mut namespace,
+                &mut context,
+            )?,
+            None if matches!(mode, Mode::Object) => Callable::empty(),
+            None => return Err(Error::MainNotFound.recover(&self.interner)),
+        };
 
         while !context.done() {
             while let Some((index, mut group)) = context.groups.pop() {
@@ -102,40 +194,103 @@ impl II {
                 groups.insert(index, group);
             }
 
-            while let Some((index, (args, block))) = context.functions.pop() {
+            while let Some((index, (name, scope, args, block))) = context.functions.pop() {
                 let callable = compile(
-                    args,
-                    block,
+                    &name,
+                    (scope, args, block),
                     limit,
                     &self.interner,
-                    &self.namespace,
+                    &mut namespace,
                     &mut context,
                 )?;
                 callables.insert(index, callable);
+                names.insert(index, name);
             }
         }
 
-        Ok(III {
+        let mut iii = III {
             main,
             text: linearize(callables),
             data: context.data.pool,
             groups: linearize(groups),
-        })
+            natives: context.natives.pool,
+            names: linearize(names),
+            symbols: Vec::new(),
+            exports: Vec::new(),
+            imports,
+            source: self.source,
+        };
+        if let Mode::Object = mode {
+            for (symbol, pt, id) in self.exports {
+                let index = match pt {
+                    Pointer::Group => context.groups.get(id),
+                    _ => context.functions.get(id),
+                };
+                iii.exports.push((symbol, pt, index));
+            }
+        }
+        iii.symbols = symbols(&iii, &self.interner);
+        Ok(iii)
+    }
+}
+
+enum Mode {
+    Lazy,
+    Eager,
+    Object,
+}
+
+fn symbols(iii: &III, interner: &Interner) -> Vec<(Id, String)> {
+    let mut ids = Vec::new();
+    for callable in iii.text.iter().chain([&iii.main]) {
+        for bytecode in callable.bytecodes.iter() {
+            if let Bytecode::Field(_, _, id)
+            | Bytecode::Method(_, _, id, _)
+            | Bytecode::TailMethod(_, _, id, _)
+            | Bytecode::Update(_, _, id, _) = bytecode
+            {
+                ids.push(*id);
+            }
+        }
+    }
+    for group in iii.groups.iter() {
+        ids.extend(group.indices.keys());
+        ids.extend(group.methods.keys());
+    }
+    ids.sort();
+    ids.dedup();
+    ids.into_iter()
+        .map(|id| (id, interner.resolve(&(id as usize)).unwrap().to_string()))
+        .collect()
+}
+
+impl Callable {
+    fn placeholder() -> Callable {
+        Callable {
+            args: 1,
+            registers: 1,
+            bytecodes: vec![Bytecode::Arg(0, 0), Bytecode::Return(0)],
+            lines: Vec::new(),
+        }
     }
 }
 
 fn compile(
-    args: Vec<usize>,
-    block: Block,
+    name: &str,
+    (scope, args, block): (usize, Vec<usize>, Block),
     limit: usize,
     interner: &Interner,
-    namespace: &Namespace,
+    namespace: &mut Namespace,
     ctx: &mut Context,
 ) -> Result<Callable, String> {
     let length = Reg::try_from(args.len()).unwrap();
-    let map = block
-        .semantic(args.iter(), namespace)
+    let (map, lambdas) = block
+        .semantic(scope, args.iter(), namespace)
         .map_err(|e| e.recover(interner))?;
+    for (id, args, block) in lambdas {
+        let todo = (format!("{name}::<closure>"), scope, args, block);
+        ctx.functions.source.insert(id, todo);
+    }
     let mut function = block
         .function(&map, interner, args)
         .map_err(|e| e.recover(interner))?;
@@ -143,10 +298,12 @@ fn compile(
     let copies = function.copies();
     let rpo = function.rpo();
     let (allocation, used) = function.allocate(&rpo, &copies);
+    let (bytecodes, lines) = function.codegen(&rpo, &allocation, ctx, copies);
     Ok(Callable {
         args: length,
         registers: used,
-        bytecodes: function.codegen(&rpo, &allocation, ctx, copies),
+        bytecodes,
+        lines,
     })
 }
 
@@ -167,7 +324,7 @@ impl Function {
         alloc: &HashMap<Var, Reg>,
         ctx: &mut Context,
         mut copies: HashMap<Label, Vec<Copy>>,
-    ) -> Vec<Bytecode> {
+    ) -> (Vec<Bytecode>, Vec<(Index, Index)>) {
         let mut index = 0;
         let mut map = HashMap::new();
         for label in rpo {
@@ -180,24 +337,63 @@ impl Function {
         }
 
         let mut bytecodes = Vec::with_capacity(index);
+        let mut lines = Vec::new();
         for label in rpo {
             let fragment = self.get(*label).unwrap();
+            let copy = copies.remove(label).unwrap_or_default();
+            let tail = match (fragment.instructions.last(), self.returned(*label)) {
+                (Some(last), Some(var)) => match copy.as_slice() {
+                    [] => last.dst() == var,
+                    [Copy(dst, src)] => *dst == var && *src == last.dst(),
+                    _ => false,
+                },
+                _ => false,
+            };
 
-            let body = fragment.instructions.iter().map(|x| x.codegen(alloc, ctx));
-            bytecodes.extend(body);
+            for (i, instruction) in fragment.instructions.iter().enumerate() {
+                let span = self.spans.get(&instruction.dst());
+                locate(span, bytecodes.len(), &mut lines);
+                let bytecode = instruction.codegen(alloc, ctx);
+                if tail && i + 1 == fragment.instructions.len() {
+                    bytecodes.push(bytecode.tail());
+                } else {
+                    bytecodes.push(bytecode);
+                }
+            }
 
-            let copy = copies
-                .remove(label)
-                .unwrap_or_default()
-                .into_iter()
-                .map(|copy| copy.codegen(alloc));
-            bytecodes.extend(copy);
+            bytecodes.extend(copy.into_iter().map(|copy| copy.codegen(alloc)));
 
-            if let Some(term) = fragment.terminator.as_ref().map(|x| x.codegen(alloc, &map)) {
-                bytecodes.push(term);
+            if let Some(term) = fragment.terminator.as_ref() {
+                if let Terminator::Branch(..) = term {
+                    locate(self.branches.get(label), bytecodes.len(), &mut lines);
+                }
+                bytecodes.push(term.codegen(alloc, &map));
+            }
+        }
+        (bytecodes, lines)
+    }
+
+    fn returned(&self, label: Label) -> Option<Var> {
+        let mut visited = vec![label];
+        let mut fragment = self.get(label)?;
+        while let Some(Terminator::Jump(to)) = fragment.terminator {
+            if visited.contains(&to) {
+                return None;
             }
+            visited.push(to);
+            fragment = self.get(to).filter(|x| x.instructions.is_empty())?;
         }
-        bytecodes
+        match fragment.terminator {
+            Some(Terminator::Return(var)) => Some(var),
+            _ => None,
+        }
+    }
+}
+
+fn locate(span: Option<&usize>, pc: usize, lines: &mut Vec<(Index, Index)>) {
+    if let Some(span) = span {
+        let pc = Index::try_from(pc).unwrap();
+        lines.push((pc, Index::try_from(*span).unwrap()));
     }
 }
 
@@ -222,7 +418,11 @@ impl Instruction {
                     *pt,
                     Index::try_from(ctx.groups.get(*ptr)).unwrap(),
                 ),
-                Pointer::Rust => Bytecode::Pointer(alloc[dst], *pt, Index::try_from(*ptr).unwrap()),
+                Pointer::Rust => Bytecode::Pointer(
+                    alloc[dst],
+                    *pt,
+                    Index::try_from(ctx.natives.index(*ptr)).unwrap(),
+                ),
             },
             Instruction::Load(dst, id) => Bytecode::Load(
                 alloc[dst],
@@ -235,6 +435,17 @@ impl Instruction {
                 *alloc.get(rhs).unwrap_or(&0),
             ),
             Instruction::Unary(dst, op, src) => Bytecode::Unary(alloc[dst], *op, alloc[src]),
+            Instruction::Check(dst, src) => Bytecode::Check(alloc[dst], alloc[src]),
+            Instruction::Test(dst, src, shape, n) => {
+                let n = match shape {
+                    Shape::Group => ctx.groups.get(*n),
+                    _ => Index::try_from(*n).unwrap(),
+                };
+                Bytecode::Test(alloc[dst], alloc[src], *shape, n)
+            }
+            Instruction::Tail(dst, src, n) => {
+                Bytecode::Tail(alloc[dst], alloc[src], Index::try_from(*n).unwrap())
+            }
             Instruction::Call(dst, src, args) => Bytecode::Call(
                 alloc[dst],
                 alloc[src],
@@ -246,15 +457,45 @@ impl Instruction {
             Instruction::Tuple(dst, args) => {
                 Bytecode::Tuple(alloc[dst], args.iter().map(|x| alloc[x]).collect())
             }
+            Instruction::Map(dst, args) => {
+                Bytecode::Map(alloc[dst], args.iter().map(|x| alloc[x]).collect())
+            }
+            Instruction::Closure(dst, ptr, args) => Bytecode::Closure(
+                alloc[dst],
+                ctx.functions.get(*ptr),
+                args.iter().map(|x| alloc[x]).collect(),
+            ),
             Instruction::Index(dst, src, index) => {
                 Bytecode::Index(alloc[dst], alloc[src], alloc[index])
             }
+            Instruction::Nth(dst, src, index) => {
+                Bytecode::Nth(alloc[dst], alloc[src], alloc[index])
+            }
+            Instruction::Update(dst, src, id, value) => Bytecode::Update(
+                alloc[dst],
+                alloc[src],
+                Id::try_from(*id).unwrap(),
+                alloc[value],
+            ),
             Instruction::Method(dst, src, id, args) => Bytecode::Method(
                 alloc[dst],
                 alloc[src],
                 Id::try_from(*id).unwrap(),
                 args.iter().map(|x| alloc[x]).collect(),
             ),
+            Instruction::Iter(dst, src) => Bytecode::Iter(alloc[dst], alloc[src]),
+            Instruction::Has(dst, src) => Bytecode::Has(alloc[dst], alloc[src]),
+            Instruction::Next(dst, src) => Bytecode::Next(alloc[dst], alloc[src]),
+        }
+    }
+}
+
+impl Bytecode {
+    fn tail(self) -> Bytecode {
+        match self {
+            Bytecode::Call(dst, src, args) => Bytecode::TailCall(dst, src, args),
+            Bytecode::Method(dst, src, id, args) => Bytecode::TailMethod(dst, src, id, args),
+            bytecode => bytecode,
         }
     }
 }
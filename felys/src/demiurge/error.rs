@@ -40,6 +40,8 @@ impl Display for Const {
             Const::Float(x) => write!(f, "{}", f32::from_bits(*x)),
             Const::Bool(x) => write!(f, "{}", x),
             Const::Str(x) => write!(f, "\"{}\"", x),
+            Const::I64(x) => write!(f, "{}", x),
+            Const::F64(x) => write!(f, "{}", f64::from_bits(*x)),
         }
     }
 }
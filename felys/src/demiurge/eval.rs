@@ -1,6 +1,6 @@
 use crate::demiurge::error::Error;
 use crate::utils::ast::{BinOp, UnaOp};
-use crate::utils::function::Const;
+use crate::utils::function::{Const, Shape};
 use std::ops::{Add, Div, Mul, Rem, Sub};
 
 impl Const {
@@ -28,9 +28,21 @@ impl Const {
             UnaOp::Not => self.not(),
             UnaOp::Pos => self.pos(),
             UnaOp::Neg => self.neg(),
+            UnaOp::Fmt => self.fmt(),
         }
     }
 
+    pub fn test(&self, shape: &Shape) -> bool {
+        matches!(
+            (shape, self),
+            (
+                Shape::Number,
+                Const::Int(_) | Const::Float(_) | Const::I64(_) | Const::F64(_)
+            ) | (Shape::Str, Const::Str(_))
+                | (Shape::Bool, Const::Bool(_))
+        )
+    }
+
     pub fn bool(&self) -> Result<bool, Error> {
         if let Const::Bool(x) = self {
             Ok(*x)
@@ -55,6 +67,22 @@ impl Const {
         }
     }
 
+    fn i64(&self) -> Result<i64, Error> {
+        if let Const::I64(x) = self {
+            Ok(*x)
+        } else {
+            Err(Error::ConstantType(self.clone(), "i64"))
+        }
+    }
+
+    fn f64(&self) -> Result<f64, Error> {
+        if let Const::F64(x) = self {
+            Ok(f64::from_bits(*x))
+        } else {
+            Err(Error::ConstantType(self.clone(), "f64"))
+        }
+    }
+
     fn str(&self) -> Result<&str, Error> {
         if let Const::Str(x) = self {
             Ok(x)
@@ -84,9 +112,14 @@ impl Const {
     }
 
     fn gt(&self, rhs: &Const) -> Result<Const, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.gt(&rhs);
+        }
         let value = match self {
             Const::Int(x) => (*x) > rhs.int()?,
             Const::Float(x) => f32::from_bits(*x) > rhs.float()?,
+            Const::I64(x) => (*x) > rhs.i64()?,
+            Const::F64(x) => f64::from_bits(*x) > rhs.f64()?,
             _ => {
                 return Err(Error::BinaryOperation(">", self.clone(), rhs.clone()));
             }
@@ -95,9 +128,14 @@ impl Const {
     }
 
     fn ge(&self, rhs: &Const) -> Result<Const, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.ge(&rhs);
+        }
         let value = match self {
             Const::Int(x) => (*x) >= rhs.int()?,
             Const::Float(x) => f32::from_bits(*x) >= rhs.float()?,
+            Const::I64(x) => (*x) >= rhs.i64()?,
+            Const::F64(x) => f64::from_bits(*x) >= rhs.f64()?,
             _ => {
                 return Err(Error::BinaryOperation(">=", self.clone(), rhs.clone()));
             }
@@ -106,9 +144,14 @@ impl Const {
     }
 
     fn lt(&self, rhs: &Const) -> Result<Const, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.lt(&rhs);
+        }
         let value = match self {
             Const::Int(x) => (*x) < rhs.int()?,
             Const::Float(x) => f32::from_bits(*x) < rhs.float()?,
+            Const::I64(x) => (*x) < rhs.i64()?,
+            Const::F64(x) => f64::from_bits(*x) < rhs.f64()?,
             _ => {
                 return Err(Error::BinaryOperation("<", self.clone(), rhs.clone()));
             }
@@ -117,9 +160,14 @@ impl Const {
     }
 
     fn le(&self, rhs: &Const) -> Result<Const, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.le(&rhs);
+        }
         let value = match self {
             Const::Int(x) => (*x) <= rhs.int()?,
             Const::Float(x) => f32::from_bits(*x) <= rhs.float()?,
+            Const::I64(x) => (*x) <= rhs.i64()?,
+            Const::F64(x) => f64::from_bits(*x) <= rhs.f64()?,
             _ => {
                 return Err(Error::BinaryOperation("<=", self.clone(), rhs.clone()));
             }
@@ -128,98 +176,144 @@ impl Const {
     }
 
     fn eq(&self, rhs: &Const) -> Result<Const, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.eq(&rhs);
+        }
         let value = match self {
             Const::Int(x) => (*x) == rhs.int()?,
             Const::Float(x) => f32::from_bits(*x) == rhs.float()?,
             Const::Bool(x) => *x == rhs.bool()?,
             Const::Str(x) => x.as_ref() == rhs.str()?,
+            Const::I64(x) => (*x) == rhs.i64()?,
+            Const::F64(x) => f64::from_bits(*x) == rhs.f64()?,
         };
         Ok(Const::Bool(value))
     }
 
     fn ne(&self, rhs: &Const) -> Result<Const, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.ne(&rhs);
+        }
         let value = match self {
             Const::Int(x) => (*x) != rhs.int()?,
             Const::Float(x) => f32::from_bits(*x) != rhs.float()?,
             Const::Bool(x) => *x != rhs.bool()?,
             Const::Str(x) => x.as_ref() != rhs.str()?,
+            Const::I64(x) => (*x) != rhs.i64()?,
+            Const::F64(x) => f64::from_bits(*x) != rhs.f64()?,
         };
         Ok(Const::Bool(value))
     }
 
     fn add(&self, rhs: &Const) -> Result<Const, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.add(&rhs);
+        }
+        let error = || Error::BinaryOperation("+", self.clone(), rhs.clone());
         let value = match self {
-            Const::Int(x) => (*x)
-                .checked_add(rhs.int()?)
-                .ok_or(Error::BinaryOperation("+", self.clone(), rhs.clone()))?
-                .into(),
+            Const::Int(x) => x.checked_add(rhs.int()?).ok_or_else(error)?.into(),
             Const::Float(x) => f32::from_bits(*x).add(rhs.float()?).into(),
+            Const::I64(x) => x.checked_add(rhs.i64()?).ok_or_else(error)?.into(),
+            Const::F64(x) => f64::from_bits(*x).add(rhs.f64()?).into(),
             Const::Str(x) => format!("{}{}", x, rhs.str()?).into(),
-            _ => {
-                return Err(Error::BinaryOperation("+", self.clone(), rhs.clone()));
-            }
+            _ => return Err(error()),
         };
         Ok(value)
     }
 
     fn sub(&self, rhs: &Const) -> Result<Const, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.sub(&rhs);
+        }
+        let error = || Error::BinaryOperation("-", self.clone(), rhs.clone());
         let value = match self {
-            Const::Int(x) => {
-                Const::from((*x).checked_sub(rhs.int()?).ok_or(Error::BinaryOperation(
-                    "-",
-                    self.clone(),
-                    rhs.clone(),
-                ))?)
-            }
+            Const::Int(x) => x.checked_sub(rhs.int()?).ok_or_else(error)?.into(),
             Const::Float(x) => f32::from_bits(*x).sub(rhs.float()?).into(),
-            _ => {
-                return Err(Error::BinaryOperation("-", self.clone(), rhs.clone()));
-            }
+            Const::I64(x) => x.checked_sub(rhs.i64()?).ok_or_else(error)?.into(),
+            Const::F64(x) => f64::from_bits(*x).sub(rhs.f64()?).into(),
+            _ => return Err(error()),
         };
         Ok(value)
     }
 
     fn mul(&self, rhs: &Const) -> Result<Const, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.mul(&rhs);
+        }
+        let error = || Error::BinaryOperation("*", self.clone(), rhs.clone());
         let value = match self {
-            Const::Int(x) => (*x)
-                .checked_mul(rhs.int()?)
-                .ok_or(Error::BinaryOperation("*", self.clone(), rhs.clone()))?
-                .into(),
+            Const::Int(x) => x.checked_mul(rhs.int()?).ok_or_else(error)?.into(),
             Const::Float(x) => f32::from_bits(*x).mul(rhs.float()?).into(),
-            _ => {
-                return Err(Error::BinaryOperation("*", self.clone(), rhs.clone()));
-            }
+            Const::I64(x) => x.checked_mul(rhs.i64()?).ok_or_else(error)?.into(),
+            Const::F64(x) => f64::from_bits(*x).mul(rhs.f64()?).into(),
+            _ => return Err(error()),
         };
         Ok(value)
     }
 
     fn div(&self, rhs: &Const) -> Result<Const, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.div(&rhs);
+        }
+        let error = || Error::BinaryOperation("/", self.clone(), rhs.clone());
         let value = match self {
-            Const::Int(x) => (*x)
-                .checked_div(rhs.int()?)
-                .ok_or(Error::BinaryOperation("/", self.clone(), rhs.clone()))?
-                .into(),
+            Const::Int(x) => x.checked_div(rhs.int()?).ok_or_else(error)?.into(),
             Const::Float(x) => f32::from_bits(*x).div(rhs.float()?).into(),
-            _ => {
-                return Err(Error::BinaryOperation("/", self.clone(), rhs.clone()));
-            }
+            Const::I64(x) => x.checked_div(rhs.i64()?).ok_or_else(error)?.into(),
+            Const::F64(x) => f64::from_bits(*x).div(rhs.f64()?).into(),
+            _ => return Err(error()),
         };
         Ok(value)
     }
 
     fn rem(&self, rhs: &Const) -> Result<Const, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.rem(&rhs);
+        }
+        let error = || Error::BinaryOperation("%", self.clone(), rhs.clone());
         let value = match self {
-            Const::Int(x) => (*x).rem(rhs.int()?).into(),
+            Const::Int(x) => x.checked_rem(rhs.int()?).ok_or_else(error)?.into(),
             Const::Float(x) => f32::from_bits(*x).rem(rhs.float()?).into(),
-            _ => {
-                return Err(Error::BinaryOperation("%", self.clone(), rhs.clone()));
-            }
+            Const::I64(x) => x.checked_rem(rhs.i64()?).ok_or_else(error)?.into(),
+            Const::F64(x) => f64::from_bits(*x).rem(rhs.f64()?).into(),
+            _ => return Err(error()),
         };
         Ok(value)
     }
 
-    fn dot(&self, _: &Const) -> Result<Const, Error> {
-        Ok(self.clone())
+    fn promote(&self, rhs: &Const) -> Option<(Const, Const)> {
+        let (lf, lw) = self.numeric()?;
+        let (rf, rw) = rhs.numeric()?;
+        if (lf, lw) == (rf, rw) {
+            return None;
+        }
+        let (float, wide) = (lf || rf, lw || rw);
+        Some((self.cast(float, wide), rhs.cast(float, wide)))
+    }
+
+    fn numeric(&self) -> Option<(bool, bool)> {
+        match self {
+            Const::Int(_) => Some((false, false)),
+            Const::I64(_) => Some((false, true)),
+            Const::Float(_) => Some((true, false)),
+            Const::F64(_) => Some((true, true)),
+            _ => None,
+        }
+    }
+
+    fn cast(&self, float: bool, wide: bool) -> Const {
+        match (self, float, wide) {
+            (Const::Int(x), false, true) => (*x as i64).into(),
+            (Const::Int(x), true, false) => (*x as f32).into(),
+            (Const::Int(x), true, true) => (*x as f64).into(),
+            (Const::I64(x), true, true) => (*x as f64).into(),
+            (Const::Float(x), true, true) => (f32::from_bits(*x) as f64).into(),
+            (other, _, _) => other.clone(),
+        }
+    }
+
+    fn dot(&self, rhs: &Const) -> Result<Const, Error> {
+        Err(Error::BinaryOperation("@", self.clone(), rhs.clone()))
     }
 
     fn not(&self) -> Result<Const, Error> {
@@ -231,7 +325,10 @@ impl Const {
     }
 
     fn pos(&self) -> Result<Const, Error> {
-        if matches!(self, Const::Int(_) | Const::Float(_)) {
+        if matches!(
+            self,
+            Const::Int(_) | Const::Float(_) | Const::I64(_) | Const::F64(_)
+        ) {
             Ok(self.clone())
         } else {
             Err(Error::UnaryOperation("+", self.clone()))
@@ -240,12 +337,28 @@ impl Const {
 
     fn neg(&self) -> Result<Const, Error> {
         let value = match self {
-            Const::Int(x) => (-*x).into(),
+            Const::Int(x) => x
+                .checked_neg()
+                .ok_or(Error::UnaryOperation("-", self.clone()))?
+                .into(),
             Const::Float(x) => (-f32::from_bits(*x)).into(),
+            Const::I64(x) => x
+                .checked_neg()
+                .ok_or(Error::UnaryOperation("-", self.clone()))?
+                .into(),
+            Const::F64(x) => (-f64::from_bits(*x)).into(),
             _ => return Err(Error::UnaryOperation("-", self.clone())),
         };
         Ok(value)
     }
+
+    fn fmt(&self) -> Result<Const, Error> {
+        let value = match self {
+            Const::Str(x) => x.clone(),
+            other => other.to_string().into(),
+        };
+        Ok(Const::Str(value))
+    }
 }
 
 impl From<f32> for Const {
@@ -254,6 +367,18 @@ impl From<f32> for Const {
     }
 }
 
+impl From<f64> for Const {
+    fn from(x: f64) -> Const {
+        Const::F64(x.to_bits())
+    }
+}
+
+impl From<i64> for Const {
+    fn from(x: i64) -> Const {
+        Const::I64(x)
+    }
+}
+
 impl From<i32> for Const {
     fn from(x: i32) -> Const {
         Const::Int(x)
@@ -171,7 +171,10 @@ impl Instruction {
             Instruction::Load(var, c) => meta.update(*var, Lattice::Const(c.clone())),
             Instruction::Binary(var, lhs, op, rhs) => {
                 let new = match (meta.get(*lhs), meta.get(*rhs)) {
-                    (Lattice::Const(l), Lattice::Const(r)) => Lattice::Const(l.binary(op, r)?),
+                    (Lattice::Const(l), Lattice::Const(r)) => match l.binary(op, r) {
+                        Ok(c) => Lattice::Const(c),
+                        Err(_) => Lattice::Bottom,
+                    },
                     (Lattice::Bottom, _) | (_, Lattice::Bottom) => Lattice::Bottom,
                     _ => Lattice::Top,
                 };
@@ -180,7 +183,26 @@ impl Instruction {
             Instruction::Unary(var, op, src) => {
                 let new = match meta.get(*src) {
                     Lattice::Top => Lattice::Top,
-                    Lattice::Const(c) => Lattice::Const(c.unary(op)?),
+                    Lattice::Const(c) => match c.unary(op) {
+                        Ok(c) => Lattice::Const(c),
+                        Err(_) => Lattice::Bottom,
+                    },
+                    Lattice::Bottom => Lattice::Bottom,
+                };
+                meta.update(*var, new);
+            }
+            Instruction::Check(var, src) => {
+                let new = match meta.get(*src) {
+                    Lattice::Top => Lattice::Top,
+                    Lattice::Const(_) => Lattice::Const(Const::Bool(false)),
+                    Lattice::Bottom => Lattice::Bottom,
+                };
+                meta.update(*var, new);
+            }
+            Instruction::Test(var, src, shape, _) => {
+                let new = match meta.get(*src) {
+                    Lattice::Top => Lattice::Top,
+                    Lattice::Const(c) => Lattice::Const(Const::Bool(c.test(shape))),
                     Lattice::Bottom => Lattice::Bottom,
                 };
                 meta.update(*var, new);
@@ -188,11 +210,19 @@ impl Instruction {
             Instruction::Arg(dst, _)
             | Instruction::Field(dst, _, _)
             | Instruction::Unpack(dst, _, _)
+            | Instruction::Tail(dst, _, _)
             | Instruction::Call(dst, _, _)
             | Instruction::List(dst, _)
             | Instruction::Tuple(dst, _)
+            | Instruction::Map(dst, _)
+            | Instruction::Closure(dst, _, _)
             | Instruction::Index(dst, _, _)
+            | Instruction::Nth(dst, _, _)
+            | Instruction::Update(dst, _, _, _)
             | Instruction::Method(dst, _, _, _)
+            | Instruction::Iter(dst, _)
+            | Instruction::Has(dst, _)
+            | Instruction::Next(dst, _)
             | Instruction::Pointer(dst, _, _) => meta.update(*dst, Lattice::Bottom),
         }
         Ok(())
@@ -205,8 +235,17 @@ impl Instruction {
         match self {
             Instruction::Field(_, src, _)
             | Instruction::Unpack(_, src, _)
-            | Instruction::Unary(_, _, src) => update(src),
-            Instruction::Binary(_, src, _, other) | Instruction::Index(_, src, other) => {
+            | Instruction::Unary(_, _, src)
+            | Instruction::Check(_, src)
+            | Instruction::Test(_, src, _, _)
+            | Instruction::Tail(_, src, _)
+            | Instruction::Iter(_, src)
+            | Instruction::Has(_, src)
+            | Instruction::Next(_, src) => update(src),
+            Instruction::Binary(_, src, _, other)
+            | Instruction::Index(_, src, other)
+            | Instruction::Nth(_, src, other)
+            | Instruction::Update(_, src, _, other) => {
                 update(src);
                 update(other);
             }
@@ -214,7 +253,10 @@ impl Instruction {
                 update(src);
                 args.iter().for_each(update);
             }
-            Instruction::List(_, args) | Instruction::Tuple(_, args) => {
+            Instruction::List(_, args)
+            | Instruction::Tuple(_, args)
+            | Instruction::Map(_, args)
+            | Instruction::Closure(_, _, args) => {
                 args.iter().for_each(update);
             }
             Instruction::Arg(_, _) | Instruction::Pointer(_, _, _) | Instruction::Load(_, _) => {}
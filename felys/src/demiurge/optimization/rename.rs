@@ -98,12 +98,24 @@ impl Instruction {
         match self {
             Instruction::Unary(_, _, src)
             | Instruction::Field(_, src, _)
-            | Instruction::Unpack(_, src, _) => *src = renamer.get(*src),
-            Instruction::Binary(_, src, _, other) | Instruction::Index(_, src, other) => {
+            | Instruction::Unpack(_, src, _)
+            | Instruction::Check(_, src)
+            | Instruction::Test(_, src, _, _)
+            | Instruction::Tail(_, src, _)
+            | Instruction::Iter(_, src)
+            | Instruction::Has(_, src)
+            | Instruction::Next(_, src) => *src = renamer.get(*src),
+            Instruction::Binary(_, src, _, other)
+            | Instruction::Index(_, src, other)
+            | Instruction::Nth(_, src, other)
+            | Instruction::Update(_, src, _, other) => {
                 *src = renamer.get(*src);
                 *other = renamer.get(*other);
             }
-            Instruction::List(_, args) | Instruction::Tuple(_, args) => {
+            Instruction::List(_, args)
+            | Instruction::Tuple(_, args)
+            | Instruction::Map(_, args)
+            | Instruction::Closure(_, _, args) => {
                 args.iter_mut().for_each(|x| *x = renamer.get(*x));
             }
             Instruction::Call(_, src, args) | Instruction::Method(_, src, _, args) => {
@@ -108,7 +108,10 @@ impl Fragment {
 impl Instruction {
     fn rewrite(&mut self, meta: &Meta) -> bool {
         match self {
-            Instruction::Binary(dst, _, _, _) | Instruction::Unary(dst, _, _) => {
+            Instruction::Binary(dst, _, _, _)
+            | Instruction::Unary(dst, _, _)
+            | Instruction::Check(dst, _)
+            | Instruction::Test(dst, _, _, _) => {
                 if let Lattice::Const(c) = meta.get(*dst) {
                     *self = Instruction::Load(*dst, c.clone());
                     return true;
@@ -119,12 +119,24 @@ impl Instruction {
         match self {
             Instruction::Field(_, src, _)
             | Instruction::Unpack(_, src, _)
-            | Instruction::Unary(_, _, src) => ctx.visit(src),
-            Instruction::Binary(_, src, _, other) | Instruction::Index(_, src, other) => {
+            | Instruction::Unary(_, _, src)
+            | Instruction::Check(_, src)
+            | Instruction::Test(_, src, _, _)
+            | Instruction::Tail(_, src, _)
+            | Instruction::Iter(_, src)
+            | Instruction::Has(_, src)
+            | Instruction::Next(_, src) => ctx.visit(src),
+            Instruction::Binary(_, src, _, other)
+            | Instruction::Index(_, src, other)
+            | Instruction::Nth(_, src, other)
+            | Instruction::Update(_, src, _, other) => {
                 ctx.visit(src);
                 ctx.visit(other);
             }
-            Instruction::List(_, args) | Instruction::Tuple(_, args) => {
+            Instruction::List(_, args)
+            | Instruction::Tuple(_, args)
+            | Instruction::Map(_, args)
+            | Instruction::Closure(_, _, args) => {
                 args.iter().for_each(|x| ctx.visit(x));
             }
             Instruction::Call(_, src, args) | Instruction::Method(_, src, _, args) => {
@@ -135,25 +147,14 @@ impl Instruction {
         }
     }
 
-    fn dst(&self) -> Var {
-        match self {
-            Instruction::Arg(dst, _)
-            | Instruction::Field(dst, _, _)
-            | Instruction::Unpack(dst, _, _)
-            | Instruction::Load(dst, _)
-            | Instruction::Binary(dst, _, _, _)
-            | Instruction::Unary(dst, _, _)
-            | Instruction::Call(dst, _, _)
-            | Instruction::List(dst, _)
-            | Instruction::Tuple(dst, _)
-            | Instruction::Index(dst, _, _)
-            | Instruction::Method(dst, _, _, _)
-            | Instruction::Pointer(dst, _, _) => *dst,
-        }
-    }
-
     fn functional(&self) -> bool {
-        !matches!(self, Instruction::Call(..) | Instruction::Method(..))
+        !matches!(
+            self,
+            Instruction::Call(..)
+                | Instruction::Method(..)
+                | Instruction::Has(..)
+                | Instruction::Next(..)
+        )
     }
 }
 
@@ -0,0 +1,439 @@
+use crate::utils::ast::{BinOp, UnaOp};
+use crate::utils::bytecode::{Bytecode, Reg};
+use crate::utils::function::{Const, Pointer, Shape};
+use crate::utils::group::Group;
+use crate::utils::stages::{Callable, III};
+use crate::utils::stdlib::registry::Registry;
+use std::collections::{HashMap, VecDeque};
+use std::str::FromStr;
+
+impl III {
+    pub fn assemble(listing: &str, registry: &Registry) -> Result<III, String> {
+        let mut main = None;
+        let mut text = Vec::new();
+        let mut names = Vec::new();
+        let mut data = Vec::new();
+        let mut groups = Vec::new();
+        let mut natives = Vec::new();
+        let mut symbols = Vec::new();
+        let mut exports = Vec::new();
+        let mut imports = Vec::new();
+        let mut section = Section::None;
+
+        for (i, raw) in listing.lines().enumerate() {
+            let mut line = Line::new(i + 1, raw);
+            let Some(first) = line.tokens.pop_front() else {
+                continue;
+            };
+
+            if let Some(header) = first.strip_prefix('.') {
+                section = match header {
+                    "main" if main.is_none() => {
+                        main = Some(Callable::empty());
+                        Section::Main
+                    }
+                    "text" => {
+                        line.sequence(text.len())?;
+                        text.push(Callable::empty());
+                        names.push(line.tokens.pop_front().unwrap_or_default().to_string());
+                        Section::Text
+                    }
+                    "data" => Section::Data,
+                    "native" => Section::Native,
+                    "symbol" => Section::Symbol,
+                    "export" => Section::Export,
+                    "import" => Section::Import,
+                    "group" => {
+                        line.sequence(groups.len())?;
+                        groups.push(Group {
+                            name: line.tokens.pop_front().unwrap_or_default().to_string(),
+                            indices: HashMap::new(),
+                            methods: HashMap::new(),
+                        });
+                        Section::Group
+                    }
+                    _ => return Err(line.error(&format!("unexpected section `{first}`"))),
+                };
+                line.end()?;
+                continue;
+            }
+
+            match section {
+                Section::None => return Err(line.error("expecting a section")),
+                Section::Main => main.as_mut().unwrap().assemble(first, line)?,
+                Section::Text => text.last_mut().unwrap().assemble(first, line)?,
+                Section::Data => {
+                    let expected = format!("d{}", data.len());
+                    if first != expected {
+                        return Err(line.error(&format!("expecting `{expected}`")));
+                    }
+                    data.push(Const::assemble(i + 1, raw)?);
+                }
+                Section::Native => {
+                    let expected = format!("n{}", natives.len());
+                    if first != expected {
+                        return Err(line.error(&format!("expecting `{expected}`")));
+                    }
+                    let symbol = line.next()?;
+                    let native = registry
+                        .find(symbol)
+                        .ok_or_else(|| line.error(&format!("native `{symbol}` not found")))?;
+                    natives.push((symbol.to_string(), native.clone()));
+                    line.end()?;
+                }
+                Section::Group => groups.last_mut().unwrap().assemble(first, line)?,
+                Section::Symbol => {
+                    line.tokens.push_front(first);
+                    symbols.push((line.prefixed('#')?, line.next()?.to_string()));
+                    line.end()?;
+                }
+                Section::Export | Section::Import => {
+                    let entry = (first.to_string(), line.pointer()?, line.int()?);
+                    line.end()?;
+                    match section {
+                        Section::Export => exports.push(entry),
+                        _ => imports.push(entry),
+                    }
+                }
+            }
+        }
+
+        let iii = III {
+            main: main.ok_or("Elysia: section `.main` not found\n")?,
+            text,
+            data,
+            groups,
+            natives,
+            names,
+            symbols,
+            exports,
+            imports,
+            source: String::new(),
+        };
+        iii.verify().map_err(|e| format!("Elysia: {e}\n"))?;
+        Ok(iii)
+    }
+}
+
+enum Section {
+    None,
+    Main,
+    Text,
+    Data,
+    Native,
+    Group,
+    Symbol,
+    Export,
+    Import,
+}
+
+impl Callable {
+    pub(crate) fn empty() -> Callable {
+        Callable {
+            args: 0,
+            registers: 0,
+            bytecodes: Vec::new(),
+            lines: Vec::new(),
+        }
+    }
+
+    fn assemble(&mut self, first: &str, mut line: Line) -> Result<(), String> {
+        match first {
+            "args" => self.args = line.int()?,
+            "registers" => self.registers = line.int()?,
+            _ => {
+                let mnemonic = if first.bytes().all(|x| x.is_ascii_digit()) {
+                    if first.parse() != Ok(self.bytecodes.len()) {
+                        return Err(line.error(&format!("expecting pc {}", self.bytecodes.len())));
+                    }
+                    line.next()?
+                } else {
+                    first
+                };
+                let bytecode = Bytecode::assemble(mnemonic, &mut line)?;
+                self.bytecodes.push(bytecode);
+            }
+        }
+        line.end()
+    }
+}
+
+impl Group {
+    fn assemble(&mut self, first: &str, mut line: Line) -> Result<(), String> {
+        match first {
+            "field" => {
+                let id = line.prefixed('#')?;
+                self.indices.insert(id, line.int()?);
+            }
+            "method" => {
+                let id = line.prefixed('#')?;
+                line.keyword("fn")?;
+                self.methods.insert(id, line.int()?);
+            }
+            _ => return Err(line.error(&format!("unexpected `{first}`"))),
+        }
+        line.end()
+    }
+}
+
+impl Bytecode {
+    fn assemble(mnemonic: &str, line: &mut Line) -> Result<Bytecode, String> {
+        let bytecode = match mnemonic {
+            "arg" => Bytecode::Arg(line.reg()?, line.int()?),
+            "field" => Bytecode::Field(line.reg()?, line.reg()?, line.prefixed('#')?),
+            "unpack" => Bytecode::Unpack(line.reg()?, line.reg()?, line.int()?),
+            "pointer" => Bytecode::Pointer(line.reg()?, line.pointer()?, line.int()?),
+            "load" => Bytecode::Load(line.reg()?, line.prefixed('d')?),
+            "binary" => Bytecode::Binary(line.reg()?, line.reg()?, line.binop()?, line.reg()?),
+            "unary" => Bytecode::Unary(line.reg()?, line.unaop()?, line.reg()?),
+            "check" => Bytecode::Check(line.reg()?, line.reg()?),
+            "test" => Bytecode::Test(line.reg()?, line.reg()?, line.shape()?, line.int()?),
+            "tail" => Bytecode::Tail(line.reg()?, line.reg()?, line.int()?),
+            "call" => Bytecode::Call(line.reg()?, line.reg()?, line.regs()?),
+            "list" => Bytecode::List(line.reg()?, line.regs()?),
+            "tuple" => Bytecode::Tuple(line.reg()?, line.regs()?),
+            "map" => Bytecode::Map(line.reg()?, line.regs()?),
+            "closure" => {
+                let dst = line.reg()?;
+                line.keyword("fn")?;
+                Bytecode::Closure(dst, line.int()?, line.regs()?)
+            }
+            "index" => Bytecode::Index(line.reg()?, line.reg()?, line.reg()?),
+            "nth" => Bytecode::Nth(line.reg()?, line.reg()?, line.reg()?),
+            "update" => {
+                Bytecode::Update(line.reg()?, line.reg()?, line.prefixed('#')?, line.reg()?)
+            }
+            "method" => {
+                Bytecode::Method(line.reg()?, line.reg()?, line.prefixed('#')?, line.regs()?)
+            }
+            "branch" => Bytecode::Branch(line.reg()?, line.prefixed('@')?, line.prefixed('@')?),
+            "jump" => Bytecode::Jump(line.prefixed('@')?),
+            "return" => Bytecode::Return(line.reg()?),
+            "copy" => Bytecode::Copy(line.reg()?, line.reg()?),
+            "iter" => Bytecode::Iter(line.reg()?, line.reg()?),
+            "has" => Bytecode::Has(line.reg()?, line.reg()?),
+            "next" => Bytecode::Next(line.reg()?, line.reg()?),
+            "tcall" => Bytecode::TailCall(line.reg()?, line.reg()?, line.regs()?),
+            "tmethod" => {
+                Bytecode::TailMethod(line.reg()?, line.reg()?, line.prefixed('#')?, line.regs()?)
+            }
+            _ => return Err(line.error(&format!("unknown instruction `{mnemonic}`"))),
+        };
+        Ok(bytecode)
+    }
+}
+
+impl Const {
+    fn assemble(number: usize, raw: &str) -> Result<Const, String> {
+        let error = |msg: &str| format!("Elysia: {msg} at line {number}\n");
+        let rest = raw.trim_start();
+        let rest = rest[rest.find(char::is_whitespace).unwrap_or(rest.len())..].trim_start();
+        let (kind, value) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let value = match kind {
+            "str" => return unescape(value.trim()).ok_or_else(|| error("invalid string")),
+            _ => value.split(';').next().unwrap().trim(),
+        };
+        let constant = match kind {
+            "int" => Const::Int(value.parse().map_err(|_| error("invalid int"))?),
+            "float" => Const::Float(
+                value
+                    .parse::<f32>()
+                    .map_err(|_| error("invalid float"))?
+                    .to_bits(),
+            ),
+            "bool" => Const::Bool(value.parse().map_err(|_| error("invalid bool"))?),
+            "i64" => Const::I64(value.parse().map_err(|_| error("invalid i64"))?),
+            "f64" => Const::F64(
+                value
+                    .parse::<f64>()
+                    .map_err(|_| error("invalid f64"))?
+                    .to_bits(),
+            ),
+            _ => return Err(error(&format!("unknown constant type `{kind}`"))),
+        };
+        Ok(constant)
+    }
+}
+
+fn unescape(literal: &str) -> Option<Const> {
+    let inner = literal.strip_prefix('"')?;
+    let mut value = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        let c = match c {
+            '"' => {
+                let rest = chars.as_str().trim_start();
+                return (rest.is_empty() || rest.starts_with(';')).then(|| value.into());
+            }
+            '\\' => match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                'r' => '\r',
+                '0' => '\0',
+                '\\' => '\\',
+                '"' => '"',
+                '\'' => '\'',
+                'u' => {
+                    let rest = chars.as_str().strip_prefix('{')?;
+                    let (hex, tail) = rest.split_once('}')?;
+                    chars = tail.chars();
+                    char::from_u32(u32::from_str_radix(hex, 16).ok()?)?
+                }
+                _ => return None,
+            },
+            c => c,
+        };
+        value.push(c);
+    }
+    None
+}
+
+struct Line<'a> {
+    number: usize,
+    tokens: VecDeque<&'a str>,
+}
+
+impl<'a> Line<'a> {
+    fn new(number: usize, raw: &'a str) -> Self {
+        let code = raw.split(';').next().unwrap();
+        let mut tokens = VecDeque::new();
+        let mut start = None;
+        for (i, c) in code.char_indices() {
+            let single = matches!(c, '[' | ']');
+            if c.is_whitespace() || c == ',' || single {
+                if let Some(s) = start.take() {
+                    tokens.push_back(&code[s..i]);
+                }
+                if single {
+                    tokens.push_back(&code[i..i + 1]);
+                }
+            } else if start.is_none() {
+                start = Some(i);
+            }
+        }
+        if let Some(s) = start {
+            tokens.push_back(&code[s..]);
+        }
+        Self { number, tokens }
+    }
+
+    fn error(&self, msg: &str) -> String {
+        format!("Elysia: {msg} at line {}\n", self.number)
+    }
+
+    fn next(&mut self) -> Result<&'a str, String> {
+        self.tokens
+            .pop_front()
+            .ok_or_else(|| self.error("unexpected end of line"))
+    }
+
+    fn end(&self) -> Result<(), String> {
+        match self.tokens.front() {
+            Some(token) => Err(self.error(&format!("unexpected `{token}`"))),
+            None => Ok(()),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str) -> Result<(), String> {
+        let token = self.next()?;
+        if token == keyword {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expecting `{keyword}`, found `{token}`")))
+        }
+    }
+
+    fn sequence(&mut self, expected: usize) -> Result<(), String> {
+        let index = self.int::<usize>()?;
+        if index == expected {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expecting index {expected}, found {index}")))
+        }
+    }
+
+    fn int<T: FromStr>(&mut self) -> Result<T, String> {
+        let token = self.next()?;
+        token
+            .parse()
+            .map_err(|_| self.error(&format!("invalid number `{token}`")))
+    }
+
+    fn prefixed<T: FromStr>(&mut self, prefix: char) -> Result<T, String> {
+        let token = self.next()?;
+        token
+            .strip_prefix(prefix)
+            .and_then(|x| x.parse().ok())
+            .ok_or_else(|| self.error(&format!("expecting `{prefix}<number>`, found `{token}`")))
+    }
+
+    fn reg(&mut self) -> Result<Reg, String> {
+        self.prefixed('r')
+    }
+
+    fn regs(&mut self) -> Result<Vec<Reg>, String> {
+        self.keyword("[")?;
+        let mut regs = Vec::new();
+        while self.tokens.front() != Some(&"]") {
+            regs.push(self.reg()?);
+        }
+        self.keyword("]")?;
+        Ok(regs)
+    }
+
+    fn pointer(&mut self) -> Result<Pointer, String> {
+        let pt = match self.next()? {
+            "group" => Pointer::Group,
+            "fn" => Pointer::Function,
+            "rust" => Pointer::Rust,
+            token => return Err(self.error(&format!("invalid pointer type `{token}`"))),
+        };
+        Ok(pt)
+    }
+
+    fn shape(&mut self) -> Result<Shape, String> {
+        let shape = match self.next()? {
+            "tuple" => Shape::Tuple,
+            "list" => Shape::List,
+            "rest" => Shape::Rest,
+            "group" => Shape::Group,
+            "number" => Shape::Number,
+            "str" => Shape::Str,
+            "bool" => Shape::Bool,
+            token => return Err(self.error(&format!("invalid shape `{token}`"))),
+        };
+        Ok(shape)
+    }
+
+    fn binop(&mut self) -> Result<BinOp, String> {
+        let op = match self.next()? {
+            "or" => BinOp::Or,
+            "and" => BinOp::And,
+            ">" => BinOp::Gt,
+            ">=" => BinOp::Ge,
+            "<" => BinOp::Lt,
+            "<=" => BinOp::Le,
+            "==" => BinOp::Eq,
+            "!=" => BinOp::Ne,
+            "+" => BinOp::Add,
+            "-" => BinOp::Sub,
+            "*" => BinOp::Mul,
+            "/" => BinOp::Div,
+            "%" => BinOp::Mod,
+            "@" => BinOp::At,
+            token => return Err(self.error(&format!("invalid binary operator `{token}`"))),
+        };
+        Ok(op)
+    }
+
+    fn unaop(&mut self) -> Result<UnaOp, String> {
+        let op = match self.next()? {
+            "not" => UnaOp::Not,
+            "+" => UnaOp::Pos,
+            "-" => UnaOp::Neg,
+            "fmt" => UnaOp::Fmt,
+            token => return Err(self.error(&format!("invalid unary operator `{token}`"))),
+        };
+        Ok(op)
+    }
+}
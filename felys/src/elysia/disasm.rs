@@ -0,0 +1,227 @@
+use crate::utils::bytecode::{Bytecode, Reg};
+use crate::utils::function::{Const, Pointer, Shape};
+use crate::utils::group::Group;
+use crate::utils::stages::{Callable, III};
+use std::fmt::{Display, Formatter, Result, Write};
+
+impl III {
+    pub fn disassemble<W: Write>(&self, f: &mut W) -> Result {
+        writeln!(f, ".main")?;
+        self.main.disassemble(f, self)?;
+
+        for (i, callable) in self.text.iter().enumerate() {
+            writeln!(f)?;
+            match self.names.get(i).filter(|x| !x.is_empty()) {
+                Some(name) => writeln!(f, ".text {i} {name}")?,
+                None => writeln!(f, ".text {i}")?,
+            }
+            callable.disassemble(f, self)?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, ".data")?;
+        for (i, constant) in self.data.iter().enumerate() {
+            write!(f, "    d{i:<4} ")?;
+            constant.disassemble(f)?;
+            writeln!(f)?;
+        }
+
+        if !self.natives.is_empty() {
+            writeln!(f)?;
+            writeln!(f, ".native")?;
+            for (i, (symbol, _)) in self.natives.iter().enumerate() {
+                writeln!(f, "    n{i:<4} {symbol}")?;
+            }
+        }
+
+        for (i, group) in self.groups.iter().enumerate() {
+            writeln!(f)?;
+            match Some(&group.name).filter(|x| !x.is_empty()) {
+                Some(name) => writeln!(f, ".group {i} {name}")?,
+                None => writeln!(f, ".group {i}")?,
+            }
+            group.disassemble(f)?;
+        }
+
+        if !self.symbols.is_empty() {
+            writeln!(f)?;
+            writeln!(f, ".symbol")?;
+            for (id, symbol) in self.symbols.iter() {
+                writeln!(f, "    {:<5} {symbol}", format!("#{id}"))?;
+            }
+        }
+
+        for (header, table) in [(".export", &self.exports), (".import", &self.imports)] {
+            if !table.is_empty() {
+                writeln!(f)?;
+                writeln!(f, "{header}")?;
+                for (symbol, pt, idx) in table.iter() {
+                    writeln!(f, "    {symbol} {pt} {idx}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Callable {
+    fn disassemble<W: Write>(&self, f: &mut W, program: &III) -> Result {
+        writeln!(f, "    args {}", self.args)?;
+        writeln!(f, "    registers {}", self.registers)?;
+        for (pc, bytecode) in self.bytecodes.iter().enumerate() {
+            let code = bytecode.to_string();
+            match bytecode.comment(program) {
+                Some(comment) => writeln!(f, "    {pc:04}  {code:<32} ; {comment}")?,
+                None => writeln!(f, "    {pc:04}  {code}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Group {
+    fn disassemble<W: Write>(&self, f: &mut W) -> Result {
+        let mut fields = self.indices.iter().collect::<Vec<_>>();
+        fields.sort_by_key(|(_, idx)| **idx);
+        for (id, idx) in fields {
+            writeln!(f, "    field #{id} {idx}")?;
+        }
+
+        let mut methods = self.methods.iter().collect::<Vec<_>>();
+        methods.sort();
+        for (id, idx) in methods {
+            writeln!(f, "    method #{id} fn {idx}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Bytecode {
+    fn comment(&self, program: &III) -> Option<String> {
+        match self {
+            Bytecode::Load(_, idx) => program.data.get(*idx as usize).map(|x| x.to_string()),
+            Bytecode::Pointer(_, Pointer::Rust, idx) => program
+                .natives
+                .get(*idx as usize)
+                .map(|(symbol, _)| symbol.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Bytecode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Bytecode::Arg(dst, idx) => write!(f, "{:<8} r{dst}, {idx}", "arg"),
+            Bytecode::Field(dst, src, id) => write!(f, "{:<8} r{dst}, r{src}, #{id}", "field"),
+            Bytecode::Unpack(dst, src, idx) => {
+                write!(f, "{:<8} r{dst}, r{src}, {idx}", "unpack")
+            }
+            Bytecode::Pointer(dst, pt, idx) => write!(f, "{:<8} r{dst}, {pt} {idx}", "pointer"),
+            Bytecode::Load(dst, idx) => write!(f, "{:<8} r{dst}, d{idx}", "load"),
+            Bytecode::Binary(dst, lhs, op, rhs) => {
+                write!(f, "{:<8} r{dst}, r{lhs}, {op}, r{rhs}", "binary")
+            }
+            Bytecode::Unary(dst, op, src) => {
+                write!(
+                    f,
+                    "{:<8} r{dst}, {}, r{src}",
+                    "unary",
+                    op.to_string().trim()
+                )
+            }
+            Bytecode::Check(dst, src) => write!(f, "{:<8} r{dst}, r{src}", "check"),
+            Bytecode::Iter(dst, src) => write!(f, "{:<8} r{dst}, r{src}", "iter"),
+            Bytecode::Has(dst, src) => write!(f, "{:<8} r{dst}, r{src}", "has"),
+            Bytecode::Next(dst, src) => write!(f, "{:<8} r{dst}, r{src}", "next"),
+            Bytecode::Test(dst, src, shape, n) => {
+                write!(f, "{:<8} r{dst}, r{src}, {shape} {n}", "test")
+            }
+            Bytecode::Tail(dst, src, n) => write!(f, "{:<8} r{dst}, r{src}, {n}", "tail"),
+            Bytecode::Call(dst, src, args) => {
+                write!(f, "{:<8} r{dst}, r{src}, {}", "call", Regs(args))
+            }
+            Bytecode::List(dst, args) => write!(f, "{:<8} r{dst}, {}", "list", Regs(args)),
+            Bytecode::Tuple(dst, args) => write!(f, "{:<8} r{dst}, {}", "tuple", Regs(args)),
+            Bytecode::Map(dst, args) => write!(f, "{:<8} r{dst}, {}", "map", Regs(args)),
+            Bytecode::Closure(dst, idx, args) => {
+                write!(f, "{:<8} r{dst}, fn {idx}, {}", "closure", Regs(args))
+            }
+            Bytecode::Index(dst, src, index) => {
+                write!(f, "{:<8} r{dst}, r{src}, r{index}", "index")
+            }
+            Bytecode::Nth(dst, src, index) => write!(f, "{:<8} r{dst}, r{src}, r{index}", "nth"),
+            Bytecode::Update(dst, src, id, value) => {
+                write!(f, "{:<8} r{dst}, r{src}, #{id}, r{value}", "update")
+            }
+            Bytecode::Method(dst, src, id, args) => {
+                write!(f, "{:<8} r{dst}, r{src}, #{id}, {}", "method", Regs(args))
+            }
+            Bytecode::Branch(cond, yes, no) => {
+                write!(f, "{:<8} r{cond}, @{yes:04}, @{no:04}", "branch")
+            }
+            Bytecode::Jump(target) => write!(f, "{:<8} @{target:04}", "jump"),
+            Bytecode::Return(src) => write!(f, "{:<8} r{src}", "return"),
+            Bytecode::Copy(dst, src) => write!(f, "{:<8} r{dst}, r{src}", "copy"),
+            Bytecode::TailCall(dst, src, args) => {
+                write!(f, "{:<8} r{dst}, r{src}, {}", "tcall", Regs(args))
+            }
+            Bytecode::TailMethod(dst, src, id, args) => {
+                write!(f, "{:<8} r{dst}, r{src}, #{id}, {}", "tmethod", Regs(args))
+            }
+        }
+    }
+}
+
+impl Display for Pointer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Pointer::Group => write!(f, "group"),
+            Pointer::Function => write!(f, "fn"),
+            Pointer::Rust => write!(f, "rust"),
+        }
+    }
+}
+
+impl Display for Shape {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Shape::Tuple => write!(f, "tuple"),
+            Shape::List => write!(f, "list"),
+            Shape::Rest => write!(f, "rest"),
+            Shape::Group => write!(f, "group"),
+            Shape::Number => write!(f, "number"),
+            Shape::Str => write!(f, "str"),
+            Shape::Bool => write!(f, "bool"),
+        }
+    }
+}
+
+impl Const {
+    fn disassemble<W: Write>(&self, f: &mut W) -> Result {
+        match self {
+            Const::Int(x) => write!(f, "int {x}"),
+            Const::Float(x) => write!(f, "float {}", f32::from_bits(*x)),
+            Const::Bool(x) => write!(f, "bool {x}"),
+            Const::Str(x) => write!(f, "str {x:?}"),
+            Const::I64(x) => write!(f, "i64 {x}"),
+            Const::F64(x) => write!(f, "f64 {}", f64::from_bits(*x)),
+        }
+    }
+}
+
+struct Regs<'a>(&'a [Reg]);
+
+impl Display for Regs<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "[")?;
+        let mut iter = self.0.iter();
+        if let Some(first) = iter.next() {
+            write!(f, "r{first}")?;
+        }
+        for reg in iter {
+            write!(f, ", r{reg}")?;
+        }
+        write!(f, "]")
+    }
+}
@@ -1,35 +1,114 @@
 use crate::utils::ast::{BinOp, UnaOp};
 use crate::utils::bytecode::{Bytecode, Index, Reg};
-use crate::utils::function::{Const, Pointer};
+use crate::utils::function::{Const, Pointer, Shape};
 use crate::utils::group::Group;
 use crate::utils::stages::{Callable, III};
 use std::io::Write;
 
+pub const MAGIC: [u8; 4] = *b"FELY";
+
+pub const VERSION: u32 = 7;
+
 impl III {
     pub fn dump<W: Write>(&self, buf: &mut W) -> std::io::Result<()> {
-        self.main.dump(buf)?;
+        let mut body = Vec::new();
+        section(&mut body, |buf| self.main.dump(buf))?;
 
-        buf.write_all(&Index::try_from(self.text.len()).unwrap().to_be_bytes())?;
-        for callable in self.text.iter() {
-            callable.dump(buf)?;
-        }
+        section(&mut body, |buf| {
+            buf.write_all(&Index::try_from(self.text.len()).unwrap().to_be_bytes())?;
+            for callable in self.text.iter() {
+                callable.dump(buf)?;
+            }
+            Ok(())
+        })?;
 
-        buf.write_all(&Index::try_from(self.data.len()).unwrap().to_be_bytes())?;
-        for constant in self.data.iter() {
-            constant.dump(buf)?;
-        }
+        section(&mut body, |buf| {
+            buf.write_all(&Index::try_from(self.data.len()).unwrap().to_be_bytes())?;
+            for constant in self.data.iter() {
+                constant.dump(buf)?;
+            }
+            Ok(())
+        })?;
+
+        section(&mut body, |buf| {
+            buf.write_all(&Index::try_from(self.groups.len()).unwrap().to_be_bytes())?;
+            for group in self.groups.iter() {
+                group.dump(buf)?;
+            }
+            Ok(())
+        })?;
+
+        section(&mut body, |buf| {
+            buf.write_all(&Index::try_from(self.natives.len()).unwrap().to_be_bytes())?;
+            for (symbol, _) in self.natives.iter() {
+                buf.write_all(&Index::try_from(symbol.len()).unwrap().to_be_bytes())?;
+                buf.write_all(symbol.as_bytes())?;
+            }
+            Ok(())
+        })?;
 
-        buf.write_all(&Index::try_from(self.groups.len()).unwrap().to_be_bytes())?;
-        for group in self.groups.iter() {
-            group.dump(buf)?;
+        section(&mut body, |buf| {
+            buf.write_all(&Index::try_from(self.names.len()).unwrap().to_be_bytes())?;
+            for name in self.names.iter() {
+                buf.write_all(&Index::try_from(name.len()).unwrap().to_be_bytes())?;
+                buf.write_all(name.as_bytes())?;
+            }
+            Ok(())
+        })?;
+
+        section(&mut body, |buf| {
+            buf.write_all(&Index::try_from(self.symbols.len()).unwrap().to_be_bytes())?;
+            for (id, symbol) in self.symbols.iter() {
+                buf.write_all(&id.to_be_bytes())?;
+                buf.write_all(&Index::try_from(symbol.len()).unwrap().to_be_bytes())?;
+                buf.write_all(symbol.as_bytes())?;
+            }
+            Ok(())
+        })?;
+
+        for table in [&self.exports, &self.imports] {
+            section(&mut body, |buf| {
+                buf.write_all(&Index::try_from(table.len()).unwrap().to_be_bytes())?;
+                for (symbol, pt, idx) in table.iter() {
+                    buf.write_all(&Index::try_from(symbol.len()).unwrap().to_be_bytes())?;
+                    buf.write_all(symbol.as_bytes())?;
+                    buf.write_all(&[pt.into()])?;
+                    buf.write_all(&idx.to_be_bytes())?;
+                }
+                Ok(())
+            })?;
         }
 
-        Ok(())
+        section(&mut body, |buf| buf.write_all(self.source.as_bytes()))?;
+
+        buf.write_all(&MAGIC)?;
+        buf.write_all(&VERSION.to_be_bytes())?;
+        buf.write_all(&checksum(&body).to_be_bytes())?;
+        buf.write_all(&body)
     }
 }
 
+fn section<F>(body: &mut Vec<u8>, f: F) -> std::io::Result<()>
+where
+    F: FnOnce(&mut Vec<u8>) -> std::io::Result<()>,
+{
+    let mut buf = Vec::new();
+    f(&mut buf)?;
+    body.write_all(&Index::try_from(buf.len()).unwrap().to_be_bytes())?;
+    body.write_all(&buf)
+}
+
+pub fn checksum(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811C9DC5, |hash, byte| {
+        (hash ^ *byte as u32).wrapping_mul(0x01000193)
+    })
+}
+
 impl Group {
     fn dump<W: Write>(&self, buf: &mut W) -> std::io::Result<()> {
+        buf.write_all(&Index::try_from(self.name.len()).unwrap().to_be_bytes())?;
+        buf.write_all(self.name.as_bytes())?;
+
         buf.write_all(&Index::try_from(self.indices.len()).unwrap().to_be_bytes())?;
         for (id, idx) in self.indices.iter() {
             buf.write_all(&id.to_be_bytes())?;
@@ -63,6 +142,14 @@ impl Const {
                 buf.write_all(&Index::try_from(x.len()).unwrap().to_be_bytes())?;
                 buf.write_all(x.as_bytes())
             }
+            Const::I64(x) => {
+                buf.write_all(&[0x4])?;
+                buf.write_all(&x.to_be_bytes())
+            }
+            Const::F64(x) => {
+                buf.write_all(&[0x5])?;
+                buf.write_all(&x.to_be_bytes())
+            }
         }
     }
 }
@@ -74,6 +161,11 @@ impl Callable {
         for bytecode in self.bytecodes.iter() {
             bytecode.dump(buf)?;
         }
+        buf.write_all(&Index::try_from(self.lines.len()).unwrap().to_be_bytes())?;
+        for (pc, span) in self.lines.iter() {
+            buf.write_all(&pc.to_be_bytes())?;
+            buf.write_all(&span.to_be_bytes())?;
+        }
         Ok(())
     }
 }
@@ -143,6 +235,54 @@ impl Bytecode {
             Bytecode::Copy(dst, src) => {
                 buf.write_all(&[0xF, *dst, *src])?;
             }
+            Bytecode::Map(dst, args) => {
+                buf.write_all(&[0x10, *dst, Reg::try_from(args.len()).unwrap()])?;
+                buf.write_all(args)?;
+            }
+            Bytecode::Nth(dst, src, index) => {
+                buf.write_all(&[0x11, *dst, *src, *index])?;
+            }
+            Bytecode::Check(dst, src) => {
+                buf.write_all(&[0x13, *dst, *src])?;
+            }
+            Bytecode::Test(dst, src, shape, n) => {
+                buf.write_all(&[0x14, *dst, *src, shape.into()])?;
+                buf.write_all(&n.to_be_bytes())?;
+            }
+            Bytecode::Tail(dst, src, n) => {
+                buf.write_all(&[0x15, *dst, *src])?;
+                buf.write_all(&n.to_be_bytes())?;
+            }
+            Bytecode::Update(dst, src, id, value) => {
+                buf.write_all(&[0x16, *dst, *src])?;
+                buf.write_all(&id.to_be_bytes())?;
+                buf.write_all(&[*value])?;
+            }
+            Bytecode::Closure(dst, idx, args) => {
+                buf.write_all(&[0x12, *dst])?;
+                buf.write_all(&idx.to_be_bytes())?;
+                buf.write_all(&[Reg::try_from(args.len()).unwrap()])?;
+                buf.write_all(args)?;
+            }
+            Bytecode::Iter(dst, src) => {
+                buf.write_all(&[0x17, *dst, *src])?;
+            }
+            Bytecode::Has(dst, src) => {
+                buf.write_all(&[0x18, *dst, *src])?;
+            }
+            Bytecode::Next(dst, src) => {
+                buf.write_all(&[0x19, *dst, *src])?;
+            }
+            Bytecode::TailCall(dst, src, args) => {
+                buf.write_all(&[0x1A, *dst, *src, Reg::try_from(args.len()).unwrap()])?;
+                buf.write_all(args)?;
+            }
+            Bytecode::TailMethod(dst, src, id, args) => {
+                buf.write_all(&[0x1B, *dst, *src])?;
+                buf.write_all(&(*id).to_be_bytes())?;
+                buf.write_all(&[Reg::try_from(args.len()).unwrap()])?;
+                buf.write_all(args)?;
+            }
         }
         Ok(())
     }
@@ -175,6 +315,7 @@ impl From<&UnaOp> for u8 {
             UnaOp::Not => 0x0,
             UnaOp::Pos => 0x1,
             UnaOp::Neg => 0x2,
+            UnaOp::Fmt => 0x3,
         }
     }
 }
@@ -188,3 +329,17 @@ impl From<&Pointer> for u8 {
         }
     }
 }
+
+impl From<&Shape> for u8 {
+    fn from(value: &Shape) -> Self {
+        match value {
+            Shape::Tuple => 0x0,
+            Shape::List => 0x1,
+            Shape::Rest => 0x2,
+            Shape::Group => 0x3,
+            Shape::Number => 0x4,
+            Shape::Str => 0x5,
+            Shape::Bool => 0x6,
+        }
+    }
+}
@@ -1,14 +1,31 @@
+use crate::elysia::dump::VERSION;
 use crate::elysia::runtime::object::Object;
-use crate::elysia::runtime::vm::DEPTH;
+use crate::elysia::runtime::vm::{DEPTH, NESTING};
+use crate::utils::bytecode::{Index, Reg};
+use crate::utils::snippet::Snippet;
+use std::fmt::{Display, Formatter};
+use std::io;
 
 pub enum Error {
     DataType(Object, &'static str),
     BinaryOperation(&'static str, Object, Object),
     UnaryOperation(&'static str, Object),
+    Overflow(String),
     NumArgsNotMatch(usize, usize),
     IndexOutOfBounds(Object, i32),
+    KeyNotFound(Object, Object),
+    FieldNotFound(Object, String),
+    MethodNotFound(Object, String),
     NotEnoughToUnpack(Object, u32),
+    Exhausted,
     StackOverflow,
+    TooDeeplyNested,
+    FuelExhausted(usize),
+    TooManyObjects(usize),
+    Interrupted,
+    Terminated,
+    Unlinked(String),
+    Entry,
     Any(String),
 }
 
@@ -28,6 +45,10 @@ impl From<Error> for String {
                 let s = format!("cannot apply `{op}` to `{src}`");
                 msg.push_str(&s);
             }
+            Error::Overflow(expr) => {
+                let s = format!("integer overflow in `{expr}`");
+                msg.push_str(&s);
+            }
             Error::NumArgsNotMatch(expected, args) => {
                 let s = format!("expected {expected} arguments, got {args}");
                 msg.push_str(&s);
@@ -36,17 +57,156 @@ impl From<Error> for String {
                 let s = format!("index {index} is out of boundaries for `{obj}`");
                 msg.push_str(&s);
             }
+            Error::KeyNotFound(obj, key) => {
+                let s = format!("key `{key}` does not exist in `{obj}`");
+                msg.push_str(&s);
+            }
+            Error::FieldNotFound(obj, field) => {
+                let s = format!("field `{field}` does not exist in `{obj}`");
+                msg.push_str(&s);
+            }
+            Error::MethodNotFound(obj, method) => {
+                let s = format!("method `{method}` does not exist in `{obj}`");
+                msg.push_str(&s);
+            }
             Error::NotEnoughToUnpack(obj, index) => {
                 let s = format!("cannot unpack element at index {index} for `{obj}`");
                 msg.push_str(&s);
             }
+            Error::Exhausted => msg.push_str("iterator is exhausted"),
             Error::StackOverflow => {
                 let s = format!("stack overflow, max depth set to {DEPTH}");
                 msg.push_str(&s);
             }
+            Error::TooDeeplyNested => {
+                let s = format!("native callbacks nested too deeply, max depth set to {NESTING}");
+                msg.push_str(&s);
+            }
+            Error::FuelExhausted(fuel) => {
+                let s = format!("ran out of fuel after {fuel} instructions");
+                msg.push_str(&s);
+            }
+            Error::TooManyObjects(objects) => {
+                let s = format!("allocated more than {objects} objects");
+                msg.push_str(&s);
+            }
+            Error::Interrupted => msg.push_str("interrupted by the host"),
+            Error::Terminated => msg.push_str("program has already terminated"),
+            Error::Unlinked(symbol) => {
+                let s = format!("symbol `{symbol}` is imported but never linked");
+                msg.push_str(&s);
+            }
+            Error::Entry => msg.push_str("program entry not found"),
             Error::Any(s) => msg.push_str(&s),
         }
         msg.push('\n');
         msg
     }
 }
+
+impl Error {
+    pub fn recover(self, source: &str, span: Option<Index>) -> String {
+        let mut msg = String::from(self);
+        if let Some(snippet) = span.and_then(|x| Snippet::locate(source, x as usize)) {
+            msg.pop();
+            msg.push_str(&format!(" at {}:{}\n", snippet.row + 1, snippet.col + 1));
+            msg.push_str(&snippet.to_string());
+        }
+        msg
+    }
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    Magic,
+    Version(u32),
+    Checksum(u32, u32),
+    Section(&'static str),
+    Tag(&'static str, u8),
+    Utf8(&'static str),
+    NativeNotFound(String),
+    Register(Option<Index>, Index, Reg),
+    Undefined(Option<Index>, Index, Reg),
+    Reference(Option<Index>, Index, &'static str, Index),
+    Target(Option<Index>, Index, Index),
+    Fallthrough(Option<Index>),
+    Pairs(Option<Index>, Index),
+    Group(Index),
+    Symbol(String),
+    Unresolved(String),
+    Redefined(String),
+    Entry,
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let at = |idx: &Option<Index>| match idx {
+            Some(x) => format!("text {x}"),
+            None => "main".to_string(),
+        };
+        match self {
+            LoadError::Io(e) => write!(f, "{e}"),
+            LoadError::Magic => write!(f, "not a felys binary"),
+            LoadError::Version(version) => {
+                write!(
+                    f,
+                    "unsupported format version {version}, expecting {VERSION}"
+                )
+            }
+            LoadError::Checksum(expected, found) => {
+                write!(
+                    f,
+                    "checksum mismatch, expecting {expected:08x}, found {found:08x}"
+                )
+            }
+            LoadError::Section(name) => write!(f, "section `{name}` does not match its length"),
+            LoadError::Tag(kind, tag) => write!(f, "invalid {kind} tag {tag:#04x}"),
+            LoadError::Utf8(kind) => write!(f, "invalid utf-8 in {kind}"),
+            LoadError::NativeNotFound(symbol) => write!(f, "native `{symbol}` not found"),
+            LoadError::Register(idx, pc, reg) => {
+                write!(
+                    f,
+                    "register r{reg} is out of bounds in {} @{pc:04}",
+                    at(idx)
+                )
+            }
+            LoadError::Undefined(idx, pc, reg) => {
+                write!(
+                    f,
+                    "register r{reg} is read before it is defined in {} @{pc:04}",
+                    at(idx)
+                )
+            }
+            LoadError::Reference(idx, pc, kind, x) => {
+                write!(f, "{kind} {x} is out of bounds in {} @{pc:04}", at(idx))
+            }
+            LoadError::Target(idx, pc, target) => {
+                write!(
+                    f,
+                    "target @{target:04} is out of bounds in {} @{pc:04}",
+                    at(idx)
+                )
+            }
+            LoadError::Fallthrough(idx) => {
+                write!(f, "{} does not end with a terminator", at(idx))
+            }
+            LoadError::Pairs(idx, pc) => {
+                write!(f, "map has an unpaired key in {} @{pc:04}", at(idx))
+            }
+            LoadError::Group(idx) => write!(f, "group {idx} is malformed"),
+            LoadError::Symbol(symbol) => write!(f, "symbol `{symbol}` is malformed"),
+            LoadError::Unresolved(symbol) => write!(f, "symbol `{symbol}` is not defined"),
+            LoadError::Redefined(symbol) => {
+                write!(f, "symbol `{symbol}` is defined more than once")
+            }
+            LoadError::Entry => write!(f, "program entry not found"),
+        }
+    }
+}
+
+impl From<io::Error> for LoadError {
+    fn from(value: io::Error) -> Self {
+        LoadError::Io(value)
+    }
+}
@@ -0,0 +1,254 @@
+use crate::elysia::error::LoadError;
+use crate::utils::bytecode::{Bytecode, Id, Index};
+use crate::utils::function::{Const, Pointer, Shape};
+use crate::utils::group::Group;
+use crate::utils::stages::{Callable, III};
+use crate::utils::stdlib::registry::Native;
+use std::collections::HashMap;
+
+type Result<T> = std::result::Result<T, LoadError>;
+
+struct Layout {
+    text: Vec<Index>,
+    groups: Vec<Index>,
+    data: Vec<Index>,
+    natives: Vec<Index>,
+    ids: HashMap<Id, Id>,
+    offset: Index,
+}
+
+impl Layout {
+    fn id(&self, id: Id) -> Result<Id> {
+        self.ids
+            .get(&id)
+            .copied()
+            .ok_or(LoadError::Symbol(format!("#{id}")))
+    }
+}
+
+#[derive(Default)]
+struct Linker {
+    data: Vec<Const>,
+    consts: HashMap<Const, Index>,
+    natives: Vec<(String, Native)>,
+    symbols: HashMap<String, Id>,
+    source: String,
+    text: Index,
+    groups: Index,
+}
+
+impl Linker {
+    fn layout(&mut self, unit: &III) -> Layout {
+        let slots = |len: usize, pt: Pointer, next: &mut Index| {
+            (0..len)
+                .map(|i| {
+                    if imported(&unit.imports, pt, i) {
+                        Index::MAX
+                    } else {
+                        *next += 1;
+                        *next - 1
+                    }
+                })
+                .collect()
+        };
+        let text = slots(unit.text.len(), Pointer::Function, &mut self.text);
+        let groups = slots(unit.groups.len(), Pointer::Group, &mut self.groups);
+
+        let mut data = Vec::new();
+        for constant in unit.data.iter() {
+            let len = Index::try_from(self.data.len()).unwrap();
+            let index = *self.consts.entry(constant.clone()).or_insert(len);
+            if index == len {
+                self.data.push(constant.clone());
+            }
+            data.push(index);
+        }
+
+        let mut natives = Vec::new();
+        for (symbol, native) in unit.natives.iter() {
+            let index = match self.natives.iter().position(|(x, _)| x == symbol) {
+                Some(index) => index,
+                None => {
+                    self.natives.push((symbol.clone(), native.clone()));
+                    self.natives.len() - 1
+                }
+            };
+            natives.push(Index::try_from(index).unwrap());
+        }
+
+        let mut ids = HashMap::new();
+        for (id, symbol) in unit.symbols.iter() {
+            let len = Id::try_from(self.symbols.len()).unwrap();
+            ids.insert(*id, *self.symbols.entry(symbol.clone()).or_insert(len));
+        }
+
+        if !self.source.is_empty() {
+            self.source.push('\n');
+        }
+        let offset = Index::try_from(self.source.len()).unwrap();
+        self.source.push_str(&unit.source);
+
+        Layout {
+            text,
+            groups,
+            data,
+            natives,
+            ids,
+            offset,
+        }
+    }
+}
+
+impl III {
+    pub fn link(self, units: Vec<III>) -> Result<III> {
+        let units = [self].into_iter().chain(units).collect::<Vec<_>>();
+
+        let mut defined = HashMap::new();
+        for (u, unit) in units.iter().enumerate() {
+            for (symbol, pt, idx) in unit.exports.iter() {
+                if defined.insert(symbol.as_str(), (u, *pt, *idx)).is_some() {
+                    return Err(LoadError::Redefined(symbol.clone()));
+                }
+            }
+        }
+
+        let mut linker = Linker::default();
+        let mut layouts = units
+            .iter()
+            .map(|unit| linker.layout(unit))
+            .collect::<Vec<_>>();
+
+        for (u, unit) in units.iter().enumerate() {
+            for (symbol, pt, idx) in unit.imports.iter() {
+                let Some(&(v, kind, target)) = defined.get(symbol.as_str()) else {
+                    return Err(LoadError::Unresolved(symbol.clone()));
+                };
+                if kind != *pt {
+                    return Err(LoadError::Unresolved(symbol.clone()));
+                }
+                let (idx, target) = (*idx as usize, target as usize);
+                if kind == Pointer::Group {
+                    layouts[u].groups[idx] = layouts[v].groups[target];
+                } else {
+                    layouts[u].text[idx] = layouts[v].text[target];
+                }
+            }
+        }
+
+        let mut main = None;
+        let mut text = Vec::new();
+        let mut names = Vec::new();
+        let mut groups = Vec::new();
+        for (unit, layout) in units.into_iter().zip(layouts) {
+            let imports = unit.imports;
+            for (i, callable) in unit.text.into_iter().enumerate() {
+                if !imported(&imports, Pointer::Function, i) {
+                    text.push(callable.relocate(&layout)?);
+                    names.push(unit.names.get(i).cloned().unwrap_or_default());
+                }
+            }
+            for (i, group) in unit.groups.into_iter().enumerate() {
+                if !imported(&imports, Pointer::Group, i) {
+                    groups.push(group.relocate(&layout)?);
+                }
+            }
+            if main.is_none() && !unit.main.bytecodes.is_empty() {
+                main = Some(unit.main.relocate(&layout)?);
+            }
+        }
+
+        let mut symbols = linker
+            .symbols
+            .into_iter()
+            .map(|(symbol, id)| (id, symbol))
+            .collect::<Vec<_>>();
+        symbols.sort();
+        let iii = III {
+            main: main.ok_or(LoadError::Entry)?,
+            text,
+            data: linker.data,
+            groups,
+            natives: linker.natives,
+            names,
+            symbols,
+            exports: Vec::new(),
+            imports: Vec::new(),
+            source: linker.source,
+        };
+        iii.verify()?;
+        Ok(iii)
+    }
+}
+
+fn imported(imports: &[(String, Pointer, Index)], pt: Pointer, i: usize) -> bool {
+    imports
+        .iter()
+        .any(|(_, x, idx)| *x == pt && *idx as usize == i)
+}
+
+impl Callable {
+    fn relocate(self, layout: &Layout) -> Result<Callable> {
+        let mut bytecodes = Vec::new();
+        for bytecode in self.bytecodes {
+            let bytecode = match bytecode {
+                Bytecode::Field(dst, src, id) => Bytecode::Field(dst, src, layout.id(id)?),
+                Bytecode::Update(dst, src, id, value) => {
+                    Bytecode::Update(dst, src, layout.id(id)?, value)
+                }
+                Bytecode::Method(dst, src, id, args) => {
+                    Bytecode::Method(dst, src, layout.id(id)?, args)
+                }
+                Bytecode::TailMethod(dst, src, id, args) => {
+                    Bytecode::TailMethod(dst, src, layout.id(id)?, args)
+                }
+                Bytecode::Pointer(dst, pt, idx) => {
+                    let table = match pt {
+                        Pointer::Group => &layout.groups,
+                        Pointer::Function => &layout.text,
+                        Pointer::Rust => &layout.natives,
+                    };
+                    Bytecode::Pointer(dst, pt, table[idx as usize])
+                }
+                Bytecode::Load(dst, idx) => Bytecode::Load(dst, layout.data[idx as usize]),
+                Bytecode::Closure(dst, idx, args) => {
+                    Bytecode::Closure(dst, layout.text[idx as usize], args)
+                }
+                Bytecode::Test(dst, src, Shape::Group, idx) => {
+                    Bytecode::Test(dst, src, Shape::Group, layout.groups[idx as usize])
+                }
+                bytecode => bytecode,
+            };
+            bytecodes.push(bytecode);
+        }
+        let lines = self
+            .lines
+            .into_iter()
+            .map(|(pc, span)| (pc, span + layout.offset))
+            .collect();
+        Ok(Callable {
+            args: self.args,
+            registers: self.registers,
+            bytecodes,
+            lines,
+        })
+    }
+}
+
+impl Group {
+    fn relocate(self, layout: &Layout) -> Result<Group> {
+        let mut group = Group {
+            name: self.name,
+            indices: HashMap::new(),
+            methods: HashMap::new(),
+        };
+        for (id, idx) in self.indices {
+            group.indices.insert(layout.id(id)?, idx);
+        }
+        for (id, idx) in self.methods {
+            group
+                .methods
+                .insert(layout.id(id)?, layout.text[idx as usize]);
+        }
+        Ok(group)
+    }
+}
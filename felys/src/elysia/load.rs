@@ -1,46 +1,131 @@
+use crate::elysia::dump::{MAGIC, VERSION, checksum};
+use crate::elysia::error::LoadError;
 use crate::utils::ast::{BinOp, UnaOp};
-use crate::utils::bytecode::Bytecode;
-use crate::utils::function::{Const, Pointer};
+use crate::utils::bytecode::{Bytecode, Index};
+use crate::utils::function::{Const, Pointer, Shape};
 use crate::utils::group::Group;
 use crate::utils::stages::{Callable, III};
+use crate::utils::stdlib::registry::Registry;
 use std::collections::HashMap;
-use std::io::{Error, ErrorKind, Read, Result};
+use std::io::{self, Read};
+
+type Result<T> = std::result::Result<T, LoadError>;
 
 impl III {
-    pub fn load<T: Load>(src: &mut T) -> Result<III> {
-        Ok(III {
-            main: Callable::load(src)?,
-            text: {
+    pub fn load<T: Load>(src: &mut T, registry: &Registry) -> Result<III> {
+        let mut magic = [0; 4];
+        if src.read_exact(&mut magic).is_err() || magic != MAGIC {
+            return Err(LoadError::Magic);
+        }
+        let version = src.u32()?;
+        if version != VERSION {
+            return Err(LoadError::Version(version));
+        }
+        let expected = src.u32()?;
+        let mut body = Vec::new();
+        src.read_to_end(&mut body)?;
+        let found = checksum(&body);
+        if expected != found {
+            return Err(LoadError::Checksum(expected, found));
+        }
+
+        let src = &mut body.as_slice();
+        let iii = III {
+            main: section(src, "main", |src| Callable::load(src))?,
+            text: section(src, "text", |src| {
                 let len = src.u32()?;
-                let mut text = Vec::with_capacity(len as usize);
+                let mut text = Vec::new();
                 for _ in 0..len {
                     text.push(Callable::load(src)?);
                 }
-                text
-            },
-            data: {
+                Ok(text)
+            })?,
+            data: section(src, "data", |src| {
                 let len = src.u32()?;
-                let mut data = Vec::with_capacity(len as usize);
+                let mut data = Vec::new();
                 for _ in 0..len {
                     data.push(Const::load(src)?);
                 }
-                data
-            },
-            groups: {
+                Ok(data)
+            })?,
+            groups: section(src, "groups", |src| {
                 let len = src.u32()?;
-                let mut groups = Vec::with_capacity(len as usize);
+                let mut groups = Vec::new();
                 for _ in 0..len {
                     groups.push(Group::load(src)?);
                 }
-                groups
-            },
-        })
+                Ok(groups)
+            })?,
+            natives: section(src, "natives", |src| {
+                let len = src.u32()?;
+                let mut natives = Vec::new();
+                for _ in 0..len {
+                    let symbol = src.string("native")?;
+                    let Some(native) = registry.find(&symbol) else {
+                        return Err(LoadError::NativeNotFound(symbol));
+                    };
+                    natives.push((symbol, native.clone()));
+                }
+                Ok(natives)
+            })?,
+            names: section(src, "names", |src| {
+                let len = src.u32()?;
+                let mut names = Vec::new();
+                for _ in 0..len {
+                    names.push(src.string("name")?);
+                }
+                Ok(names)
+            })?,
+            symbols: section(src, "symbols", |src| {
+                let len = src.u32()?;
+                let mut symbols = Vec::new();
+                for _ in 0..len {
+                    symbols.push((src.u32()?, src.string("symbol")?));
+                }
+                Ok(symbols)
+            })?,
+            exports: section(src, "exports", |src| src.table())?,
+            imports: section(src, "imports", |src| src.table())?,
+            source: section(src, "source", |src| {
+                let mut bytes = Vec::new();
+                src.read_to_end(&mut bytes)?;
+                String::from_utf8(bytes).map_err(|_| LoadError::Utf8("source"))
+            })?,
+        };
+        if !src.is_empty() {
+            return Err(LoadError::Section("source"));
+        }
+
+        iii.verify()?;
+        Ok(iii)
+    }
+}
+
+fn section<T, F>(src: &mut &[u8], name: &'static str, f: F) -> Result<T>
+where
+    F: FnOnce(&mut &[u8]) -> Result<T>,
+{
+    let len = src.u32().map_err(|_| LoadError::Section(name))? as usize;
+    if len > src.len() {
+        return Err(LoadError::Section(name));
+    }
+    let (mut content, rest) = src.split_at(len);
+    *src = rest;
+    let value = f(&mut content).map_err(|e| match e {
+        LoadError::Io(_) => LoadError::Section(name),
+        e => e,
+    })?;
+    if content.is_empty() {
+        Ok(value)
+    } else {
+        Err(LoadError::Section(name))
     }
 }
 
 impl Group {
     fn load<T: Load>(src: &mut T) -> Result<Group> {
         let group = Group {
+            name: src.string("group")?,
             indices: {
                 let len = src.u32()?;
                 let mut indices = HashMap::new();
@@ -70,11 +155,12 @@ impl Const {
             0x1 => Const::Float(src.u32()?),
             0x2 => Const::Bool(src.u8()? != 0),
             0x3 => {
-                let s = String::from_utf8(src.str()?)
-                    .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+                let s = src.string("constant")?;
                 Const::Str(s.into())
             }
-            _ => return Err(Error::new(ErrorKind::InvalidData, tag.to_string())),
+            0x4 => Const::I64(src.i64()?),
+            0x5 => Const::F64(src.u64()?),
+            _ => return Err(LoadError::Tag("constant", tag)),
         };
         Ok(constant)
     }
@@ -87,12 +173,20 @@ impl Callable {
             registers: src.u8()?,
             bytecodes: {
                 let len = src.u32()?;
-                let mut bytecodes = Vec::with_capacity(len as usize);
+                let mut bytecodes = Vec::new();
                 for _ in 0..len {
                     bytecodes.push(Bytecode::load(src)?);
                 }
                 bytecodes
             },
+            lines: {
+                let len = src.u32()?;
+                let mut lines = Vec::new();
+                for _ in 0..len {
+                    lines.push((src.u32()?, src.u32()?));
+                }
+                lines
+            },
         };
         Ok(callable)
     }
@@ -118,7 +212,19 @@ impl Bytecode {
             0xD => Bytecode::Jump(src.u32()?),
             0xE => Bytecode::Return(src.u8()?),
             0xF => Bytecode::Copy(src.u8()?, src.u8()?),
-            _ => return Err(Error::new(ErrorKind::InvalidData, tag.to_string())),
+            0x10 => Bytecode::Map(src.u8()?, src.vec()?),
+            0x11 => Bytecode::Nth(src.u8()?, src.u8()?, src.u8()?),
+            0x12 => Bytecode::Closure(src.u8()?, src.u32()?, src.vec()?),
+            0x13 => Bytecode::Check(src.u8()?, src.u8()?),
+            0x14 => Bytecode::Test(src.u8()?, src.u8()?, Shape::load(src)?, src.u32()?),
+            0x15 => Bytecode::Tail(src.u8()?, src.u8()?, src.u32()?),
+            0x16 => Bytecode::Update(src.u8()?, src.u8()?, src.u32()?, src.u8()?),
+            0x17 => Bytecode::Iter(src.u8()?, src.u8()?),
+            0x18 => Bytecode::Has(src.u8()?, src.u8()?),
+            0x19 => Bytecode::Next(src.u8()?, src.u8()?),
+            0x1A => Bytecode::TailCall(src.u8()?, src.u8()?, src.vec()?),
+            0x1B => Bytecode::TailMethod(src.u8()?, src.u8()?, src.u32()?, src.vec()?),
+            _ => return Err(LoadError::Tag("bytecode", tag)),
         };
 
         Ok(bytecode)
@@ -132,12 +238,29 @@ impl Pointer {
             0x0 => Pointer::Group,
             0x1 => Pointer::Function,
             0x2 => Pointer::Rust,
-            _ => return Err(Error::new(ErrorKind::InvalidData, x.to_string())),
+            _ => return Err(LoadError::Tag("pointer", x)),
         };
         Ok(pt)
     }
 }
 
+impl Shape {
+    fn load<T: Load>(src: &mut T) -> Result<Shape> {
+        let x = src.u8()?;
+        let shape = match x {
+            0x0 => Shape::Tuple,
+            0x1 => Shape::List,
+            0x2 => Shape::Rest,
+            0x3 => Shape::Group,
+            0x4 => Shape::Number,
+            0x5 => Shape::Str,
+            0x6 => Shape::Bool,
+            _ => return Err(LoadError::Tag("shape", x)),
+        };
+        Ok(shape)
+    }
+}
+
 impl BinOp {
     fn load<T: Load>(src: &mut T) -> Result<BinOp> {
         let x = src.u8()?;
@@ -156,9 +279,7 @@ impl BinOp {
             0xB => BinOp::Div,
             0xC => BinOp::Mod,
             0xD => BinOp::At,
-            _ => {
-                return Err(Error::new(ErrorKind::InvalidData, x.to_string()));
-            }
+            _ => return Err(LoadError::Tag("binary operator", x)),
         };
         Ok(op)
     }
@@ -171,7 +292,8 @@ impl UnaOp {
             0x0 => UnaOp::Not,
             0x1 => UnaOp::Pos,
             0x2 => UnaOp::Neg,
-            _ => return Err(Error::new(ErrorKind::InvalidData, x.to_string())),
+            0x3 => UnaOp::Fmt,
+            _ => return Err(LoadError::Tag("unary operator", x)),
         };
         Ok(op)
     }
@@ -196,11 +318,38 @@ pub trait Load: Read {
         Ok(i32::from_be_bytes(buf))
     }
 
-    fn str(&mut self) -> Result<Vec<u8>> {
+    fn u64(&mut self) -> Result<u64> {
+        let mut buf = [0; 8];
+        self.read_exact(&mut buf)?;
+        Ok(u64::from_be_bytes(buf))
+    }
+
+    fn i64(&mut self) -> Result<i64> {
+        let mut buf = [0; 8];
+        self.read_exact(&mut buf)?;
+        Ok(i64::from_be_bytes(buf))
+    }
+
+    fn string(&mut self, kind: &'static str) -> Result<String> {
         let len = self.u32()?;
-        let mut vec = vec![0; len as usize];
-        self.read_exact(&mut vec)?;
-        Ok(vec)
+        let mut vec = Vec::new();
+        Read::take(&mut *self, len as u64).read_to_end(&mut vec)?;
+        if vec.len() != len as usize {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+        String::from_utf8(vec).map_err(|_| LoadError::Utf8(kind))
+    }
+
+    fn table(&mut self) -> Result<Vec<(String, Pointer, Index)>>
+    where
+        Self: Sized,
+    {
+        let len = self.u32()?;
+        let mut table = Vec::new();
+        for _ in 0..len {
+            table.push((self.string("symbol")?, Pointer::load(self)?, self.u32()?));
+        }
+        Ok(table)
     }
 
     fn vec(&mut self) -> Result<Vec<u8>> {
@@ -1,4 +1,8 @@
+mod asm;
+mod disasm;
 mod dump;
-mod error;
+pub mod error;
+mod link;
 mod load;
 pub mod runtime;
+mod verify;
@@ -0,0 +1,7 @@
+use std::io::{BufRead, Write};
+
+pub struct Io<'a> {
+    pub stdin: &'a mut dyn BufRead,
+    pub stdout: &'a mut dyn Write,
+    pub stderr: &'a mut dyn Write,
+}
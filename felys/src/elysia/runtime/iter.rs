@@ -0,0 +1,70 @@
+use crate::elysia::runtime::map::Map;
+use crate::elysia::runtime::object::Object;
+use crate::utils::bytecode::Index;
+use crate::utils::stdlib::nn::operator::Node;
+use std::rc::Rc;
+
+#[derive(Clone, Debug)]
+pub enum Iter {
+    List(Rc<[Object]>, usize),
+    Map(Map, usize),
+    Str(Rc<str>, usize),
+    Range(i32, i32),
+    Rows(Rc<Node>, usize),
+    Group(Object, Index, Option<Option<(Object, Object)>>),
+    Done,
+}
+
+impl Iter {
+    pub fn has(&self) -> bool {
+        match self {
+            Iter::List(list, i) => *i < list.len(),
+            Iter::Map(map, i) => *i < map.len(),
+            Iter::Str(s, i) => *i < s.len(),
+            Iter::Range(start, end) => start < end,
+            Iter::Rows(node, i) => Some(*i) < node.rows(),
+            Iter::Group(_, _, peeked) => matches!(peeked, Some(Some(_))),
+            Iter::Done => false,
+        }
+    }
+
+    pub fn next(&mut self) -> Option<Object> {
+        match self {
+            Iter::List(list, i) => {
+                let obj = list.get(*i).cloned()?;
+                *i += 1;
+                Some(obj)
+            }
+            Iter::Map(map, i) => {
+                let (key, value) = map.nth(*i)?;
+                let obj = Object::Tuple([key.into(), value.clone()].into());
+                *i += 1;
+                Some(obj)
+            }
+            Iter::Str(s, i) => {
+                let c = s[*i..].chars().next()?;
+                *i += c.len_utf8();
+                Some(Object::Str(c.to_string().into()))
+            }
+            Iter::Range(start, end) => {
+                if start >= end {
+                    return None;
+                }
+                let obj = Object::Int(*start);
+                *start += 1;
+                Some(obj)
+            }
+            Iter::Rows(node, i) => {
+                let row = node.row(*i)?;
+                *i += 1;
+                Some(Object::Node(row.into()))
+            }
+            Iter::Group(state, _, peeked) => {
+                let (obj, next) = peeked.take()??;
+                *state = next;
+                Some(obj)
+            }
+            Iter::Done => None,
+        }
+    }
+}
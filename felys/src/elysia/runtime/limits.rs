@@ -0,0 +1,9 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+#[derive(Clone, Default)]
+pub struct Limits {
+    pub fuel: Option<usize>,
+    pub objects: Option<usize>,
+    pub interrupt: Option<Arc<AtomicBool>>,
+}
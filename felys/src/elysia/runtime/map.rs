@@ -0,0 +1,282 @@
+use crate::elysia::error::Error;
+use crate::elysia::runtime::object::Object;
+use std::cmp::Ordering;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::rc::Rc;
+
+#[derive(Clone, Debug)]
+pub enum Key {
+    Bool(bool),
+    Int(i32),
+    I64(i64),
+    Str(Rc<str>),
+    Tuple(Rc<[Key]>),
+}
+
+impl TryFrom<&Object> for Key {
+    type Error = Error;
+
+    fn try_from(value: &Object) -> Result<Self, Self::Error> {
+        let key = match value {
+            Object::Bool(x) => Key::Bool(*x),
+            Object::Int(x) => Key::Int(*x),
+            Object::I64(x) => Key::I64(*x),
+            Object::Str(x) => Key::Str(x.clone()),
+            Object::Tuple(objs) => Key::Tuple(
+                objs.iter()
+                    .map(Key::try_from)
+                    .collect::<Result<Vec<_>, _>>()?
+                    .into(),
+            ),
+            _ => return Err(Error::DataType(value.clone(), "key")),
+        };
+        Ok(key)
+    }
+}
+
+impl Key {
+    fn rank(&self) -> u8 {
+        match self {
+            Key::Bool(_) => 0,
+            Key::Int(_) | Key::I64(_) => 1,
+            Key::Str(_) => 2,
+            Key::Tuple(_) => 3,
+        }
+    }
+
+    fn integer(&self) -> Option<i64> {
+        match self {
+            Key::Int(x) => Some(*x as i64),
+            Key::I64(x) => Some(*x),
+            _ => None,
+        }
+    }
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Key::Bool(x), Key::Bool(y)) => x.cmp(y),
+            (Key::Str(x), Key::Str(y)) => x.cmp(y),
+            (Key::Tuple(x), Key::Tuple(y)) => x.cmp(y),
+            _ => match (self.integer(), other.integer()) {
+                (Some(x), Some(y)) => x.cmp(&y),
+                _ => self.rank().cmp(&other.rank()),
+            },
+        }
+    }
+}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Key {}
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rank().hash(state);
+        match self {
+            Key::Bool(x) => x.hash(state),
+            Key::Int(_) | Key::I64(_) => self.integer().hash(state),
+            Key::Str(x) => x.hash(state),
+            Key::Tuple(x) => x.hash(state),
+        }
+    }
+}
+
+impl From<&Key> for Object {
+    fn from(value: &Key) -> Self {
+        match value {
+            Key::Bool(x) => Object::Bool(*x),
+            Key::Int(x) => Object::Int(*x),
+            Key::I64(x) => Object::I64(*x),
+            Key::Str(x) => Object::Str(x.clone()),
+            Key::Tuple(keys) => Object::Tuple(keys.iter().map(Object::from).collect()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Map {
+    root: Option<Rc<Node>>,
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    key: Key,
+    value: Object,
+    priority: u64,
+    size: usize,
+    left: Option<Rc<Node>>,
+    right: Option<Rc<Node>>,
+}
+
+impl Map {
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn get(&self, key: &Key) -> Option<&Object> {
+        let mut cursor = &self.root;
+        while let Some(node) = cursor {
+            cursor = match key.cmp(&node.key) {
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+                Ordering::Equal => return Some(&node.value),
+            };
+        }
+        None
+    }
+
+    pub fn nth(&self, mut n: usize) -> Option<(&Key, &Object)> {
+        let mut cursor = &self.root;
+        while let Some(node) = cursor {
+            let left = size(&node.left);
+            cursor = match n.cmp(&left) {
+                Ordering::Less => &node.left,
+                Ordering::Equal => return Some((&node.key, &node.value)),
+                Ordering::Greater => {
+                    n -= left + 1;
+                    &node.right
+                }
+            };
+        }
+        None
+    }
+
+    pub fn insert(&self, key: Key, value: Object) -> Map {
+        let priority = priority(&key);
+        Map {
+            root: Some(insert(&self.root, key, value, priority)),
+        }
+    }
+
+    pub fn remove(&self, key: &Key) -> Map {
+        match remove(&self.root, key) {
+            Some(root) => Map { root },
+            None => self.clone(),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Key, &Object)> {
+        (0..self.len()).map(|i| self.nth(i).unwrap())
+    }
+}
+
+fn size(node: &Option<Rc<Node>>) -> usize {
+    node.as_ref().map(|x| x.size).unwrap_or(0)
+}
+
+fn priority(key: &Key) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn build(mut node: Node) -> Rc<Node> {
+    node.size = size(&node.left) + size(&node.right) + 1;
+    Rc::new(node)
+}
+
+fn insert(node: &Option<Rc<Node>>, key: Key, value: Object, priority: u64) -> Rc<Node> {
+    let Some(old) = node else {
+        return build(Node {
+            key,
+            value,
+            priority,
+            size: 1,
+            left: None,
+            right: None,
+        });
+    };
+    match key.cmp(&old.key) {
+        Ordering::Equal => build(Node {
+            value,
+            ..Node::clone(old)
+        }),
+        _ if priority > old.priority => {
+            let (left, right) = split(node, &key);
+            build(Node {
+                key,
+                value,
+                priority,
+                size: 1,
+                left,
+                right,
+            })
+        }
+        Ordering::Less => build(Node {
+            left: Some(insert(&old.left, key, value, priority)),
+            ..Node::clone(old)
+        }),
+        Ordering::Greater => build(Node {
+            right: Some(insert(&old.right, key, value, priority)),
+            ..Node::clone(old)
+        }),
+    }
+}
+
+fn split(node: &Option<Rc<Node>>, key: &Key) -> (Option<Rc<Node>>, Option<Rc<Node>>) {
+    let Some(old) = node else {
+        return (None, None);
+    };
+    if *key < old.key {
+        let (left, right) = split(&old.left, key);
+        let node = build(Node {
+            left: right,
+            ..Node::clone(old)
+        });
+        (left, Some(node))
+    } else {
+        let (left, right) = split(&old.right, key);
+        let node = build(Node {
+            right: left,
+            ..Node::clone(old)
+        });
+        (Some(node), right)
+    }
+}
+
+fn merge(left: &Option<Rc<Node>>, right: &Option<Rc<Node>>) -> Option<Rc<Node>> {
+    let node = match (left, right) {
+        (None, other) | (other, None) => return other.clone(),
+        (Some(l), Some(r)) if l.priority >= r.priority => build(Node {
+            right: merge(&l.right, right),
+            ..Node::clone(l)
+        }),
+        (_, Some(r)) => build(Node {
+            left: merge(left, &r.left),
+            ..Node::clone(r)
+        }),
+    };
+    Some(node)
+}
+
+fn remove(node: &Option<Rc<Node>>, key: &Key) -> Option<Option<Rc<Node>>> {
+    let old = node.as_ref()?;
+    let node = match key.cmp(&old.key) {
+        Ordering::Equal => return Some(merge(&old.left, &old.right)),
+        Ordering::Less => build(Node {
+            left: remove(&old.left, key)?,
+            ..Node::clone(old)
+        }),
+        Ordering::Greater => build(Node {
+            right: remove(&old.right, key)?,
+            ..Node::clone(old)
+        }),
+    };
+    Some(Some(node))
+}
@@ -1,2 +1,6 @@
+pub mod io;
+pub mod iter;
+pub mod limits;
+pub mod map;
 pub mod object;
 pub mod vm;
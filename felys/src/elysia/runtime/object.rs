@@ -1,22 +1,31 @@
 use crate::elysia::error::Error;
+use crate::elysia::runtime::iter::Iter;
+use crate::elysia::runtime::map::Map;
 use crate::utils::ast::{BinOp, UnaOp};
 use crate::utils::bytecode::Index;
 use crate::utils::function::Pointer;
 use crate::utils::stdlib::nn::operator::Node;
+use std::cell::RefCell;
 use std::fmt::{Display, Formatter};
 use std::rc::Rc;
 
 #[derive(Clone, Debug)]
 pub enum Object {
     Pointer(Pointer, Index),
+    Closure(Index, Rc<[Object]>),
+    Err(Rc<Object>),
     List(Rc<[Object]>),
     Tuple(Rc<[Object]>),
     Group(Index, Rc<[Object]>),
+    Map(Map),
     Str(Rc<str>),
     Int(i32),
     Float(f32),
     Bool(bool),
     Node(Rc<Node>),
+    I64(i64),
+    F64(f64),
+    Iter(Rc<RefCell<Iter>>),
 }
 
 impl Display for Object {
@@ -27,6 +36,8 @@ impl Display for Object {
                 Pointer::Group => write!(f, "G @ {ptr:#010x}"),
                 Pointer::Rust => write!(f, "R @ {ptr:#010x}"),
             },
+            Object::Closure(ptr, _) => write!(f, "C @ {ptr:#010x}"),
+            Object::Err(x) => write!(f, "Err({x})"),
             Object::List(objs) => {
                 write!(f, "[")?;
                 let mut iter = objs.iter();
@@ -60,11 +71,27 @@ impl Display for Object {
                 }
                 write!(f, "> as {id:#010x}")
             }
+            Object::Map(map) => {
+                if map.is_empty() {
+                    return write!(f, "{{:}}");
+                }
+                write!(f, "{{")?;
+                for (i, (key, value)) in map.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {value}", Object::from(key))?;
+                }
+                write!(f, "}}")
+            }
             Object::Str(x) => write!(f, "{}", x),
             Object::Int(x) => write!(f, "{}", x),
             Object::Float(x) => write!(f, "{}", x),
             Object::Bool(x) => write!(f, "{}", x),
             Object::Node(x) => write!(f, "{}", x),
+            Object::I64(x) => write!(f, "{}", x),
+            Object::F64(x) => write!(f, "{}", x),
+            Object::Iter(_) => write!(f, "<iter>"),
         }
     }
 }
@@ -94,6 +121,22 @@ impl Object {
         }
     }
 
+    pub fn map(&self) -> Result<Map, Error> {
+        if let Object::Map(x) = self {
+            Ok(x.clone())
+        } else {
+            Err(Error::DataType(self.clone(), "map"))
+        }
+    }
+
+    pub fn err(&self) -> Result<Rc<Object>, Error> {
+        if let Object::Err(x) = self {
+            Ok(x.clone())
+        } else {
+            Err(Error::DataType(self.clone(), "err"))
+        }
+    }
+
     pub fn pointer(&self) -> Result<(Pointer, Index), Error> {
         if let Object::Pointer(ty, idx) = self {
             Ok((*ty, *idx))
@@ -134,6 +177,42 @@ impl Object {
         }
     }
 
+    pub fn i64(&self) -> Result<i64, Error> {
+        if let Object::I64(x) = self {
+            Ok(*x)
+        } else {
+            Err(Error::DataType(self.clone(), "i64"))
+        }
+    }
+
+    pub fn f64(&self) -> Result<f64, Error> {
+        if let Object::F64(x) = self {
+            Ok(*x)
+        } else {
+            Err(Error::DataType(self.clone(), "f64"))
+        }
+    }
+
+    pub fn heap(&self) -> bool {
+        !matches!(
+            self,
+            Object::Pointer(_, _)
+                | Object::Int(_)
+                | Object::Float(_)
+                | Object::Bool(_)
+                | Object::I64(_)
+                | Object::F64(_)
+        )
+    }
+
+    pub fn iter(&self) -> Result<Rc<RefCell<Iter>>, Error> {
+        if let Object::Iter(x) = self {
+            Ok(x.clone())
+        } else {
+            Err(Error::DataType(self.clone(), "iter"))
+        }
+    }
+
     pub fn str(&self) -> Result<&str, Error> {
         if let Object::Str(x) = self {
             Ok(x)
@@ -166,6 +245,7 @@ impl Object {
             UnaOp::Not => self.not(),
             UnaOp::Pos => self.pos(),
             UnaOp::Neg => self.neg(),
+            UnaOp::Fmt => self.fmt(),
         }
     }
 
@@ -186,48 +266,74 @@ impl Object {
     }
 
     fn gt(&self, rhs: &Object) -> Result<Object, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.gt(&rhs);
+        }
         let value = match (self, rhs) {
             (Object::Int(x), Object::Int(y)) => x > y,
             (Object::Float(x), Object::Float(y)) => x > y,
+            (Object::I64(x), Object::I64(y)) => x > y,
+            (Object::F64(x), Object::F64(y)) => x > y,
             _ => return Err(Error::BinaryOperation(">", self.clone(), rhs.clone())),
         };
         Ok(Object::Bool(value))
     }
 
     fn ge(&self, rhs: &Object) -> Result<Object, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.ge(&rhs);
+        }
         let value = match (self, rhs) {
             (Object::Int(x), Object::Int(y)) => x >= y,
             (Object::Float(x), Object::Float(y)) => x >= y,
+            (Object::I64(x), Object::I64(y)) => x >= y,
+            (Object::F64(x), Object::F64(y)) => x >= y,
             _ => return Err(Error::BinaryOperation(">=", self.clone(), rhs.clone())),
         };
         Ok(Object::Bool(value))
     }
 
     fn lt(&self, rhs: &Object) -> Result<Object, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.lt(&rhs);
+        }
         let value = match (self, rhs) {
             (Object::Int(x), Object::Int(y)) => x < y,
             (Object::Float(x), Object::Float(y)) => x < y,
+            (Object::I64(x), Object::I64(y)) => x < y,
+            (Object::F64(x), Object::F64(y)) => x < y,
             _ => return Err(Error::BinaryOperation("<", self.clone(), rhs.clone())),
         };
         Ok(Object::Bool(value))
     }
 
     fn le(&self, rhs: &Object) -> Result<Object, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.le(&rhs);
+        }
         let value = match (self, rhs) {
             (Object::Int(x), Object::Int(y)) => x <= y,
             (Object::Float(x), Object::Float(y)) => x <= y,
+            (Object::I64(x), Object::I64(y)) => x <= y,
+            (Object::F64(x), Object::F64(y)) => x <= y,
             _ => return Err(Error::BinaryOperation("<=", self.clone(), rhs.clone())),
         };
         Ok(Object::Bool(value))
     }
 
     fn eq(&self, rhs: &Object) -> Result<Object, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.eq(&rhs);
+        }
         let value = match (self, rhs) {
             (Object::Int(x), Object::Int(y)) => x == y,
             (Object::Float(x), Object::Float(y)) => x == y,
+            (Object::I64(x), Object::I64(y)) => x == y,
+            (Object::F64(x), Object::F64(y)) => x == y,
             (Object::Bool(x), Object::Bool(y)) => x == y,
             (Object::Str(x), Object::Str(y)) => x == y,
             (Object::Pointer(x, lhs), Object::Pointer(y, rhs)) => x == y && lhs == rhs,
+            (Object::Err(lhs), Object::Err(rhs)) => lhs.eq(rhs)?.bool()?,
             (Object::Tuple(lhs), Object::Tuple(rhs)) => {
                 if lhs.len() != rhs.len() {
                     false
@@ -270,6 +376,20 @@ impl Object {
                     res
                 }
             }
+            (Object::Map(lhs), Object::Map(rhs)) => {
+                if lhs.len() != rhs.len() {
+                    false
+                } else {
+                    let mut res = true;
+                    for ((kx, vx), (ky, vy)) in lhs.iter().zip(rhs.iter()) {
+                        if kx != ky || !vx.eq(vy)?.bool()? {
+                            res = false;
+                            break;
+                        }
+                    }
+                    res
+                }
+            }
             _ => return Err(Error::BinaryOperation("==", self.clone(), rhs.clone())),
         };
         Ok(Object::Bool(value))
@@ -284,9 +404,14 @@ impl Object {
     }
 
     fn add(&self, rhs: &Object) -> Result<Object, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.add(&rhs);
+        }
         let value = match (self, rhs) {
-            (Object::Int(x), Object::Int(y)) => x.wrapping_add(*y).into(),
+            (Object::Int(x), Object::Int(y)) => self.checked("+", rhs, x.checked_add(*y))?.into(),
             (Object::Float(x), Object::Float(y)) => (*x + *y).into(),
+            (Object::I64(x), Object::I64(y)) => self.checked("+", rhs, x.checked_add(*y))?.into(),
+            (Object::F64(x), Object::F64(y)) => (*x + *y).into(),
             (Object::Str(x), Object::Str(y)) => format!("{}{}", x, y).into(),
             (Object::Node(x), Object::Node(y)) => {
                 Node::add(x.clone(), y.clone()).map_err(Error::Any)?.into()
@@ -305,9 +430,14 @@ impl Object {
     }
 
     fn sub(&self, rhs: &Object) -> Result<Object, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.sub(&rhs);
+        }
         let value = match (self, rhs) {
-            (Object::Int(x), Object::Int(y)) => x.wrapping_sub(*y).into(),
+            (Object::Int(x), Object::Int(y)) => self.checked("-", rhs, x.checked_sub(*y))?.into(),
             (Object::Float(x), Object::Float(y)) => (*x - *y).into(),
+            (Object::I64(x), Object::I64(y)) => self.checked("-", rhs, x.checked_sub(*y))?.into(),
+            (Object::F64(x), Object::F64(y)) => (*x - *y).into(),
             (Object::Node(x), Object::Node(y)) => {
                 Node::sub(x.clone(), y.clone()).map_err(Error::Any)?.into()
             }
@@ -325,9 +455,14 @@ impl Object {
     }
 
     fn mul(&self, rhs: &Object) -> Result<Object, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.mul(&rhs);
+        }
         let value = match (self, rhs) {
-            (Object::Int(x), Object::Int(y)) => x.wrapping_mul(*y).into(),
+            (Object::Int(x), Object::Int(y)) => self.checked("*", rhs, x.checked_mul(*y))?.into(),
             (Object::Float(x), Object::Float(y)) => (*x * *y).into(),
+            (Object::I64(x), Object::I64(y)) => self.checked("*", rhs, x.checked_mul(*y))?.into(),
+            (Object::F64(x), Object::F64(y)) => (*x * *y).into(),
             (Object::Node(x), Object::Node(y)) => {
                 Node::mul(x.clone(), y.clone()).map_err(Error::Any)?.into()
             }
@@ -345,9 +480,14 @@ impl Object {
     }
 
     fn div(&self, rhs: &Object) -> Result<Object, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.div(&rhs);
+        }
         let value = match (self, rhs) {
-            (Object::Int(x), Object::Int(y)) => x.wrapping_div(*y).into(),
+            (Object::Int(x), Object::Int(y)) => self.checked("/", rhs, x.checked_div(*y))?.into(),
             (Object::Float(x), Object::Float(y)) => (*x / *y).into(),
+            (Object::I64(x), Object::I64(y)) => self.checked("/", rhs, x.checked_div(*y))?.into(),
+            (Object::F64(x), Object::F64(y)) => (*x / *y).into(),
             (Object::Node(x), Object::Node(y)) => {
                 Node::div(x.clone(), y.clone()).map_err(Error::Any)?.into()
             }
@@ -365,13 +505,59 @@ impl Object {
     }
 
     fn rem(&self, rhs: &Object) -> Result<Object, Error> {
+        if let Some((lhs, rhs)) = self.promote(rhs) {
+            return lhs.rem(&rhs);
+        }
         let value = match (self, rhs) {
-            (Object::Int(x), Object::Int(y)) => (*x % *y).into(),
+            (Object::Int(x), Object::Int(y)) => self.checked("%", rhs, x.checked_rem(*y))?.into(),
+            (Object::Float(x), Object::Float(y)) => (*x % *y).into(),
+            (Object::I64(x), Object::I64(y)) => self.checked("%", rhs, x.checked_rem(*y))?.into(),
+            (Object::F64(x), Object::F64(y)) => (*x % *y).into(),
             _ => return Err(Error::BinaryOperation("%", self.clone(), rhs.clone())),
         };
         Ok(value)
     }
 
+    fn promote(&self, rhs: &Object) -> Option<(Object, Object)> {
+        let (lf, lw) = self.numeric()?;
+        let (rf, rw) = rhs.numeric()?;
+        if (lf, lw) == (rf, rw) {
+            return None;
+        }
+        let (float, wide) = (lf || rf, lw || rw);
+        Some((self.cast(float, wide), rhs.cast(float, wide)))
+    }
+
+    fn numeric(&self) -> Option<(bool, bool)> {
+        match self {
+            Object::Int(_) => Some((false, false)),
+            Object::I64(_) => Some((false, true)),
+            Object::Float(_) => Some((true, false)),
+            Object::F64(_) => Some((true, true)),
+            _ => None,
+        }
+    }
+
+    fn cast(&self, float: bool, wide: bool) -> Object {
+        match (self, float, wide) {
+            (Object::Int(x), false, true) => Object::I64(*x as i64),
+            (Object::Int(x), true, false) => Object::Float(*x as f32),
+            (Object::Int(x), true, true) => Object::F64(*x as f64),
+            (Object::I64(x), true, true) => Object::F64(*x as f64),
+            (Object::Float(x), true, true) => Object::F64(*x as f64),
+            (other, _, _) => other.clone(),
+        }
+    }
+
+    fn checked<T>(&self, op: &'static str, rhs: &Object, value: Option<T>) -> Result<T, Error> {
+        value.ok_or_else(|| match rhs {
+            Object::Int(0) | Object::I64(0) => {
+                Error::BinaryOperation(op, self.clone(), rhs.clone())
+            }
+            _ => Error::Overflow(format!("{self} {op} {rhs}")),
+        })
+    }
+
     fn matmul(&self, rhs: &Object) -> Result<Object, Error> {
         let value = match (self, rhs) {
             (Object::Node(lhs), Object::Node(rhs)) => Node::matmul(lhs.clone(), rhs.clone())
@@ -393,7 +579,12 @@ impl Object {
     fn pos(&self) -> Result<Object, Error> {
         if matches!(
             self,
-            Object::Int(_) | Object::Float(_) | Object::Node(_) | Object::Group(_, _)
+            Object::Int(_)
+                | Object::Float(_)
+                | Object::I64(_)
+                | Object::F64(_)
+                | Object::Node(_)
+                | Object::Group(_, _)
         ) {
             Ok(self.clone())
         } else {
@@ -403,8 +594,16 @@ impl Object {
 
     fn neg(&self) -> Result<Object, Error> {
         let value = match self {
-            Object::Int(x) => (-*x).into(),
+            Object::Int(x) => x
+                .checked_neg()
+                .ok_or_else(|| Error::Overflow(format!("-({self})")))?
+                .into(),
             Object::Float(x) => (-*x).into(),
+            Object::I64(x) => x
+                .checked_neg()
+                .ok_or_else(|| Error::Overflow(format!("-({self})")))?
+                .into(),
+            Object::F64(x) => (-*x).into(),
             Object::Node(x) => Node::neg(x.clone()).map_err(Error::Any)?.into(),
             Object::Group(x, objs) => {
                 let body = objs.iter().map(Self::neg).collect::<Result<Vec<_>, _>>()?;
@@ -414,6 +613,14 @@ impl Object {
         };
         Ok(value)
     }
+
+    fn fmt(&self) -> Result<Object, Error> {
+        let value = match self {
+            Object::Str(x) => x.clone(),
+            other => other.to_string().into(),
+        };
+        Ok(Object::Str(value))
+    }
 }
 
 impl From<f32> for Object {
@@ -422,6 +629,18 @@ impl From<f32> for Object {
     }
 }
 
+impl From<f64> for Object {
+    fn from(x: f64) -> Object {
+        Object::F64(x)
+    }
+}
+
+impl From<i64> for Object {
+    fn from(x: i64) -> Object {
+        Object::I64(x)
+    }
+}
+
 impl From<i32> for Object {
     fn from(x: i32) -> Object {
         Object::Int(x)
@@ -1,33 +1,123 @@
 use crate::elysia::error::Error;
+use crate::elysia::runtime::io::Io;
+use crate::elysia::runtime::iter::Iter;
+use crate::elysia::runtime::limits::Limits;
+use crate::elysia::runtime::map::{Key, Map};
 use crate::elysia::runtime::object::Object;
-use crate::utils::bytecode::{Bytecode, Index, Reg};
-use crate::utils::function::{Const, Pointer};
+use crate::utils::bytecode::{Bytecode, Id, Index, Reg};
+use crate::utils::function::{Const, Pointer, Shape};
+use crate::utils::snippet::Snippet;
 use crate::utils::stages::{Callable, III};
-use crate::utils::stdlib::registry::STDLIB;
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
+use std::sync::atomic::Ordering;
 
 pub const DEPTH: usize = 1024;
 
+pub const NESTING: usize = 64;
+
 impl III {
     pub fn exec(&self, args: Object, stdout: &mut String) -> Result<Object, String> {
-        let mut runtime = self.init(args)?;
-        loop {
-            let (idx, frame) = runtime.active();
-            let bytecode = self.loc(idx).loc(frame.pc);
-            frame.pc += 1;
-            if let Some(exit) = bytecode.exec(self, &mut runtime, stdout)? {
-                break Ok(exit);
-            }
+        let mut stdin = io::empty();
+        let mut buffer = Vec::new();
+        let mut io = Io {
+            stdin: &mut stdin,
+            stdout: &mut buffer,
+            stderr: &mut io::sink(),
+        };
+        let result = self.run(args, &mut io);
+        stdout.push_str(&String::from_utf8_lossy(&buffer));
+        result
+    }
+
+    pub fn run(&self, args: Object, io: &mut Io) -> Result<Object, String> {
+        match self.spawn(args, Limits::default())?.resume(None, io)? {
+            Status::Exited(obj) => Ok(obj),
+            Status::Paused => unreachable!(),
         }
     }
 
-    fn init(&self, args: Object) -> Result<Runtime, Error> {
+    pub fn spawn(&self, args: Object, limits: Limits) -> Result<Process<'_>, String> {
+        if let Some((symbol, _, _)) = self.imports.first() {
+            return Err(Error::Unlinked(symbol.clone()).into());
+        }
+        if self.main.bytecodes.is_empty() {
+            return Err(Error::Entry.into());
+        }
         let runtime = Runtime {
             args,
             rets: vec![],
-            main: self.main.frame(vec![0])?,
+            main: self.main.frame([].into(), vec![0])?,
             stack: vec![],
+            objects: 0,
+            steps: 0,
+            nested: 0,
+            limits,
         };
-        Ok(runtime)
+        let process = Process {
+            program: self,
+            runtime,
+            terminated: false,
+        };
+        Ok(process)
+    }
+
+    fn traceback(&self, rt: &Runtime) -> String {
+        let mut msg = "Traceback (most recent call last):\n".to_string();
+        let trace = rt.trace();
+        let mut i = 0;
+        while i < trace.len() {
+            let (idx, pc) = trace[i];
+            let name = match idx {
+                Some(x) => match self.names.get(x as usize).filter(|x| !x.is_empty()) {
+                    Some(name) => name.clone(),
+                    None => format!("<fn {x}>"),
+                },
+                None => "main".to_string(),
+            };
+            let span = self.loc(idx).span(pc);
+            match span.and_then(|x| Snippet::locate(&self.source, x as usize)) {
+                Some(snippet) => {
+                    let (row, col) = (snippet.row + 1, snippet.col + 1);
+                    msg.push_str(&format!("  in {name} at {row}:{col}\n"));
+                }
+                None => msg.push_str(&format!("  in {name} @{pc:04}\n")),
+            }
+
+            let repeated = trace[i + 1..]
+                .iter()
+                .take_while(|x| **x == (idx, pc))
+                .count();
+            if repeated > 0 {
+                msg.push_str(&format!(
+                    "  [previous frame repeated {repeated} more times]\n"
+                ));
+            }
+            i += repeated + 1;
+        }
+        msg
+    }
+
+    fn field(&self, obj: &Object, gp: Index, id: Id) -> Result<usize, Error> {
+        let group = self.groups.get(gp as usize).unwrap();
+        match group.indices.get(&id) {
+            Some(idx) => Ok(*idx as usize),
+            None => Err(Error::FieldNotFound(obj.clone(), self.symbol(id))),
+        }
+    }
+
+    fn symbol(&self, id: Id) -> String {
+        match self.symbols.binary_search_by_key(&id, |(x, _)| *x) {
+            Ok(i) => self.symbols[i].1.clone(),
+            Err(_) => format!("#{id}"),
+        }
+    }
+
+    fn method(&self, gp: Index, name: &str) -> Option<Index> {
+        let (id, _) = self.symbols.iter().find(|(_, x)| x == name)?;
+        let group = self.groups.get(gp as usize).unwrap();
+        group.methods.get(id).copied()
     }
 
     fn loc(&self, idx: Option<Index>) -> &Callable {
@@ -38,11 +128,134 @@ impl III {
     }
 }
 
+pub struct Handle<'a, 'b> {
+    program: &'a III,
+    rt: &'a mut Runtime,
+    pub io: &'a mut Io<'b>,
+    failure: Option<Error>,
+}
+
+impl Handle<'_, '_> {
+    pub fn call(&mut self, callee: &Object, args: Vec<Object>) -> Result<Object, String> {
+        self.guard(|vm| vm.dispatch(callee, args))
+    }
+
+    pub fn collect(&mut self, obj: Object) -> Result<Vec<Object>, String> {
+        self.guard(|vm| {
+            let program = vm.program;
+            let iter = vm.rt.iter(program, obj)?.iter()?;
+            let mut objs = Vec::new();
+            while vm.rt.has(program, vm.io, &iter)? {
+                objs.push(vm.rt.next(program, vm.io, &iter)?);
+            }
+            Ok(objs)
+        })
+    }
+
+    fn guard<T, F>(&mut self, f: F) -> Result<T, String>
+    where
+        F: FnOnce(&mut Self) -> Result<T, Error>,
+    {
+        if self.failure.is_some() {
+            return Err("callback already failed".to_string());
+        }
+        f(self).map_err(|e| {
+            self.failure = Some(e);
+            "callback failed".to_string()
+        })
+    }
+
+    fn dispatch(&mut self, callee: &Object, args: Vec<Object>) -> Result<Object, Error> {
+        let program = self.program;
+        let (idx, env) = match callee {
+            Object::Closure(idx, env) => (*idx, env.clone()),
+            _ => match callee.pointer()? {
+                (Pointer::Function, idx) => (idx, [].into()),
+                (Pointer::Group, idx) => return self.rt.construct(program, idx, args),
+                (Pointer::Rust, idx) => return self.rt.native(program, self.io, idx, args),
+            },
+        };
+        let frame = program.text.get(idx as usize).unwrap().bind(env, args)?;
+        self.rt.invoke(program, self.io, idx, frame)
+    }
+
+    pub fn name(&self, obj: &Object) -> Result<String, Error> {
+        let (gp, _) = obj.group()?;
+        Ok(self.program.groups.get(gp as usize).unwrap().name.clone())
+    }
+
+    pub fn fields(&self, obj: &Object) -> Result<Vec<String>, Error> {
+        let (gp, _) = obj.group()?;
+        let group = self.program.groups.get(gp as usize).unwrap();
+        let mut fields = group.indices.iter().collect::<Vec<_>>();
+        fields.sort_by_key(|(_, idx)| **idx);
+        Ok(fields
+            .into_iter()
+            .map(|(id, _)| self.program.symbol(*id))
+            .collect())
+    }
+}
+
+pub enum Status {
+    Exited(Object),
+    Paused,
+}
+
+pub struct Process<'a> {
+    program: &'a III,
+    runtime: Runtime,
+    terminated: bool,
+}
+
+impl Process<'_> {
+    pub fn resume(&mut self, slice: Option<usize>, io: &mut Io) -> Result<Status, String> {
+        if self.terminated {
+            return Err(Error::Terminated.into());
+        }
+        let program = self.program;
+        let mut executed = 0;
+        loop {
+            if slice.is_some_and(|x| executed >= x) {
+                break Ok(Status::Paused);
+            }
+            executed += 1;
+
+            let (idx, frame) = self.runtime.active();
+            let pc = frame.pc;
+            let callable = program.loc(idx);
+            frame.pc += 1;
+            let result = self
+                .runtime
+                .check()
+                .and_then(|_| callable.loc(pc).exec(program, &mut self.runtime, io));
+            match result {
+                Ok(Some(exit)) => {
+                    self.terminated = true;
+                    break Ok(Status::Exited(exit));
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    self.terminated = true;
+                    let (idx, frame) = self.runtime.active();
+                    let span = program.loc(idx).span(frame.pc - 1);
+                    let mut msg = program.traceback(&self.runtime);
+                    msg.push_str(&e.recover(&program.source, span));
+                    break Err(msg);
+                }
+            }
+        }
+    }
+}
+
 struct Runtime {
     args: Object,
     rets: Vec<Reg>,
     main: Frame,
     stack: Vec<(Index, Frame)>,
+    objects: usize,
+    steps: usize,
+    nested: usize,
+    limits: Limits,
 }
 
 impl Runtime {
@@ -53,6 +266,15 @@ impl Runtime {
             .unwrap_or((None, &mut self.main))
     }
 
+    fn trace(&self) -> Vec<(Option<Index>, Index)> {
+        let main = (None, self.main.pc - 1);
+        let stack = self
+            .stack
+            .iter()
+            .map(|(idx, frame)| (Some(*idx), frame.pc - 1));
+        [main].into_iter().chain(stack).collect()
+    }
+
     fn frame(&mut self) -> &mut Frame {
         self.stack
             .last_mut()
@@ -62,8 +284,14 @@ impl Runtime {
 
     fn arg(&mut self, idx: Index) -> Object {
         if let Some(tmp) = self.stack.pop() {
-            let reg = *tmp.1.args.get(idx as usize).unwrap();
-            let obj = self.frame().load(reg);
+            let env = &tmp.1.env;
+            let obj = match env.get(idx as usize) {
+                Some(obj) => obj.clone(),
+                None => {
+                    let reg = *tmp.1.args.get(idx as usize - env.len()).unwrap();
+                    self.frame().load(reg)
+                }
+            };
             self.stack.push(tmp);
             obj
         } else {
@@ -71,6 +299,29 @@ impl Runtime {
         }
     }
 
+    fn check(&mut self) -> Result<(), Error> {
+        if let Some(fuel) = self.limits.fuel {
+            if self.steps >= fuel {
+                return Err(Error::FuelExhausted(fuel));
+            }
+            self.steps += 1;
+        }
+        if let Some(flag) = &self.limits.interrupt
+            && flag.load(Ordering::Relaxed)
+        {
+            return Err(Error::Interrupted);
+        }
+        Ok(())
+    }
+
+    fn allocate(&mut self) -> Result<(), Error> {
+        self.objects += 1;
+        match self.limits.objects {
+            Some(budget) if self.objects > budget => Err(Error::TooManyObjects(budget)),
+            _ => Ok(()),
+        }
+    }
+
     fn call(&mut self, dst: Reg, idx: Index, frame: Frame) -> Result<(), Error> {
         if self.stack.len() >= DEPTH {
             return Err(Error::StackOverflow);
@@ -80,6 +331,183 @@ impl Runtime {
         Ok(())
     }
 
+    fn tail(&mut self, dst: Reg, idx: Index, frame: Frame) -> Result<(), Error> {
+        match self.stack.last_mut() {
+            Some(top) => {
+                *top = (idx, frame);
+                Ok(())
+            }
+            None => self.call(dst, idx, frame),
+        }
+    }
+
+    fn resolve(
+        &mut self,
+        program: &III,
+        io: &mut Io,
+        dst: Reg,
+        callee: Object,
+        args: &[Reg],
+        tail: bool,
+    ) -> Result<Option<(Index, Frame)>, Error> {
+        let (idx, env) = match callee {
+            Object::Closure(idx, env) => (idx, env),
+            _ => match callee.pointer()? {
+                (Pointer::Function, idx) => (idx, [].into()),
+                (Pointer::Group, idx) => {
+                    let objs = self.frame().gather(args);
+                    let obj = self.construct(program, idx, objs)?;
+                    self.frame().store(dst, obj);
+                    return Ok(None);
+                }
+                (Pointer::Rust, idx) => {
+                    let objs = self.frame().gather(args);
+                    let obj = self.native(program, io, idx, objs)?;
+                    self.frame().store(dst, obj);
+                    return Ok(None);
+                }
+            },
+        };
+        let callable = program.text.get(idx as usize).unwrap();
+        let frame = if tail {
+            callable.bind(env, self.frame().gather(args))?
+        } else {
+            callable.frame(env, args.to_vec())?
+        };
+        Ok(Some((idx, frame)))
+    }
+
+    fn method(&mut self, program: &III, src: Reg, id: Id) -> Result<Index, Error> {
+        let obj = self.frame().load(src);
+        let (gp, _) = obj.group()?;
+        let group = program.groups.get(gp as usize).unwrap();
+        match group.methods.get(&id) {
+            Some(idx) => Ok(*idx),
+            None => Err(Error::MethodNotFound(obj.clone(), program.symbol(id))),
+        }
+    }
+
+    fn invoke(
+        &mut self,
+        program: &III,
+        io: &mut Io,
+        idx: Index,
+        frame: Frame,
+    ) -> Result<Object, Error> {
+        if self.stack.len() >= DEPTH {
+            return Err(Error::StackOverflow);
+        }
+        if self.nested >= NESTING {
+            return Err(Error::TooDeeplyNested);
+        }
+        self.stack.push((idx, frame));
+        self.nested += 1;
+        let result = self.drive(program, io);
+        self.nested -= 1;
+        result
+    }
+
+    fn drive(&mut self, program: &III, io: &mut Io) -> Result<Object, Error> {
+        let base = self.stack.len();
+        loop {
+            let (idx, frame) = self.active();
+            let pc = frame.pc;
+            frame.pc += 1;
+            self.check()?;
+            let bytecode = program.loc(idx).loc(pc);
+            if let Bytecode::Return(src) = bytecode
+                && self.stack.len() == base
+            {
+                let obj = self.frame().load(*src);
+                self.stack.pop();
+                return Ok(obj);
+            }
+            bytecode.exec(program, self, io)?;
+        }
+    }
+
+    fn iter(&mut self, program: &III, obj: Object) -> Result<Object, Error> {
+        self.allocate()?;
+        let iter = match &obj {
+            Object::List(list) => Iter::List(list.clone(), 0),
+            Object::Map(map) => Iter::Map(map.clone(), 0),
+            Object::Str(s) => Iter::Str(s.clone(), 0),
+            Object::Node(node) if node.rows().is_some() => Iter::Rows(node.clone(), 0),
+            Object::Iter(iter) => iter.borrow().clone(),
+            Object::Group(gp, _) => match program.method(*gp, "next") {
+                Some(idx) => Iter::Group(obj.clone(), idx, None),
+                None => return Err(Error::DataType(obj, "iterable")),
+            },
+            _ => return Err(Error::DataType(obj, "iterable")),
+        };
+        Ok(Object::Iter(Rc::new(RefCell::new(iter))))
+    }
+
+    fn has(&mut self, program: &III, io: &mut Io, iter: &RefCell<Iter>) -> Result<bool, Error> {
+        let (state, idx) = match &*iter.borrow() {
+            Iter::Group(state, idx, None) => (state.clone(), *idx),
+            other => return Ok(other.has()),
+        };
+        let frame = program
+            .text
+            .get(idx as usize)
+            .unwrap()
+            .bind([].into(), vec![state])?;
+        let peeked = match self.invoke(program, io, idx, frame)? {
+            Object::Iter(iter) if matches!(*iter.borrow(), Iter::Done) => None,
+            obj => match &*obj.tuple()? {
+                [value, next] => Some((value.clone(), next.clone())),
+                _ => return Err(Error::DataType(obj, "(value, state)")),
+            },
+        };
+        let more = peeked.is_some();
+        if let Iter::Group(_, _, slot) = &mut *iter.borrow_mut() {
+            *slot = Some(peeked);
+        }
+        Ok(more)
+    }
+
+    fn next(&mut self, program: &III, io: &mut Io, iter: &RefCell<Iter>) -> Result<Object, Error> {
+        self.has(program, io, iter)?;
+        let obj = iter.borrow_mut().next();
+        obj.ok_or(Error::Exhausted)
+    }
+
+    fn construct(&mut self, program: &III, idx: Index, objs: Vec<Object>) -> Result<Object, Error> {
+        self.allocate()?;
+        let group = program.groups.get(idx as usize).unwrap();
+        let expected = group.indices.len();
+        if expected != objs.len() {
+            return Err(Error::NumArgsNotMatch(expected, objs.len()));
+        }
+        Ok(Object::Group(idx, objs.into()))
+    }
+
+    fn native(
+        &mut self,
+        program: &III,
+        io: &mut Io,
+        idx: Index,
+        objs: Vec<Object>,
+    ) -> Result<Object, Error> {
+        let (_, f) = program.natives.get(idx as usize).unwrap();
+        let mut vm = Handle {
+            program,
+            rt: self,
+            io,
+            failure: None,
+        };
+        let result = f(objs, &mut vm);
+        let obj = match vm.failure {
+            Some(e) => return Err(e),
+            None => result.map_err(Error::Any)?,
+        };
+        if obj.heap() {
+            self.allocate()?;
+        }
+        Ok(obj)
+    }
+
     fn ret(&mut self, src: Reg) -> Result<Option<Object>, Error> {
         let obj = self.frame().load(src);
         if self.stack.pop().is_none() {
@@ -94,6 +522,7 @@ impl Runtime {
 struct Frame {
     pc: Index,
     registers: Box<[Option<Object>]>,
+    env: Rc<[Object]>,
     args: Box<[Reg]>,
 }
 
@@ -121,13 +550,29 @@ impl Callable {
         self.bytecodes.get(idx as usize).unwrap()
     }
 
-    fn frame(&self, args: Vec<Reg>) -> Result<Frame, Error> {
-        if self.args as usize != args.len() {
-            return Err(Error::NumArgsNotMatch(self.args as usize, args.len()));
+    fn span(&self, pc: Index) -> Option<Index> {
+        let i = self.lines.binary_search_by_key(&pc, |(x, _)| *x).ok()?;
+        Some(self.lines[i].1)
+    }
+
+    fn bind(&self, env: Rc<[Object]>, args: Vec<Object>) -> Result<Frame, Error> {
+        let expected = (self.args as usize).saturating_sub(env.len());
+        if expected != args.len() {
+            return Err(Error::NumArgsNotMatch(expected, args.len()));
+        }
+        let env = env.iter().cloned().chain(args).collect();
+        self.frame(env, vec![])
+    }
+
+    fn frame(&self, env: Rc<[Object]>, args: Vec<Reg>) -> Result<Frame, Error> {
+        if self.args as usize != env.len() + args.len() {
+            let expected = (self.args as usize).saturating_sub(env.len());
+            return Err(Error::NumArgsNotMatch(expected, args.len()));
         }
         let frame = Frame {
             pc: 0,
             registers: vec![None; self.registers as usize].into_boxed_slice(),
+            env,
             args: args.into_boxed_slice(),
         };
         Ok(frame)
@@ -135,12 +580,7 @@ impl Callable {
 }
 
 impl Bytecode {
-    fn exec(
-        &self,
-        program: &III,
-        rt: &mut Runtime,
-        stdout: &mut String,
-    ) -> Result<Option<Object>, Error> {
+    fn exec(&self, program: &III, rt: &mut Runtime, io: &mut Io) -> Result<Option<Object>, Error> {
         match self {
             Bytecode::Arg(dst, idx) => {
                 let obj = rt.arg(*idx);
@@ -150,28 +590,21 @@ impl Bytecode {
                 let frame = rt.frame();
                 let tmp = frame.load(*src);
                 let (gp, group) = tmp.group()?;
-                let idx = program
-                    .groups
-                    .get(gp as usize)
-                    .unwrap()
-                    .indices
-                    .get(id)
-                    .unwrap();
-                let obj = group.get(*idx as usize).cloned().unwrap();
+                let idx = program.field(&tmp, gp, *id)?;
+                let obj = group.get(idx).cloned().unwrap();
                 frame.store(*dst, obj);
             }
             Bytecode::Unpack(dst, src, idx) => {
                 let frame = rt.frame();
                 let tmp = frame.load(*src);
-                let obj = if *idx == 0
-                    && let Ok(list) = tmp.list()
-                {
-                    Object::Int(list.len() as i32)
-                } else {
-                    tmp.tuple()?
+                let obj = match (&tmp, idx) {
+                    (Object::List(list), 0) => Object::Int(list.len() as i32),
+                    (Object::Map(map), 0) => Object::Int(map.len() as i32),
+                    _ => tmp
+                        .tuple()?
                         .get(*idx as usize)
                         .cloned()
-                        .ok_or(Error::NotEnoughToUnpack(tmp, *idx))?
+                        .ok_or(Error::NotEnoughToUnpack(tmp, *idx))?,
                 };
                 frame.store(*dst, obj);
             }
@@ -198,83 +631,154 @@ impl Bytecode {
                 let l = frame.load(*lhs);
                 let r = frame.load(*rhs);
                 let obj = l.binary(*op, &r)?;
-                frame.store(*dst, obj);
+                if obj.heap() {
+                    rt.allocate()?;
+                }
+                rt.frame().store(*dst, obj);
             }
             Bytecode::Unary(dst, op, src) => {
                 let frame = rt.frame();
                 let s = frame.load(*src);
                 let obj = s.unary(*op)?;
-                frame.store(*dst, obj);
+                if obj.heap() {
+                    rt.allocate()?;
+                }
+                rt.frame().store(*dst, obj);
             }
-            Bytecode::Call(dst, src, args) => {
+            Bytecode::Check(dst, src) => {
+                let frame = rt.frame();
+                let obj = matches!(frame.load(*src), Object::Err(_));
+                frame.store(*dst, Object::Bool(obj));
+            }
+            Bytecode::Test(dst, src, shape, n) => {
                 let frame = rt.frame();
-                let (ty, idx) = frame.load(*src).pointer()?;
-                match ty {
-                    Pointer::Function => {
-                        let new = program
-                            .text
-                            .get(idx as usize)
-                            .unwrap()
-                            .frame(args.clone())?;
-                        rt.call(*dst, idx, new)?
-                    }
-                    Pointer::Group => {
-                        let group = program.groups.get(idx as usize).unwrap();
-                        let objs = frame.gather(args);
-                        let expected = group.indices.len();
-                        if expected != args.len() {
-                            return Err(Error::NumArgsNotMatch(expected, args.len()));
-                        }
-                        frame.store(*dst, Object::Group(idx, objs.into()));
-                    }
-                    Pointer::Rust => {
-                        let (_, _, f) = STDLIB.get(idx as usize).unwrap();
-                        let objs = frame.gather(args);
-                        frame.store(*dst, f(objs, stdout).map_err(Error::Any)?);
-                    }
+                let n = *n as usize;
+                let obj = match (shape, frame.load(*src)) {
+                    (Shape::Tuple, Object::Tuple(objs)) => objs.len() == n,
+                    (Shape::List, Object::List(objs)) => objs.len() == n,
+                    (Shape::Rest, Object::List(objs)) => objs.len() >= n,
+                    (Shape::Group, Object::Group(gp, _)) => gp as usize == n,
+                    (
+                        Shape::Number,
+                        Object::Int(_) | Object::Float(_) | Object::I64(_) | Object::F64(_),
+                    ) => true,
+                    (Shape::Str, Object::Str(_)) => true,
+                    (Shape::Bool, Object::Bool(_)) => true,
+                    _ => false,
                 };
+                frame.store(*dst, Object::Bool(obj));
+            }
+            Bytecode::Tail(dst, src, n) => {
+                rt.allocate()?;
+                let frame = rt.frame();
+                let list = frame.load(*src).list()?;
+                let objs = list.get(*n as usize..).unwrap_or_default();
+                frame.store(*dst, Object::List(objs.into()));
+            }
+            Bytecode::Call(dst, src, args) => {
+                let callee = rt.frame().load(*src);
+                if let Some((idx, new)) = rt.resolve(program, io, *dst, callee, args, false)? {
+                    rt.call(*dst, idx, new)?;
+                }
+            }
+            Bytecode::TailCall(dst, src, args) => {
+                let callee = rt.frame().load(*src);
+                if let Some((idx, new)) = rt.resolve(program, io, *dst, callee, args, true)? {
+                    rt.tail(*dst, idx, new)?;
+                }
             }
             Bytecode::List(dst, args) => {
+                rt.allocate()?;
                 let frame = rt.frame();
                 let objs = frame.gather(args);
                 frame.store(*dst, Object::List(objs.into()));
             }
             Bytecode::Tuple(dst, args) => {
+                rt.allocate()?;
                 let frame = rt.frame();
                 let objs = frame.gather(args);
                 frame.store(*dst, Object::Tuple(objs.into()));
             }
+            Bytecode::Map(dst, args) => {
+                rt.allocate()?;
+                let frame = rt.frame();
+                let mut map = Map::default();
+                for pair in args.chunks(2) {
+                    let key = Key::try_from(&frame.load(pair[0]))?;
+                    map = map.insert(key, frame.load(pair[1]));
+                }
+                frame.store(*dst, Object::Map(map));
+            }
+            Bytecode::Closure(dst, idx, args) => {
+                rt.allocate()?;
+                let frame = rt.frame();
+                let objs = frame.gather(args);
+                frame.store(*dst, Object::Closure(*idx, objs.into()));
+            }
             Bytecode::Index(dst, src, index) => {
                 let frame = rt.frame();
                 let tmp = frame.load(*src);
-                let list = tmp.list()?;
+                let key = frame.load(*index);
+                let obj = match (&tmp, &key) {
+                    (Object::Map(map), _) => map
+                        .get(&Key::try_from(&key)?)
+                        .cloned()
+                        .ok_or(Error::KeyNotFound(tmp.clone(), key))?,
+                    (Object::Str(_), Object::Tuple(bounds)) => slice(&tmp, bounds)?,
+                    (Object::Str(_), _) => char(&tmp, key.int()?)?,
+                    _ => nth(&tmp, key.int()?)?,
+                };
+                frame.store(*dst, obj);
+            }
+            Bytecode::Nth(dst, src, index) => {
+                let frame = rt.frame();
+                let tmp = frame.load(*src);
                 let int = frame.load(*index).int()?;
-                let idx = if int >= 0 {
-                    int as usize
-                } else {
-                    list.len()
-                        .checked_sub(int.unsigned_abs() as usize)
-                        .ok_or(Error::IndexOutOfBounds(tmp.clone(), int))?
+                let obj = match &tmp {
+                    Object::Map(map) => usize::try_from(int)
+                        .ok()
+                        .and_then(|x| map.nth(x))
+                        .map(|(key, value)| Object::Tuple([key.into(), value.clone()].into()))
+                        .ok_or(Error::IndexOutOfBounds(tmp.clone(), int))?,
+                    Object::Group(_, objs) => usize::try_from(int)
+                        .ok()
+                        .and_then(|x| objs.get(x).cloned())
+                        .ok_or(Error::IndexOutOfBounds(tmp.clone(), int))?,
+                    _ => nth(&tmp, int)?,
                 };
-                let obj = list
-                    .get(idx)
-                    .cloned()
-                    .ok_or(Error::IndexOutOfBounds(tmp, int))?;
                 frame.store(*dst, obj);
             }
+            Bytecode::Update(dst, src, id, value) => {
+                rt.allocate()?;
+                let frame = rt.frame();
+                let tmp = frame.load(*src);
+                let (gp, group) = tmp.group()?;
+                let idx = program.field(&tmp, gp, *id)?;
+                let mut objs = group.to_vec();
+                objs[idx] = frame.load(*value);
+                frame.store(*dst, Object::Group(gp, objs.into()));
+            }
             Bytecode::Method(dst, src, id, args) => {
-                let (gp, _) = rt.frame().load(*src).group()?;
-                let idx = program
-                    .groups
-                    .get(gp as usize)
-                    .unwrap()
-                    .methods
-                    .get(id)
-                    .unwrap();
+                let idx = rt.method(program, *src, *id)?;
                 let mut args = args.clone();
                 args.insert(0, *src);
-                let new = program.text.get(*idx as usize).unwrap().frame(args)?;
-                rt.call(*dst, *idx as Index, new)?;
+                let new = program
+                    .text
+                    .get(idx as usize)
+                    .unwrap()
+                    .frame([].into(), args)?;
+                rt.call(*dst, idx, new)?;
+            }
+            Bytecode::TailMethod(dst, src, id, args) => {
+                let idx = rt.method(program, *src, *id)?;
+                let mut objs = vec![rt.frame().load(*src)];
+                objs.extend(rt.frame().gather(args));
+                let new = program
+                    .text
+                    .get(idx as usize)
+                    .unwrap()
+                    .bind([].into(), objs)?;
+                rt.tail(*dst, idx, new)?;
             }
             Bytecode::Branch(cond, yes, no) => {
                 let frame = rt.frame();
@@ -291,11 +795,78 @@ impl Bytecode {
                 let obj = frame.load(*src);
                 frame.store(*dst, obj);
             }
+            Bytecode::Iter(dst, src) => {
+                let obj = rt.frame().load(*src);
+                let iter = rt.iter(program, obj)?;
+                rt.frame().store(*dst, iter);
+            }
+            Bytecode::Has(dst, src) => {
+                let iter = rt.frame().load(*src).iter()?;
+                let more = rt.has(program, io, &iter)?;
+                rt.frame().store(*dst, Object::Bool(more));
+            }
+            Bytecode::Next(dst, src) => {
+                let iter = rt.frame().load(*src).iter()?;
+                let obj = rt.next(program, io, &iter)?;
+                rt.frame().store(*dst, obj);
+            }
         }
         Ok(None)
     }
 }
 
+fn nth(src: &Object, int: i32) -> Result<Object, Error> {
+    let list = src.list()?;
+    let idx = if int >= 0 {
+        int as usize
+    } else {
+        list.len()
+            .checked_sub(int.unsigned_abs() as usize)
+            .ok_or(Error::IndexOutOfBounds(src.clone(), int))?
+    };
+    list.get(idx)
+        .cloned()
+        .ok_or(Error::IndexOutOfBounds(src.clone(), int))
+}
+
+fn char(src: &Object, int: i32) -> Result<Object, Error> {
+    let s = src.str()?;
+    let idx = bound(src, s.chars().count(), int)?;
+    s.chars()
+        .nth(idx)
+        .map(|x| Object::Str(x.to_string().into()))
+        .ok_or(Error::IndexOutOfBounds(src.clone(), int))
+}
+
+fn slice(src: &Object, bounds: &[Object]) -> Result<Object, Error> {
+    let s = src.str()?;
+    let [start, end] = bounds else {
+        return Err(Error::DataType(Object::Tuple(bounds.into()), "(int, int)"));
+    };
+    let len = s.chars().count();
+    let (start, end) = (start.int()?, end.int()?);
+    let lo = bound(src, len, start)?;
+    let hi = bound(src, len, end)?;
+    if lo > hi {
+        return Err(Error::IndexOutOfBounds(src.clone(), end));
+    }
+    let sub = s.chars().skip(lo).take(hi - lo).collect::<String>();
+    Ok(Object::Str(sub.into()))
+}
+
+fn bound(src: &Object, len: usize, int: i32) -> Result<usize, Error> {
+    let idx = if int >= 0 {
+        int as usize
+    } else {
+        len.checked_sub(int.unsigned_abs() as usize)
+            .ok_or(Error::IndexOutOfBounds(src.clone(), int))?
+    };
+    if idx > len {
+        return Err(Error::IndexOutOfBounds(src.clone(), int));
+    }
+    Ok(idx)
+}
+
 impl From<&Const> for Object {
     fn from(value: &Const) -> Self {
         match value {
@@ -303,6 +874,8 @@ impl From<&Const> for Object {
             Const::Float(x) => Object::Float(f32::from_bits(*x)),
             Const::Bool(x) => Object::Bool(*x),
             Const::Str(x) => Object::Str(x.clone()),
+            Const::I64(x) => Object::I64(*x),
+            Const::F64(x) => Object::F64(f64::from_bits(*x)),
         }
     }
 }
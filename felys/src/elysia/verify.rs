@@ -0,0 +1,200 @@
+use crate::elysia::error::LoadError;
+use crate::utils::bytecode::{Bytecode, Index, Reg};
+use crate::utils::function::{Pointer, Shape};
+use crate::utils::stages::{Callable, III};
+
+impl III {
+    pub fn verify(&self) -> Result<(), LoadError> {
+        self.main.verify(None, self)?;
+        for (i, callable) in self.text.iter().enumerate() {
+            callable.verify(Some(Index::try_from(i).unwrap()), self)?;
+        }
+
+        for (i, group) in self.groups.iter().enumerate() {
+            let gp = Index::try_from(i).unwrap();
+            let mut fields = group.indices.values().copied().collect::<Vec<_>>();
+            fields.sort();
+            if fields.iter().zip(0..).any(|(x, i)| *x != i) {
+                return Err(LoadError::Group(gp));
+            }
+            if group
+                .methods
+                .values()
+                .any(|x| *x as usize >= self.text.len())
+            {
+                return Err(LoadError::Group(gp));
+            }
+        }
+
+        for (symbol, pt, idx) in self.exports.iter().chain(self.imports.iter()) {
+            let len = match pt {
+                Pointer::Group => self.groups.len(),
+                Pointer::Function => self.text.len(),
+                Pointer::Rust => 0,
+            };
+            if *idx as usize >= len {
+                return Err(LoadError::Symbol(symbol.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Callable {
+    fn verify(&self, idx: Option<Index>, program: &III) -> Result<(), LoadError> {
+        if idx.is_none() && self.bytecodes.is_empty() {
+            return Ok(());
+        }
+        match self.bytecodes.last() {
+            Some(Bytecode::Return(_) | Bytecode::Jump(_) | Bytecode::Branch(_, _, _)) => {}
+            _ => return Err(LoadError::Fallthrough(idx)),
+        }
+
+        for (pc, bytecode) in self.bytecodes.iter().enumerate() {
+            let pc = Index::try_from(pc).unwrap();
+            let reference = |kind, x: Index, len: usize| {
+                if (x as usize) < len {
+                    Ok(())
+                } else {
+                    Err(LoadError::Reference(idx, pc, kind, x))
+                }
+            };
+            let target = |x: Index| {
+                if (x as usize) < self.bytecodes.len() {
+                    Ok(())
+                } else {
+                    Err(LoadError::Target(idx, pc, x))
+                }
+            };
+
+            for reg in bytecode.registers() {
+                if reg >= self.registers {
+                    return Err(LoadError::Register(idx, pc, reg));
+                }
+            }
+            match bytecode {
+                Bytecode::Arg(_, x) => reference("arg", *x, self.args as usize)?,
+                Bytecode::Pointer(_, Pointer::Function, x) => {
+                    reference("fn", *x, program.text.len())?
+                }
+                Bytecode::Pointer(_, Pointer::Group, x) => {
+                    reference("group", *x, program.groups.len())?
+                }
+                Bytecode::Pointer(_, Pointer::Rust, x) => {
+                    reference("native", *x, program.natives.len())?
+                }
+                Bytecode::Test(_, _, Shape::Group, x) => {
+                    reference("group", *x, program.groups.len())?
+                }
+                Bytecode::Closure(_, x, _) => reference("fn", *x, program.text.len())?,
+                Bytecode::Load(_, x) => reference("data", *x, program.data.len())?,
+                Bytecode::Map(_, args) if args.len() % 2 != 0 => {
+                    return Err(LoadError::Pairs(idx, pc));
+                }
+                Bytecode::Branch(_, yes, no) => {
+                    target(*yes)?;
+                    target(*no)?;
+                }
+                Bytecode::Jump(x) => target(*x)?,
+                _ => {}
+            }
+        }
+        self.defined(idx)
+    }
+
+    fn defined(&self, idx: Option<Index>) -> Result<(), LoadError> {
+        let mut states = vec![None; self.bytecodes.len()];
+        states[0] = Some(vec![false; self.registers as usize]);
+        let mut worklist = vec![0];
+        while let Some(pc) = worklist.pop() {
+            let mut state: Vec<bool> = states[pc].clone().unwrap();
+            let bytecode = &self.bytecodes[pc];
+            for reg in bytecode.reads() {
+                if !state[reg as usize] {
+                    let pc = Index::try_from(pc).unwrap();
+                    return Err(LoadError::Undefined(idx, pc, reg));
+                }
+            }
+            if let Some(reg) = bytecode.writes() {
+                state[reg as usize] = true;
+            }
+            for next in bytecode.successors(pc) {
+                let merged = match &states[next] {
+                    Some(old) => old.iter().zip(&state).map(|(x, y)| *x && *y).collect(),
+                    None => state.clone(),
+                };
+                if states[next].as_ref() != Some(&merged) {
+                    states[next] = Some(merged);
+                    worklist.push(next);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Bytecode {
+    fn writes(&self) -> Option<Reg> {
+        match self {
+            Bytecode::Branch(_, _, _) | Bytecode::Jump(_) | Bytecode::Return(_) => None,
+            _ => self.registers().first().copied(),
+        }
+    }
+
+    fn reads(&self) -> Vec<Reg> {
+        let mut registers = self.registers();
+        if self.writes().is_some() {
+            registers.remove(0);
+        }
+        registers
+    }
+
+    fn successors(&self, pc: usize) -> Vec<usize> {
+        match self {
+            Bytecode::Branch(_, yes, no) => vec![*yes as usize, *no as usize],
+            Bytecode::Jump(target) => vec![*target as usize],
+            Bytecode::Return(_) => vec![],
+            _ => vec![pc + 1],
+        }
+    }
+
+    fn registers(&self) -> Vec<Reg> {
+        match self {
+            Bytecode::Arg(dst, _)
+            | Bytecode::Pointer(dst, _, _)
+            | Bytecode::Load(dst, _)
+            | Bytecode::Return(dst) => vec![*dst],
+            Bytecode::Field(dst, src, _)
+            | Bytecode::Unpack(dst, src, _)
+            | Bytecode::Unary(dst, _, src)
+            | Bytecode::Check(dst, src)
+            | Bytecode::Test(dst, src, _, _)
+            | Bytecode::Tail(dst, src, _)
+            | Bytecode::Copy(dst, src)
+            | Bytecode::Iter(dst, src)
+            | Bytecode::Has(dst, src)
+            | Bytecode::Next(dst, src) => vec![*dst, *src],
+            Bytecode::Binary(dst, lhs, _, rhs) | Bytecode::Update(dst, lhs, _, rhs) => {
+                vec![*dst, *lhs, *rhs]
+            }
+            Bytecode::Index(dst, src, index) | Bytecode::Nth(dst, src, index) => {
+                vec![*dst, *src, *index]
+            }
+            Bytecode::Call(dst, src, args)
+            | Bytecode::Method(dst, src, _, args)
+            | Bytecode::TailCall(dst, src, args)
+            | Bytecode::TailMethod(dst, src, _, args) => [*dst, *src]
+                .into_iter()
+                .chain(args.iter().copied())
+                .collect(),
+            Bytecode::List(dst, args)
+            | Bytecode::Tuple(dst, args)
+            | Bytecode::Map(dst, args)
+            | Bytecode::Closure(dst, _, args) => {
+                [*dst].into_iter().chain(args.iter().copied()).collect()
+            }
+            Bytecode::Branch(cond, _, _) => vec![*cond],
+            Bytecode::Jump(_) => vec![],
+        }
+    }
+}
@@ -4,8 +4,16 @@ mod elysia;
 mod philia093;
 mod utils;
 
+pub use cyrene::import::Loader;
+pub use elysia::error::LoadError;
+pub use elysia::runtime::io::Io;
+pub use elysia::runtime::limits::Limits;
+pub use elysia::runtime::map::{Key, Map};
 pub use elysia::runtime::object::Object;
+pub use elysia::runtime::vm::{Handle, Process, Status};
 pub use philia093::PhiLia093;
 pub use utils::ast::BinOp;
 pub use utils::ast::UnaOp;
+pub use utils::repl::Repl;
 pub use utils::stages::III;
+pub use utils::stdlib::registry::Registry;
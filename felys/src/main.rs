@@ -0,0 +1,238 @@
+use felys::{III, Io, Limits, Loader, Object, PhiLia093, Registry, Repl, Status};
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: felys <command> [options] <file> [args...]
+       felys repl [options]
+
+Commands:
+    run        compile and execute a source file
+    compile    compile a source file into a binary
+    exec       execute a compiled binary
+    disasm     list the bytecode of a source file or a binary
+    repl       evaluate items and statements interactively
+
+Options:
+    -O <limit>    optimization limit of the compiler [default: 1]
+    -o <path>     output path of `compile` [default: <file>.bin]
+    --fuel <n>    maximum number of instructions executed by `run` and `exec`
+";
+
+enum Command {
+    Run,
+    Compile,
+    Exec,
+    Disasm,
+    Repl,
+}
+
+struct Options {
+    command: Command,
+    file: String,
+    output: Option<String>,
+    limit: usize,
+    fuel: Option<usize>,
+    args: Vec<String>,
+}
+
+impl Options {
+    fn parse(mut iter: impl Iterator<Item = String>) -> Result<Options, String> {
+        let command = match iter.next().as_deref() {
+            Some("run") => Command::Run,
+            Some("compile") => Command::Compile,
+            Some("exec") => Command::Exec,
+            Some("disasm") => Command::Disasm,
+            Some("repl") => Command::Repl,
+            Some(other) => return Err(format!("unknown command `{other}`")),
+            None => return Err("missing command".to_string()),
+        };
+
+        let mut file = None;
+        let mut output = None;
+        let mut limit = 1;
+        let mut fuel = None;
+        while file.is_none() {
+            match iter.next().as_deref() {
+                Some("-O") => {
+                    limit = iter
+                        .next()
+                        .and_then(|x| x.parse().ok())
+                        .ok_or("`-O` expects a number")?;
+                }
+                Some("--fuel") => {
+                    let n = iter.next().and_then(|x| x.parse().ok());
+                    fuel = Some(n.ok_or("`--fuel` expects a number")?);
+                }
+                Some("-o") => output = Some(iter.next().ok_or("`-o` expects a path")?),
+                Some(path) if matches!(command, Command::Repl) => {
+                    return Err(format!("unexpected argument `{path}`"));
+                }
+                Some(path) => file = Some(path.to_string()),
+                None if matches!(command, Command::Repl) => break,
+                None => return Err("missing input file".to_string()),
+            }
+        }
+
+        let args = iter.collect::<Vec<_>>();
+        if matches!(command, Command::Compile | Command::Disasm)
+            && let Some(arg) = args.first()
+        {
+            return Err(format!("unexpected argument `{arg}`"));
+        }
+
+        Ok(Options {
+            command,
+            file: file.unwrap_or_default(),
+            output,
+            limit,
+            fuel,
+            args,
+        })
+    }
+
+    fn compile(&self) -> Result<III, String> {
+        let code = fs::read_to_string(&self.file).map_err(|e| format!("{}: {e}\n", self.file))?;
+        let root = Path::new(&self.file).parent().unwrap_or(Path::new("."));
+        PhiLia093::from(code)
+            .parse()?
+            .import(&mut Files(root.to_path_buf()))?
+            .desugar(&Registry::default())?
+            .codegen(self.limit)
+    }
+
+    fn load(&self) -> Result<III, String> {
+        let binary = fs::read(&self.file).map_err(|e| format!("{}: {e}\n", self.file))?;
+        III::load(&mut binary.as_slice(), &Registry::default())
+            .map_err(|e| format!("{}: {e}\n", self.file))
+    }
+
+    fn args(&self) -> Object {
+        let args = self.args.iter().map(|x| Object::Str(x.as_str().into()));
+        Object::List(args.collect())
+    }
+}
+
+struct Files(PathBuf);
+
+impl Loader for Files {
+    fn load(&mut self, path: &str) -> Option<String> {
+        let mut file = self.0.clone();
+        file.extend(path.split("::"));
+        file.set_extension("fs");
+        fs::read_to_string(file).ok()
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("felys: {e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match execute(options) {
+        Ok(code) => code,
+        Err(e) => {
+            eprint!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn execute(options: Options) -> Result<ExitCode, String> {
+    match options.command {
+        Command::Run => run(options.compile()?, &options),
+        Command::Exec => run(options.load()?, &options),
+        Command::Compile => {
+            let iii = options.compile()?;
+            let output = options
+                .output
+                .clone()
+                .unwrap_or_else(|| format!("{}.bin", options.file));
+            let mut binary = Vec::new();
+            iii.dump(&mut binary).unwrap();
+            fs::write(&output, binary).map_err(|e| format!("{output}: {e}\n"))?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Disasm => {
+            let iii = if options.file.ends_with(".fs") {
+                options.compile()?
+            } else {
+                options.load()?
+            };
+            let mut listing = String::new();
+            iii.disassemble(&mut listing).unwrap();
+            print!("{listing}");
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Repl => repl(options.limit),
+    }
+}
+
+fn run(iii: III, options: &Options) -> Result<ExitCode, String> {
+    let limits = Limits {
+        fuel: options.fuel,
+        ..Limits::default()
+    };
+    let mut process = iii.spawn(options.args(), limits)?;
+    let mut io = Io {
+        stdin: &mut io::stdin().lock(),
+        stdout: &mut io::stdout(),
+        stderr: &mut io::stderr(),
+    };
+    let result = process.resume(None, &mut io);
+    let _ = io.stdout.flush();
+    let Status::Exited(obj) = result? else {
+        unreachable!()
+    };
+    let code = match obj {
+        Object::Int(x) => u8::try_from(x)
+            .map(ExitCode::from)
+            .unwrap_or(ExitCode::FAILURE),
+        Object::Bool(x) if !x => ExitCode::FAILURE,
+        Object::Err(x) => {
+            eprintln!("{x}");
+            ExitCode::FAILURE
+        }
+        _ => ExitCode::SUCCESS,
+    };
+    Ok(code)
+}
+
+fn repl(limit: usize) -> Result<ExitCode, String> {
+    let mut repl = Repl::new(Registry::default(), limit);
+    let stdin = io::stdin();
+    let mut input = String::new();
+    loop {
+        print!("{}", if input.is_empty() { ">>> " } else { "... " });
+        io::stdout().flush().map_err(|e| format!("{e}\n"))?;
+        let read = stdin
+            .lock()
+            .read_line(&mut input)
+            .map_err(|e| format!("{e}\n"))?;
+        if read == 0 {
+            println!();
+            return Ok(ExitCode::SUCCESS);
+        }
+        if !Repl::balanced(&input) {
+            continue;
+        }
+
+        let mut io = Io {
+            stdin: &mut stdin.lock(),
+            stdout: &mut io::stdout(),
+            stderr: &mut io::stderr(),
+        };
+        match repl.eval(&input, &mut io) {
+            Ok(Some(obj)) => println!("{obj}"),
+            Ok(None) => {}
+            Err(e) => eprint!("{e}"),
+        }
+        input.clear();
+    }
+}
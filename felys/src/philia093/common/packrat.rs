@@ -18,12 +18,15 @@ impl From<String> for PhiLia093 {
                     | "fn"
                     | "group"
                     | "impl"
+                    | "use"
+                    | "mod"
                     | "if"
                     | "else"
                     | "for"
                     | "in"
                     | "while"
                     | "loop"
+                    | "match"
                     | "return"
                     | "break"
                     | "continue"
@@ -38,7 +38,7 @@ impl super::PhiLia093 {
         if self.__snapshot.is_some() {
             return None;
         }
-        const RULES: super::R<Item, 4usize> = [
+        const RULES: super::R<Item, 6usize> = [
             |x| {
                 let _ = x.GROUP()?;
                 let ident = match x.IDENT() {
@@ -132,6 +132,30 @@ impl super::PhiLia093 {
                 };
                 Some(Item::Fn(ident, params, block))
             },
+            |x| {
+                let _ = x.USE()?;
+                let path = match x.path() {
+                    Some(value) => value,
+                    None => return x.__error("<path>"),
+                };
+                let _ = match x.SEMI() {
+                    Some(value) => value,
+                    None => return x.__error("<SEMI>"),
+                };
+                Some(Item::Use(path))
+            },
+            |x| {
+                let _ = x.MOD()?;
+                let path = match x.path() {
+                    Some(value) => value,
+                    None => return x.__error("<path>"),
+                };
+                let _ = match x.SEMI() {
+                    Some(value) => value,
+                    None => return x.__error("<SEMI>"),
+                };
+                Some(Item::Mod(path))
+            },
         ];
         self.__peg(RULES)
     }
@@ -242,6 +266,154 @@ impl super::PhiLia093 {
         self.__memo.pat.insert(start, (end, cache));
         result
     }
+    pub fn case(&mut self) -> Option<Case> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<Case, 8usize> = [
+            |x| {
+                let _ = x.UNDERSCORE()?;
+                Some(Case::Any)
+            },
+            |x| {
+                let path = x.path()?;
+                let span = x.SPAN()?;
+                let _ = x.LPAR()?;
+                let cases = x.cases()?;
+                let _ = match x.RPAR() {
+                    Some(value) => value,
+                    None => return x.__error("<RPAR>"),
+                };
+                Some(Case::Group(span, path, cases))
+            },
+            |x| {
+                let ident = x.IDENT()?;
+                Some(Case::Ident(ident))
+            },
+            |x| {
+                let lit = x.lit()?;
+                Some(Case::Lit(lit))
+            },
+            |x| {
+                let _ = x.DASH()?;
+                let lit = match x.lit() {
+                    Some(value) => value,
+                    None => return x.__error("<lit>"),
+                };
+                Some(Case::Neg(lit))
+            },
+            |x| {
+                let _ = x.LPAR()?;
+                let first = x.case()?;
+                let _ = x.COMMA()?;
+                let second = x.case()?;
+                let more = {
+                    let mut body = Vec::new();
+                    while let Some(data) = x.__peg([|x| {
+                        let _ = x.COMMA()?;
+                        let case = x.case()?;
+                        Some((case))
+                    }]) {
+                        body.push(data)
+                    }
+                    body
+                };
+                let _ = x.COMMA();
+                let _ = x.RPAR()?;
+                Some(Case::Tuple(BufVec::new([first, second], more)))
+            },
+            |x| {
+                let _ = x.LSQB()?;
+                let first = x.case()?;
+                let more = {
+                    let mut body = Vec::new();
+                    while let Some(data) = x.__peg([|x| {
+                        let _ = x.COMMA()?;
+                        let case = x.case()?;
+                        Some((case))
+                    }]) {
+                        body.push(data)
+                    }
+                    body
+                };
+                let rest = x.__peg([|x| {
+                    let _ = x.COMMA()?;
+                    let _ = x.DOTDOT()?;
+                    let ident = x.IDENT();
+                    Some((ident))
+                }]);
+                let _ = x.COMMA();
+                let _ = match x.RSQB() {
+                    Some(value) => value,
+                    None => return x.__error("<RSQB>"),
+                };
+                Some(Case::List(BufVec::new([first], more).vec(), rest))
+            },
+            |x| {
+                let _ = x.LSQB()?;
+                let rest = x.__peg([|x| {
+                    let _ = x.DOTDOT()?;
+                    let ident = x.IDENT();
+                    Some((ident))
+                }]);
+                let _ = match x.RSQB() {
+                    Some(value) => value,
+                    None => return x.__error("<RSQB>"),
+                };
+                Some(Case::List(Vec::new(), rest))
+            },
+        ];
+        self.__peg(RULES)
+    }
+    pub fn cases(&mut self) -> Option<BufVec<Case, 1>> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<BufVec<Case, 1>, 1usize> = [|x| {
+            let first = x.case()?;
+            let more = {
+                let mut body = Vec::new();
+                while let Some(data) = x.__peg([|x| {
+                    let _ = x.COMMA()?;
+                    let case = x.case()?;
+                    Some((case))
+                }]) {
+                    body.push(data)
+                }
+                body
+            };
+            let _ = x.COMMA();
+            Some(BufVec::new([first], more))
+        }];
+        self.__peg(RULES)
+    }
+    pub fn arm(&mut self) -> Option<Arm> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<Arm, 1usize> = [|x| {
+            let span = x.SPAN()?;
+            let case = x.case()?;
+            let guard = x.__peg([|x| {
+                let _ = x.IF()?;
+                let expr = match x.expr() {
+                    Some(value) => value,
+                    None => return x.__error("<expr>"),
+                };
+                Some((expr))
+            }]);
+            let _ = match x.ARROW() {
+                Some(value) => value,
+                None => return x.__error("<ARROW>"),
+            };
+            let expr = match x.expr() {
+                Some(value) => value,
+                None => return x.__error("<expr>"),
+            };
+            Some(Arm(span, case, guard, expr))
+        }];
+        self.__peg(RULES)
+    }
     pub fn block(&mut self) -> Option<Block> {
         if self.__snapshot.is_some() {
             return None;
@@ -299,57 +471,63 @@ impl super::PhiLia093 {
         const RULES: super::R<Stmt, 6usize> = [
             |x| {
                 let pat = x.pat()?;
+                let span = x.SPAN()?;
                 let _ = x.EQ()?;
                 let expr = match x.expr() {
                     Some(value) => value,
                     None => return x.__error("<expr>"),
                 };
-                Some(Stmt::Assign(pat, AssOp::Eq, expr))
+                Some(Stmt::Assign(span, pat, AssOp::Eq, expr))
             },
             |x| {
                 let pat = x.pat()?;
+                let span = x.SPAN()?;
                 let _ = x.ADDEQ()?;
                 let expr = match x.expr() {
                     Some(value) => value,
                     None => return x.__error("<expr>"),
                 };
-                Some(Stmt::Assign(pat, AssOp::AddEq, expr))
+                Some(Stmt::Assign(span, pat, AssOp::AddEq, expr))
             },
             |x| {
                 let pat = x.pat()?;
+                let span = x.SPAN()?;
                 let _ = x.SUBEQ()?;
                 let expr = match x.expr() {
                     Some(value) => value,
                     None => return x.__error("<expr>"),
                 };
-                Some(Stmt::Assign(pat, AssOp::SubEq, expr))
+                Some(Stmt::Assign(span, pat, AssOp::SubEq, expr))
             },
             |x| {
                 let pat = x.pat()?;
+                let span = x.SPAN()?;
                 let _ = x.MULEQ()?;
                 let expr = match x.expr() {
                     Some(value) => value,
                     None => return x.__error("<expr>"),
                 };
-                Some(Stmt::Assign(pat, AssOp::MulEq, expr))
+                Some(Stmt::Assign(span, pat, AssOp::MulEq, expr))
             },
             |x| {
                 let pat = x.pat()?;
+                let span = x.SPAN()?;
                 let _ = x.DIVEQ()?;
                 let expr = match x.expr() {
                     Some(value) => value,
                     None => return x.__error("<expr>"),
                 };
-                Some(Stmt::Assign(pat, AssOp::DivEq, expr))
+                Some(Stmt::Assign(span, pat, AssOp::DivEq, expr))
             },
             |x| {
                 let pat = x.pat()?;
+                let span = x.SPAN()?;
                 let _ = x.MODEQ()?;
                 let expr = match x.expr() {
                     Some(value) => value,
                     None => return x.__error("<expr>"),
                 };
-                Some(Stmt::Assign(pat, AssOp::ModEq, expr))
+                Some(Stmt::Assign(span, pat, AssOp::ModEq, expr))
             },
         ];
         self.__peg(RULES)
@@ -358,21 +536,24 @@ impl super::PhiLia093 {
         if self.__snapshot.is_some() {
             return None;
         }
-        const RULES: super::R<Expr, 6usize> = [
+        const RULES: super::R<Expr, 7usize> = [
             |x| {
                 let disjunction = x.disjunction()?;
                 Some((disjunction))
             },
             |x| {
+                let span = x.SPAN()?;
                 let _ = x.BREAK()?;
                 let expr = x.expr();
-                Some(Expr::Break(expr.map(Rc::new)))
+                Some(Expr::Break(span, expr.map(Rc::new)))
             },
             |x| {
+                let span = x.SPAN()?;
                 let _ = x.CONTINUE()?;
-                Some(Expr::Continue)
+                Some(Expr::Continue(span))
             },
             |x| {
+                let span = x.SPAN()?;
                 let _ = x.FOR()?;
                 let pat = match x.pat() {
                     Some(value) => value,
@@ -390,17 +571,19 @@ impl super::PhiLia093 {
                     Some(value) => value,
                     None => return x.__error("<block>"),
                 };
-                Some(Expr::For(pat, expr.into(), block))
+                Some(Expr::For(span, pat, expr.into(), block))
             },
             |x| {
+                let span = x.SPAN()?;
                 let _ = x.RETURN()?;
                 let expr = match x.expr() {
                     Some(value) => value,
                     None => return x.__error("<expr>"),
                 };
-                Some(Expr::Return(expr.into()))
+                Some(Expr::Return(span, expr.into()))
             },
             |x| {
+                let span = x.SPAN()?;
                 let _ = x.WHILE()?;
                 let expr = match x.expr() {
                     Some(value) => value,
@@ -410,7 +593,21 @@ impl super::PhiLia093 {
                     Some(value) => value,
                     None => return x.__error("<block>"),
                 };
-                Some(Expr::While(expr.into(), block))
+                Some(Expr::While(span, expr.into(), block))
+            },
+            |x| {
+                let span = x.SPAN()?;
+                let _ = x.PIPE()?;
+                let params = x.params();
+                let _ = match x.PIPE() {
+                    Some(value) => value,
+                    None => return x.__error("<PIPE>"),
+                };
+                let expr = match x.expr() {
+                    Some(value) => value,
+                    None => return x.__error("<expr>"),
+                };
+                Some(Expr::Closure(span, params, expr.into()))
             },
         ];
         let start = self.__stream.cursor;
@@ -431,12 +628,13 @@ impl super::PhiLia093 {
         const RULES: super::R<Expr, 2usize> = [
             |x| {
                 let lhs = x.disjunction()?;
+                let span = x.SPAN()?;
                 let _ = x.OR()?;
                 let rhs = match x.conjunction() {
                     Some(value) => value,
                     None => return x.__error("<conjunction>"),
                 };
-                Some(Expr::Binary(lhs.into(), BinOp::Or, rhs.into()))
+                Some(Expr::Binary(span, lhs.into(), BinOp::Or, rhs.into()))
             },
             |x| {
                 let conjunction = x.conjunction()?;
@@ -474,12 +672,13 @@ impl super::PhiLia093 {
         const RULES: super::R<Expr, 2usize> = [
             |x| {
                 let lhs = x.conjunction()?;
+                let span = x.SPAN()?;
                 let _ = x.AND()?;
                 let rhs = match x.inversion() {
                     Some(value) => value,
                     None => return x.__error("<inversion>"),
                 };
-                Some(Expr::Binary(lhs.into(), BinOp::And, rhs.into()))
+                Some(Expr::Binary(span, lhs.into(), BinOp::And, rhs.into()))
             },
             |x| {
                 let inversion = x.inversion()?;
@@ -516,12 +715,13 @@ impl super::PhiLia093 {
         }
         const RULES: super::R<Expr, 2usize> = [
             |x| {
+                let span = x.SPAN()?;
                 let _ = x.NOT()?;
                 let inversion = match x.inversion() {
                     Some(value) => value,
                     None => return x.__error("<inversion>"),
                 };
-                Some(Expr::Unary(UnaOp::Not, inversion.into()))
+                Some(Expr::Unary(span, UnaOp::Not, inversion.into()))
             },
             |x| {
                 let equality = x.equality()?;
@@ -537,21 +737,23 @@ impl super::PhiLia093 {
         const RULES: super::R<Expr, 3usize> = [
             |x| {
                 let lhs = x.equality()?;
+                let span = x.SPAN()?;
                 let _ = x.EQ2()?;
                 let rhs = match x.comparison() {
                     Some(value) => value,
                     None => return x.__error("<comparison>"),
                 };
-                Some(Expr::Binary(lhs.into(), BinOp::Eq, rhs.into()))
+                Some(Expr::Binary(span, lhs.into(), BinOp::Eq, rhs.into()))
             },
             |x| {
                 let lhs = x.equality()?;
+                let span = x.SPAN()?;
                 let _ = x.NEQ()?;
                 let rhs = match x.comparison() {
                     Some(value) => value,
                     None => return x.__error("<comparison>"),
                 };
-                Some(Expr::Binary(lhs.into(), BinOp::Ne, rhs.into()))
+                Some(Expr::Binary(span, lhs.into(), BinOp::Ne, rhs.into()))
             },
             |x| {
                 let comparison = x.comparison()?;
@@ -589,39 +791,43 @@ impl super::PhiLia093 {
         const RULES: super::R<Expr, 5usize> = [
             |x| {
                 let lhs = x.comparison()?;
+                let span = x.SPAN()?;
                 let _ = x.GE()?;
                 let rhs = match x.term() {
                     Some(value) => value,
                     None => return x.__error("<term>"),
                 };
-                Some(Expr::Binary(lhs.into(), BinOp::Ge, rhs.into()))
+                Some(Expr::Binary(span, lhs.into(), BinOp::Ge, rhs.into()))
             },
             |x| {
                 let lhs = x.comparison()?;
+                let span = x.SPAN()?;
                 let _ = x.LE()?;
                 let rhs = match x.term() {
                     Some(value) => value,
                     None => return x.__error("<term>"),
                 };
-                Some(Expr::Binary(lhs.into(), BinOp::Le, rhs.into()))
+                Some(Expr::Binary(span, lhs.into(), BinOp::Le, rhs.into()))
             },
             |x| {
                 let lhs = x.comparison()?;
+                let span = x.SPAN()?;
                 let _ = x.GT()?;
                 let rhs = match x.term() {
                     Some(value) => value,
                     None => return x.__error("<term>"),
                 };
-                Some(Expr::Binary(lhs.into(), BinOp::Gt, rhs.into()))
+                Some(Expr::Binary(span, lhs.into(), BinOp::Gt, rhs.into()))
             },
             |x| {
                 let lhs = x.comparison()?;
+                let span = x.SPAN()?;
                 let _ = x.LT()?;
                 let rhs = match x.term() {
                     Some(value) => value,
                     None => return x.__error("<term>"),
                 };
-                Some(Expr::Binary(lhs.into(), BinOp::Lt, rhs.into()))
+                Some(Expr::Binary(span, lhs.into(), BinOp::Lt, rhs.into()))
             },
             |x| {
                 let term = x.term()?;
@@ -659,21 +865,23 @@ impl super::PhiLia093 {
         const RULES: super::R<Expr, 3usize> = [
             |x| {
                 let lhs = x.term()?;
+                let span = x.SPAN()?;
                 let _ = x.PLUS()?;
                 let rhs = match x.factor() {
                     Some(value) => value,
                     None => return x.__error("<factor>"),
                 };
-                Some(Expr::Binary(lhs.into(), BinOp::Add, rhs.into()))
+                Some(Expr::Binary(span, lhs.into(), BinOp::Add, rhs.into()))
             },
             |x| {
                 let lhs = x.term()?;
+                let span = x.SPAN()?;
                 let _ = x.DASH()?;
                 let rhs = match x.factor() {
                     Some(value) => value,
                     None => return x.__error("<factor>"),
                 };
-                Some(Expr::Binary(lhs.into(), BinOp::Sub, rhs.into()))
+                Some(Expr::Binary(span, lhs.into(), BinOp::Sub, rhs.into()))
             },
             |x| {
                 let factor = x.factor()?;
@@ -711,30 +919,33 @@ impl super::PhiLia093 {
         const RULES: super::R<Expr, 4usize> = [
             |x| {
                 let lhs = x.factor()?;
+                let span = x.SPAN()?;
                 let _ = x.ASTERISK()?;
                 let rhs = match x.at() {
                     Some(value) => value,
                     None => return x.__error("<at>"),
                 };
-                Some(Expr::Binary(lhs.into(), BinOp::Mul, rhs.into()))
+                Some(Expr::Binary(span, lhs.into(), BinOp::Mul, rhs.into()))
             },
             |x| {
                 let lhs = x.factor()?;
+                let span = x.SPAN()?;
                 let _ = x.SLASH()?;
                 let rhs = match x.at() {
                     Some(value) => value,
                     None => return x.__error("<at>"),
                 };
-                Some(Expr::Binary(lhs.into(), BinOp::Div, rhs.into()))
+                Some(Expr::Binary(span, lhs.into(), BinOp::Div, rhs.into()))
             },
             |x| {
                 let lhs = x.factor()?;
+                let span = x.SPAN()?;
                 let _ = x.PERCENT()?;
                 let rhs = match x.at() {
                     Some(value) => value,
                     None => return x.__error("<at>"),
                 };
-                Some(Expr::Binary(lhs.into(), BinOp::Mod, rhs.into()))
+                Some(Expr::Binary(span, lhs.into(), BinOp::Mod, rhs.into()))
             },
             |x| {
                 let at = x.at()?;
@@ -772,12 +983,13 @@ impl super::PhiLia093 {
         const RULES: super::R<Expr, 2usize> = [
             |x| {
                 let lhs = x.at()?;
+                let span = x.SPAN()?;
                 let _ = x.AT()?;
                 let rhs = match x.unary() {
                     Some(value) => value,
                     None => return x.__error("<unary>"),
                 };
-                Some(Expr::Binary(lhs.into(), BinOp::At, rhs.into()))
+                Some(Expr::Binary(span, lhs.into(), BinOp::At, rhs.into()))
             },
             |x| {
                 let unary = x.unary()?;
@@ -814,20 +1026,22 @@ impl super::PhiLia093 {
         }
         const RULES: super::R<Expr, 3usize> = [
             |x| {
+                let span = x.SPAN()?;
                 let _ = x.PLUS()?;
                 let unary = match x.unary() {
                     Some(value) => value,
                     None => return x.__error("<unary>"),
                 };
-                Some(Expr::Unary(UnaOp::Pos, unary.into()))
+                Some(Expr::Unary(span, UnaOp::Pos, unary.into()))
             },
             |x| {
+                let span = x.SPAN()?;
                 let _ = x.DASH()?;
                 let unary = match x.unary() {
                     Some(value) => value,
                     None => return x.__error("<unary>"),
                 };
-                Some(Expr::Unary(UnaOp::Neg, unary.into()))
+                Some(Expr::Unary(span, UnaOp::Neg, unary.into()))
             },
             |x| {
                 let call = x.call()?;
@@ -840,9 +1054,35 @@ impl super::PhiLia093 {
         if self.__snapshot.is_some() {
             return None;
         }
-        const RULES: super::R<Expr, 5usize> = [
+        const RULES: super::R<Expr, 7usize> = [
             |x| {
                 let call = x.call()?;
+                let span = x.SPAN()?;
+                let _ = x.DOT()?;
+                let _ = x.WITH()?;
+                let _ = x.LPAR()?;
+                let first = x.update()?;
+                let more = {
+                    let mut body = Vec::new();
+                    while let Some(data) = x.__peg([|x| {
+                        let _ = x.COMMA()?;
+                        let update = x.update()?;
+                        Some((update))
+                    }]) {
+                        body.push(data)
+                    }
+                    body
+                };
+                let _ = x.COMMA();
+                let _ = match x.RPAR() {
+                    Some(value) => value,
+                    None => return x.__error("<RPAR>"),
+                };
+                Some(Expr::With(span, call.into(), BufVec::new([first], more)))
+            },
+            |x| {
+                let call = x.call()?;
+                let span = x.SPAN()?;
                 let _ = x.DOT()?;
                 let ident = match x.IDENT() {
                     Some(value) => value,
@@ -854,36 +1094,45 @@ impl super::PhiLia093 {
                     Some(value) => value,
                     None => return x.__error("<RPAR>"),
                 };
-                Some(Expr::Method(call.into(), ident, args))
+                Some(Expr::Method(span, call.into(), ident, args))
             },
             |x| {
                 let call = x.call()?;
+                let span = x.SPAN()?;
                 let _ = x.DOT()?;
                 let ident = match x.IDENT() {
                     Some(value) => value,
                     None => return x.__error("<IDENT>"),
                 };
-                Some(Expr::Field(call.into(), ident))
+                Some(Expr::Field(span, call.into(), ident))
             },
             |x| {
                 let call = x.call()?;
+                let span = x.SPAN()?;
                 let _ = x.LPAR()?;
                 let args = x.args();
                 let _ = match x.RPAR() {
                     Some(value) => value,
                     None => return x.__error("<RPAR>"),
                 };
-                Some(Expr::Call(call.into(), args))
+                Some(Expr::Call(span, call.into(), args))
             },
             |x| {
                 let call = x.call()?;
+                let span = x.SPAN()?;
                 let _ = x.LSQB()?;
                 let index = x.expr()?;
                 let _ = match x.RSQB() {
                     Some(value) => value,
                     None => return x.__error("<RSQB>"),
                 };
-                Some(Expr::Index(call.into(), index.into()))
+                Some(Expr::Index(span, call.into(), index.into()))
+            },
+            |x| {
+                let call = x.call()?;
+                let span = x.SPAN()?;
+                let _ = x.QUESTION()?;
+                Some(Expr::Try(span, call.into()))
             },
             |x| {
                 let primary = x.primary()?;
@@ -914,6 +1163,21 @@ impl super::PhiLia093 {
         self.__memo.call.insert(start, (end, cache));
         result
     }
+    pub fn update(&mut self) -> Option<(usize, Expr)> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<(usize, Expr), 1usize> = [|x| {
+            let ident = x.IDENT()?;
+            let _ = x.EQ()?;
+            let expr = match x.expr() {
+                Some(value) => value,
+                None => return x.__error("<expr>"),
+            };
+            Some((ident, expr))
+        }];
+        self.__peg(RULES)
+    }
     pub fn args(&mut self) -> Option<BufVec<Expr, 1>> {
         if self.__snapshot.is_some() {
             return None;
@@ -940,25 +1204,34 @@ impl super::PhiLia093 {
         if self.__snapshot.is_some() {
             return None;
         }
-        const RULES: super::R<Expr, 8usize> = [
+        const RULES: super::R<Expr, 12usize> = [
             |x| {
+                let span = x.SPAN()?;
                 let lit = x.lit()?;
-                Some(Expr::Lit(lit))
+                Some(Expr::Lit(span, lit))
             },
             |x| {
+                let span = x.SPAN()?;
+                let fmt = x.FMT()?;
+                Some(Expr::Format(span, fmt))
+            },
+            |x| {
+                let span = x.SPAN()?;
                 let path = x.path()?;
-                Some(Expr::Path(x.__stream.cursor, path))
+                Some(Expr::Path(span, path))
             },
             |x| {
+                let span = x.SPAN()?;
                 let _ = x.LPAR()?;
                 let expr = match x.expr() {
                     Some(value) => value,
                     None => return x.__error("<expr>"),
                 };
                 let _ = x.RPAR()?;
-                Some(Expr::Paren(expr.into()))
+                Some(Expr::Paren(span, expr.into()))
             },
             |x| {
+                let span = x.SPAN()?;
                 let _ = x.LPAR()?;
                 let first = match x.expr() {
                     Some(value) => value,
@@ -988,18 +1261,52 @@ impl super::PhiLia093 {
                     Some(value) => value,
                     None => return x.__error("<RPAR>"),
                 };
-                Some(Expr::Tuple(BufVec::new([first, second], more)))
+                Some(Expr::Tuple(span, BufVec::new([first, second], more)))
             },
             |x| {
+                let span = x.SPAN()?;
                 let _ = x.LSQB()?;
                 let args = x.args();
                 let _ = match x.RSQB() {
                     Some(value) => value,
                     None => return x.__error("<RSQB>"),
                 };
-                Some(Expr::List(args))
+                Some(Expr::List(span, args))
+            },
+            |x| {
+                let span = x.SPAN()?;
+                let _ = x.LBRA()?;
+                let _ = x.COLON()?;
+                let _ = match x.RBRA() {
+                    Some(value) => value,
+                    None => return x.__error("<RBRA>"),
+                };
+                Some(Expr::Map(span, None))
+            },
+            |x| {
+                let span = x.SPAN()?;
+                let _ = x.LBRA()?;
+                let first = x.entry()?;
+                let more = {
+                    let mut body = Vec::new();
+                    while let Some(data) = x.__peg([|x| {
+                        let _ = x.COMMA()?;
+                        let entry = x.entry()?;
+                        Some((entry))
+                    }]) {
+                        body.push(data)
+                    }
+                    body
+                };
+                let _ = x.COMMA();
+                let _ = match x.RBRA() {
+                    Some(value) => value,
+                    None => return x.__error("<RBRA>"),
+                };
+                Some(Expr::Map(span, Some(BufVec::new([first], more))))
             },
             |x| {
+                let span = x.SPAN()?;
                 let _ = x.IF()?;
                 let expr = match x.expr() {
                     Some(value) => value,
@@ -1017,23 +1324,73 @@ impl super::PhiLia093 {
                     };
                     Some((expr))
                 }]);
-                Some(Expr::If(expr.into(), block, otherwise.map(Rc::new)))
+                Some(Expr::If(span, expr.into(), block, otherwise.map(Rc::new)))
             },
             |x| {
+                let span = x.SPAN()?;
+                let _ = x.MATCH()?;
+                let expr = match x.expr() {
+                    Some(value) => value,
+                    None => return x.__error("<expr>"),
+                };
+                let _ = match x.LBRA() {
+                    Some(value) => value,
+                    None => return x.__error("<LBRA>"),
+                };
+                let first = match x.arm() {
+                    Some(value) => value,
+                    None => return x.__error("<arm>"),
+                };
+                let more = {
+                    let mut body = Vec::new();
+                    while let Some(data) = x.__peg([|x| {
+                        let _ = x.COMMA()?;
+                        let arm = x.arm()?;
+                        Some((arm))
+                    }]) {
+                        body.push(data)
+                    }
+                    body
+                };
+                let _ = x.COMMA();
+                let _ = match x.RBRA() {
+                    Some(value) => value,
+                    None => return x.__error("<RBRA>"),
+                };
+                Some(Expr::Match(span, expr.into(), BufVec::new([first], more)))
+            },
+            |x| {
+                let span = x.SPAN()?;
                 let _ = x.LOOP()?;
                 let block = match x.block() {
                     Some(value) => value,
                     None => return x.__error("<block>"),
                 };
-                Some(Expr::Loop(block))
+                Some(Expr::Loop(span, block))
             },
             |x| {
+                let span = x.SPAN()?;
                 let block = x.block()?;
-                Some(Expr::Block(block))
+                Some(Expr::Block(span, block))
             },
         ];
         self.__peg(RULES)
     }
+    pub fn entry(&mut self) -> Option<(Expr, Expr)> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<(Expr, Expr), 1usize> = [|x| {
+            let key = x.expr()?;
+            let _ = x.COLON()?;
+            let value = match x.expr() {
+                Some(value) => value,
+                None => return x.__error("<expr>"),
+            };
+            Some((key, value))
+        }];
+        self.__peg(RULES)
+    }
     pub fn params(&mut self) -> Option<BufVec<usize, 1>> {
         if self.__snapshot.is_some() {
             return None;
@@ -1084,11 +1441,26 @@ impl super::PhiLia093 {
         if self.__snapshot.is_some() {
             return None;
         }
-        const RULES: super::R<Lit, 4usize> = [
+        const RULES: super::R<Lit, 7usize> = [
+            |x| {
+                let float = x.FLOAT()?;
+                let _ = x.F64()?;
+                Some(Lit::F64(float))
+            },
             |x| {
                 let float = x.FLOAT()?;
                 Some(Lit::Float(float))
             },
+            |x| {
+                let int = x.INT()?;
+                let _ = x.I64()?;
+                Some(Lit::I64(int))
+            },
+            |x| {
+                let int = x.INT()?;
+                let _ = x.F64()?;
+                Some(Lit::F64(int))
+            },
             |x| {
                 let int = x.INT()?;
                 Some(Lit::Int(int))
@@ -1121,6 +1493,23 @@ impl super::PhiLia093 {
         }];
         self.__peg(RULES)
     }
+    pub fn SPAN(&mut self) -> Option<usize> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<usize, 1usize> = [|x| {
+            let _ = {
+                let mut body = Vec::new();
+                while let Some(data) = x.T() {
+                    body.push(data)
+                }
+                body
+            };
+            let span = x.cursor()?;
+            Some((span))
+        }];
+        self.__peg(RULES)
+    }
     pub fn FLOAT(&mut self) -> Option<usize> {
         if self.__snapshot.is_some() {
             return None;
@@ -1155,6 +1544,28 @@ impl super::PhiLia093 {
         }];
         self.__peg(RULES)
     }
+    pub fn I64(&mut self) -> Option<()> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<(), 1usize> = [|x| {
+            let _ = x.__expect("i64")?;
+            let _ = x.__lookahead(|x| x.TAIL(), false)?;
+            Some(())
+        }];
+        self.__peg(RULES)
+    }
+    pub fn F64(&mut self) -> Option<()> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<(), 1usize> = [|x| {
+            let _ = x.__expect("f64")?;
+            let _ = x.__lookahead(|x| x.TAIL(), false)?;
+            Some(())
+        }];
+        self.__peg(RULES)
+    }
     pub fn STR(&mut self) -> Option<Vec<Chunk>> {
         if self.__snapshot.is_some() {
             return None;
@@ -1183,6 +1594,34 @@ impl super::PhiLia093 {
         }];
         self.__peg(RULES)
     }
+    pub fn FMT(&mut self) -> Option<Vec<Segment>> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<Vec<Segment>, 1usize> = [|x| {
+            let _ = {
+                let mut body = Vec::new();
+                while let Some(data) = x.T() {
+                    body.push(data)
+                }
+                body
+            };
+            let _ = x.__expect("f\"")?;
+            let segments = {
+                let mut body = Vec::new();
+                while let Some(data) = x.SEGMENT() {
+                    body.push(data)
+                }
+                body
+            };
+            let _ = match x.__expect("\"") {
+                Some(value) => value,
+                None => return x.__error("'\"'"),
+            };
+            Some((segments))
+        }];
+        self.__peg(RULES)
+    }
     pub fn BOOL(&mut self) -> Option<Bool> {
         if self.__snapshot.is_some() {
             return None;
@@ -1287,6 +1726,42 @@ impl super::PhiLia093 {
         }];
         self.__peg(RULES)
     }
+    pub fn USE(&mut self) -> Option<()> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<(), 1usize> = [|x| {
+            let _ = {
+                let mut body = Vec::new();
+                while let Some(data) = x.T() {
+                    body.push(data)
+                }
+                body
+            };
+            let _ = x.__expect("use")?;
+            let _ = x.__lookahead(|x| x.TAIL(), false)?;
+            Some(())
+        }];
+        self.__peg(RULES)
+    }
+    pub fn MOD(&mut self) -> Option<()> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<(), 1usize> = [|x| {
+            let _ = {
+                let mut body = Vec::new();
+                while let Some(data) = x.T() {
+                    body.push(data)
+                }
+                body
+            };
+            let _ = x.__expect("mod")?;
+            let _ = x.__lookahead(|x| x.TAIL(), false)?;
+            Some(())
+        }];
+        self.__peg(RULES)
+    }
     pub fn IF(&mut self) -> Option<()> {
         if self.__snapshot.is_some() {
             return None;
@@ -1395,6 +1870,24 @@ impl super::PhiLia093 {
         }];
         self.__peg(RULES)
     }
+    pub fn MATCH(&mut self) -> Option<()> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<(), 1usize> = [|x| {
+            let _ = {
+                let mut body = Vec::new();
+                while let Some(data) = x.T() {
+                    body.push(data)
+                }
+                body
+            };
+            let _ = x.__expect("match")?;
+            let _ = x.__lookahead(|x| x.TAIL(), false)?;
+            Some(())
+        }];
+        self.__peg(RULES)
+    }
     pub fn RETURN(&mut self) -> Option<()> {
         if self.__snapshot.is_some() {
             return None;
@@ -1467,6 +1960,24 @@ impl super::PhiLia093 {
         }];
         self.__peg(RULES)
     }
+    pub fn WITH(&mut self) -> Option<()> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<(), 1usize> = [|x| {
+            let _ = {
+                let mut body = Vec::new();
+                while let Some(data) = x.T() {
+                    body.push(data)
+                }
+                body
+            };
+            let _ = x.__expect("with")?;
+            let _ = x.__lookahead(|x| x.TAIL(), false)?;
+            Some(())
+        }];
+        self.__peg(RULES)
+    }
     pub fn OR(&mut self) -> Option<()> {
         if self.__snapshot.is_some() {
             return None;
@@ -1636,6 +2147,7 @@ impl super::PhiLia093 {
                 body
             };
             let _ = x.__expect("+")?;
+            let _ = x.__lookahead(|x| x.__expect("="), false)?;
             Some(())
         }];
         self.__peg(RULES)
@@ -1653,6 +2165,7 @@ impl super::PhiLia093 {
                 body
             };
             let _ = x.__expect("-")?;
+            let _ = x.__lookahead(|x| x.__expect("="), false)?;
             Some(())
         }];
         self.__peg(RULES)
@@ -1670,6 +2183,7 @@ impl super::PhiLia093 {
                 body
             };
             let _ = x.__expect("*")?;
+            let _ = x.__lookahead(|x| x.__expect("="), false)?;
             Some(())
         }];
         self.__peg(RULES)
@@ -1687,6 +2201,7 @@ impl super::PhiLia093 {
                 body
             };
             let _ = x.__expect("/")?;
+            let _ = x.__lookahead(|x| x.__expect("="), false)?;
             Some(())
         }];
         self.__peg(RULES)
@@ -1704,6 +2219,7 @@ impl super::PhiLia093 {
                 body
             };
             let _ = x.__expect("%")?;
+            let _ = x.__lookahead(|x| x.__expect("="), false)?;
             Some(())
         }];
         self.__peg(RULES)
@@ -1721,6 +2237,24 @@ impl super::PhiLia093 {
                 body
             };
             let _ = x.__expect(".")?;
+            let _ = x.__lookahead(|x| x.__expect("."), false)?;
+            Some(())
+        }];
+        self.__peg(RULES)
+    }
+    pub fn DOTDOT(&mut self) -> Option<()> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<(), 1usize> = [|x| {
+            let _ = {
+                let mut body = Vec::new();
+                while let Some(data) = x.T() {
+                    body.push(data)
+                }
+                body
+            };
+            let _ = x.__expect("..")?;
             Some(())
         }];
         self.__peg(RULES)
@@ -1760,6 +2294,23 @@ impl super::PhiLia093 {
         }];
         self.__peg(RULES)
     }
+    pub fn ARROW(&mut self) -> Option<()> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<(), 1usize> = [|x| {
+            let _ = {
+                let mut body = Vec::new();
+                while let Some(data) = x.T() {
+                    body.push(data)
+                }
+                body
+            };
+            let _ = x.__expect("=>")?;
+            Some(())
+        }];
+        self.__peg(RULES)
+    }
     pub fn ADDEQ(&mut self) -> Option<()> {
         if self.__snapshot.is_some() {
             return None;
@@ -1964,6 +2515,40 @@ impl super::PhiLia093 {
         }];
         self.__peg(RULES)
     }
+    pub fn PIPE(&mut self) -> Option<()> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<(), 1usize> = [|x| {
+            let _ = {
+                let mut body = Vec::new();
+                while let Some(data) = x.T() {
+                    body.push(data)
+                }
+                body
+            };
+            let _ = x.__expect("|")?;
+            Some(())
+        }];
+        self.__peg(RULES)
+    }
+    pub fn QUESTION(&mut self) -> Option<()> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<(), 1usize> = [|x| {
+            let _ = {
+                let mut body = Vec::new();
+                while let Some(data) = x.T() {
+                    body.push(data)
+                }
+                body
+            };
+            let _ = x.__expect("?")?;
+            Some(())
+        }];
+        self.__peg(RULES)
+    }
     pub fn SEMI(&mut self) -> Option<()> {
         if self.__snapshot.is_some() {
             return None;
@@ -1981,6 +2566,24 @@ impl super::PhiLia093 {
         }];
         self.__peg(RULES)
     }
+    pub fn COLON(&mut self) -> Option<()> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<(), 1usize> = [|x| {
+            let _ = {
+                let mut body = Vec::new();
+                while let Some(data) = x.T() {
+                    body.push(data)
+                }
+                body
+            };
+            let _ = x.__expect(":")?;
+            let _ = x.__lookahead(|x| x.__expect(":"), false)?;
+            Some(())
+        }];
+        self.__peg(RULES)
+    }
     pub fn COLON2(&mut self) -> Option<()> {
         if self.__snapshot.is_some() {
             return None;
@@ -2036,11 +2639,51 @@ impl super::PhiLia093 {
         if self.__snapshot.is_some() {
             return None;
         }
-        const RULES: super::R<Chunk, 3usize> = [
+        const RULES: super::R<Chunk, 2usize> = [
             |x| {
                 let slice = x.SLICE()?;
                 Some(Chunk::Slice(slice))
             },
+            |x| {
+                let escape = x.ESC()?;
+                Some(escape)
+            },
+        ];
+        self.__peg(RULES)
+    }
+    pub fn SEGMENT(&mut self) -> Option<Segment> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<Segment, 3usize> = [
+            |x| {
+                let _ = x.__expect("{")?;
+                let expr = match x.expr() {
+                    Some(value) => value,
+                    None => return x.__error("<expr>"),
+                };
+                let _ = match x.RBRA() {
+                    Some(value) => value,
+                    None => return x.__error("<RBRA>"),
+                };
+                Some(Segment::Expr(expr))
+            },
+            |x| {
+                let text = x.TEXT()?;
+                Some(Segment::Chunk(Chunk::Slice(text)))
+            },
+            |x| {
+                let escape = x.ESC()?;
+                Some(Segment::Chunk(escape))
+            },
+        ];
+        self.__peg(RULES)
+    }
+    pub fn ESC(&mut self) -> Option<Chunk> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        const RULES: super::R<Chunk, 2usize> = [
             |x| {
                 let _ = x.__expect("\\u{")?;
                 let hex = match x.HEX() {
@@ -2166,6 +2809,31 @@ impl super::PhiLia093 {
             .dfa(transition, ACCEPTANCE)
             .map(|s| self.__interner.intern(s))
     }
+    pub fn TEXT(&mut self) -> Option<usize> {
+        if self.__snapshot.is_some() {
+            return None;
+        }
+        fn transition(s: usize, c: char) -> Option<usize> {
+            let s = match (s, c as usize) {
+                (0usize, 0usize..=33usize) => 1usize,
+                (0usize, 35usize..=91usize) => 1usize,
+                (0usize, 93usize..=122usize) => 1usize,
+                (0usize, 124usize..=124usize) => 1usize,
+                (0usize, 126usize..=18446744073709551615usize) => 1usize,
+                (1usize, 0usize..=33usize) => 1usize,
+                (1usize, 35usize..=91usize) => 1usize,
+                (1usize, 93usize..=122usize) => 1usize,
+                (1usize, 124usize..=124usize) => 1usize,
+                (1usize, 126usize..=18446744073709551615usize) => 1usize,
+                _ => return None,
+            };
+            Some(s)
+        }
+        const ACCEPTANCE: [bool; 2usize] = [false, true];
+        self.__stream
+            .dfa(transition, ACCEPTANCE)
+            .map(|s| self.__interner.intern(s))
+    }
     pub fn HEX(&mut self) -> Option<usize> {
         if self.__snapshot.is_some() {
             return None;
@@ -2199,6 +2867,8 @@ impl super::PhiLia093 {
                 (0usize, 110usize..=110usize) => 1usize,
                 (0usize, 114usize..=114usize) => 1usize,
                 (0usize, 116usize..=116usize) => 1usize,
+                (0usize, 123usize..=123usize) => 1usize,
+                (0usize, 125usize..=125usize) => 1usize,
                 _ => return None,
             };
             Some(s)
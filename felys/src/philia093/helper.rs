@@ -1,4 +1,5 @@
-use crate::philia093::PhiLia093;
+use crate::philia093::{Interner, PhiLia093};
+use crate::utils::snippet::Snippet;
 use crate::utils::stages::I;
 use std::fmt::{Display, Formatter};
 
@@ -6,42 +7,28 @@ impl PhiLia093 {
     pub fn parse(mut self) -> Result<I, String> {
         let root = self.root();
         if let Some((cursor, msg)) = self.__snapshot {
-            let data = self.__stream.data;
-
-            let row = data[..cursor].chars().filter(|c| *c == '\n').count();
-            let mut col = 0;
-            let mut start = cursor;
-            for ch in data[..cursor].chars().rev() {
-                if ch == '\n' {
-                    break;
-                }
-                start -= ch.len_utf8();
-                col += 1;
-            }
-            let mut end = cursor;
-            for ch in data[cursor..].chars() {
-                if ch == '\n' {
-                    break;
-                }
-                end += ch.len_utf8();
-            }
-
-            let snippet = data[start..end].to_string();
             let error = Error {
-                snippet,
-                row,
-                col,
+                snippet: Snippet::new(&self.__stream.data, cursor),
                 msg,
             };
             Err(error.to_string())
         } else {
             Ok(I {
                 root: root.unwrap(),
+                modules: Vec::new(),
+                externs: Vec::new(),
                 interner: self.__interner,
+                source: self.__stream.data,
             })
         }
     }
 
+    pub fn resume(mut self, interner: Interner, cursor: usize) -> Self {
+        self.__interner = interner;
+        self.__stream.cursor = cursor;
+        self
+    }
+
     pub fn n2i(&mut self) -> Option<usize> {
         let id = self.NAME()?;
         let ident = self.__interner.resolve(&id).unwrap();
@@ -51,23 +38,22 @@ impl PhiLia093 {
             Some(id)
         }
     }
+
+    pub fn cursor(&mut self) -> Option<usize> {
+        Some(self.__stream.cursor)
+    }
 }
 
 struct Error {
-    snippet: String,
-    row: usize,
-    col: usize,
+    snippet: Snippet,
     msg: &'static str,
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let row = self.row + 1;
-        let col = self.col + 1;
-        let padding = " ".repeat(row.to_string().len());
+        let row = self.snippet.row + 1;
+        let col = self.snippet.col + 1;
         writeln!(f, "PhiLia093: {} at {}:{}", self.msg, row, col)?;
-        writeln!(f, " {} |", padding)?;
-        writeln!(f, " {} | {}", row, self.snippet)?;
-        writeln!(f, " {} | {}^", padding, " ".repeat(self.col))
+        write!(f, "{}", self.snippet)
     }
 }
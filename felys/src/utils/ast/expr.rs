@@ -4,24 +4,30 @@ use std::rc::Rc;
 
 #[derive(Clone, Debug)]
 pub enum Expr {
-    Block(Block),
-    Break(Option<Rc<Expr>>),
-    Continue,
-    For(Pat, Rc<Expr>, Block),
-    If(Rc<Expr>, Block, Option<Rc<Expr>>),
-    Loop(Block),
-    Return(Rc<Expr>),
-    While(Rc<Expr>, Block),
-    Binary(Rc<Expr>, BinOp, Rc<Expr>),
-    Call(Rc<Expr>, Option<BufVec<Expr, 1>>),
-    Field(Rc<Expr>, usize),
-    Method(Rc<Expr>, usize, Option<BufVec<Expr, 1>>),
-    Index(Rc<Expr>, Rc<Expr>),
-    Tuple(BufVec<Expr, 2>),
-    List(Option<BufVec<Expr, 1>>),
-    Lit(Lit),
-    Paren(Rc<Expr>),
-    Unary(UnaOp, Rc<Expr>),
+    Block(usize, Block),
+    Break(usize, Option<Rc<Expr>>),
+    Continue(usize),
+    For(usize, Pat, Rc<Expr>, Block),
+    If(usize, Rc<Expr>, Block, Option<Rc<Expr>>),
+    Loop(usize, Block),
+    Match(usize, Rc<Expr>, BufVec<Arm, 1>),
+    Return(usize, Rc<Expr>),
+    While(usize, Rc<Expr>, Block),
+    Closure(usize, Option<BufVec<usize, 1>>, Rc<Expr>),
+    Binary(usize, Rc<Expr>, BinOp, Rc<Expr>),
+    Call(usize, Rc<Expr>, Option<BufVec<Expr, 1>>),
+    Field(usize, Rc<Expr>, usize),
+    Method(usize, Rc<Expr>, usize, Option<BufVec<Expr, 1>>),
+    With(usize, Rc<Expr>, BufVec<(usize, Expr), 1>),
+    Index(usize, Rc<Expr>, Rc<Expr>),
+    Try(usize, Rc<Expr>),
+    Tuple(usize, BufVec<Expr, 2>),
+    List(usize, Option<BufVec<Expr, 1>>),
+    Map(usize, Option<BufVec<(Expr, Expr), 1>>),
+    Lit(usize, Lit),
+    Format(usize, Vec<Segment>),
+    Paren(usize, Rc<Expr>),
+    Unary(usize, UnaOp, Rc<Expr>),
     Path(usize, BufVec<usize, 1>),
 }
 
@@ -48,4 +54,5 @@ pub enum UnaOp {
     Not,
     Pos,
     Neg,
+    Fmt,
 }
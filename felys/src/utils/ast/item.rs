@@ -6,6 +6,8 @@ pub enum Item {
     Impl(usize, BufVec<Impl, 1>),
     Fn(usize, Option<BufVec<usize, 1>>, Block),
     Main(usize, Block),
+    Use(BufVec<usize, 1>),
+    Mod(BufVec<usize, 1>),
 }
 
 #[derive(Clone, Debug)]
@@ -1,9 +1,13 @@
+use crate::utils::ast::Expr;
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub enum Lit {
     Int(usize),
     Float(usize),
     Bool(Bool),
     Str(Vec<Chunk>),
+    I64(usize),
+    F64(usize),
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
@@ -18,3 +22,9 @@ pub enum Chunk {
     Unicode(usize),
     Escape(usize),
 }
+
+#[derive(Clone, Debug)]
+pub enum Segment {
+    Chunk(Chunk),
+    Expr(Expr),
+}
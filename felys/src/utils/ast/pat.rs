@@ -6,3 +6,17 @@ pub enum Pat {
     Tuple(BufVec<Pat, 2>),
     Ident(usize),
 }
+
+#[derive(Clone, Debug)]
+pub enum Case {
+    Any,
+    Ident(usize),
+    Lit(Lit),
+    Neg(Lit),
+    Tuple(BufVec<Case, 2>),
+    Group(usize, BufVec<usize, 1>, BufVec<Case, 1>),
+    List(Vec<Case>, Option<Option<usize>>),
+}
+
+#[derive(Clone, Debug)]
+pub struct Arm(pub usize, pub Case, pub Option<Expr>, pub Expr);
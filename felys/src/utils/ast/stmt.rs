@@ -5,7 +5,7 @@ pub enum Stmt {
     Empty,
     Expr(Expr),
     Semi(Expr),
-    Assign(Pat, AssOp, Expr),
+    Assign(usize, Pat, AssOp, Expr),
 }
 
 #[derive(Clone, Debug)]
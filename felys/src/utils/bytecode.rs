@@ -1,5 +1,5 @@
 use crate::utils::ast::{BinOp, UnaOp};
-use crate::utils::function::Pointer;
+use crate::utils::function::{Pointer, Shape};
 
 #[derive(Debug)]
 pub enum Bytecode {
@@ -10,15 +10,27 @@ pub enum Bytecode {
     Load(Reg, Index),
     Binary(Reg, Reg, BinOp, Reg),
     Unary(Reg, UnaOp, Reg),
+    Check(Reg, Reg),
+    Test(Reg, Reg, Shape, Index),
+    Tail(Reg, Reg, Index),
     Call(Reg, Reg, Vec<Reg>),
     List(Reg, Vec<Reg>),
     Tuple(Reg, Vec<Reg>),
+    Map(Reg, Vec<Reg>),
+    Closure(Reg, Index, Vec<Reg>),
     Index(Reg, Reg, Reg),
+    Nth(Reg, Reg, Reg),
+    Update(Reg, Reg, Id, Reg),
     Method(Reg, Reg, Id, Vec<Reg>),
     Branch(Reg, Index, Index),
     Jump(Index),
     Return(Reg),
     Copy(Reg, Reg),
+    Iter(Reg, Reg),
+    Has(Reg, Reg),
+    Next(Reg, Reg),
+    TailCall(Reg, Reg, Vec<Reg>),
+    TailMethod(Reg, Reg, Id, Vec<Reg>),
 }
 
 pub type Reg = u8;
@@ -8,6 +8,8 @@ pub struct Function {
     pub entry: Fragment,
     pub fragments: HashMap<usize, Fragment>,
     pub exit: Fragment,
+    pub spans: HashMap<Var, usize>,
+    pub branches: HashMap<Label, usize>,
 }
 
 impl Function {
@@ -109,11 +111,50 @@ pub enum Instruction {
     Load(Var, Const),
     Binary(Var, Var, BinOp, Var),
     Unary(Var, UnaOp, Var),
+    Check(Var, Var),
+    Test(Var, Var, Shape, usize),
+    Tail(Var, Var, usize),
     Call(Var, Var, Vec<Var>),
     List(Var, Vec<Var>),
     Tuple(Var, Vec<Var>),
+    Map(Var, Vec<Var>),
+    Closure(Var, usize, Vec<Var>),
     Index(Var, Var, Var),
+    Nth(Var, Var, Var),
+    Update(Var, Var, usize, Var),
     Method(Var, Var, usize, Vec<Var>),
+    Iter(Var, Var),
+    Has(Var, Var),
+    Next(Var, Var),
+}
+
+impl Instruction {
+    pub fn dst(&self) -> Var {
+        match self {
+            Instruction::Arg(dst, _)
+            | Instruction::Field(dst, _, _)
+            | Instruction::Unpack(dst, _, _)
+            | Instruction::Load(dst, _)
+            | Instruction::Binary(dst, _, _, _)
+            | Instruction::Unary(dst, _, _)
+            | Instruction::Check(dst, _)
+            | Instruction::Test(dst, _, _, _)
+            | Instruction::Tail(dst, _, _)
+            | Instruction::Call(dst, _, _)
+            | Instruction::List(dst, _)
+            | Instruction::Tuple(dst, _)
+            | Instruction::Map(dst, _)
+            | Instruction::Closure(dst, _, _)
+            | Instruction::Index(dst, _, _)
+            | Instruction::Nth(dst, _, _)
+            | Instruction::Update(dst, _, _, _)
+            | Instruction::Method(dst, _, _, _)
+            | Instruction::Iter(dst, _)
+            | Instruction::Has(dst, _)
+            | Instruction::Next(dst, _)
+            | Instruction::Pointer(dst, _, _) => *dst,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -123,6 +164,17 @@ pub enum Pointer {
     Rust,
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Shape {
+    Tuple,
+    List,
+    Rest,
+    Group,
+    Number,
+    Str,
+    Bool,
+}
+
 #[derive(Debug)]
 pub enum Terminator {
     Branch(Var, Label, Label),
@@ -146,4 +198,6 @@ pub enum Const {
     Float(u32),
     Bool(bool),
     Str(Rc<str>),
+    I64(i64),
+    F64(u64),
 }
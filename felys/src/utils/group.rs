@@ -3,17 +3,19 @@ use std::collections::HashMap;
 
 #[derive(Debug)]
 pub struct Group {
+    pub name: String,
     pub indices: HashMap<Id, Index>,
     pub methods: HashMap<Id, Index>,
 }
 
 impl Group {
-    pub fn new<'a>(fields: impl Iterator<Item = &'a usize>) -> Self {
+    pub fn new<'a>(name: String, fields: impl Iterator<Item = &'a usize>) -> Self {
         let mut indices = HashMap::new();
         for (i, field) in fields.enumerate() {
             indices.insert(Id::try_from(*field).unwrap(), Index::try_from(i).unwrap());
         }
         Self {
+            name,
             indices,
             methods: HashMap::new(),
         }
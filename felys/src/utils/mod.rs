@@ -4,5 +4,7 @@ pub mod function;
 pub mod group;
 pub mod namespace;
 mod recover;
+pub mod repl;
+pub mod snippet;
 pub mod stages;
 pub mod stdlib;
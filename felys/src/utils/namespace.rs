@@ -1,11 +1,19 @@
+use crate::cyrene::error::Error;
 use crate::philia093::Interner;
 use crate::utils::function::Pointer;
-use crate::utils::stdlib::registry::STDLIB;
+use crate::utils::stdlib::registry::Registry;
 use std::collections::HashMap;
 
 pub struct Namespace {
     ids: usize,
     tree: HashMap<usize, Node>,
+    scopes: Vec<Scope>,
+    arities: HashMap<usize, usize>,
+}
+
+struct Scope {
+    prefix: Vec<usize>,
+    imports: HashMap<usize, Vec<usize>>,
 }
 
 enum Node {
@@ -16,23 +24,78 @@ enum Node {
 }
 
 impl Namespace {
-    pub fn init(interner: &mut Interner) -> Self {
-        let mut base = HashMap::new();
-        for (i, (sub, inner, _)) in STDLIB.iter().enumerate() {
-            if let Node::Redirect(x) = base
-                .entry(interner.intern(sub))
-                .or_insert(Node::Redirect(HashMap::new()))
-            {
-                x.insert(interner.intern(inner), Node::Rust(i));
+    pub fn init(interner: &mut Interner, registry: &Registry) -> Result<Self, Error> {
+        let mut tree = HashMap::new();
+        for (i, (symbol, _)) in registry.iter().enumerate() {
+            let mut path = symbol
+                .split("::")
+                .map(|x| interner.intern(x))
+                .collect::<Vec<_>>();
+            let name = path.pop().unwrap();
+            let mut cursor = &mut tree;
+            for space in path {
+                let node = cursor
+                    .entry(space)
+                    .or_insert(Node::Redirect(HashMap::new()));
+                let Node::Redirect(next) = node else {
+                    return Err(Error::ConflictingNative(symbol.clone()));
+                };
+                cursor = next;
+            }
+            if cursor.insert(name, Node::Rust(i)).is_some() {
+                return Err(Error::ConflictingNative(symbol.clone()));
             }
         }
-        Self {
+        let root = Scope {
+            prefix: Vec::new(),
+            imports: HashMap::new(),
+        };
+        Ok(Self {
             ids: 0,
-            tree: HashMap::from([(interner.intern("std"), Node::Redirect(base))]),
+            tree,
+            scopes: vec![root],
+            arities: HashMap::new(),
+        })
+    }
+
+    pub fn scope(&mut self, prefix: Vec<usize>) -> usize {
+        let scope = Scope {
+            prefix,
+            imports: HashMap::new(),
+        };
+        self.scopes.push(scope);
+        self.scopes.len() - 1
+    }
+
+    pub fn prefix(&self, scope: usize) -> &[usize] {
+        &self.scopes[scope].prefix
+    }
+
+    pub fn import(&mut self, scope: usize, path: Vec<usize>) -> bool {
+        let name = *path.last().unwrap();
+        let imports = &mut self.scopes[scope].imports;
+        imports.insert(name, path).is_none()
+    }
+
+    pub fn loaded(&self, path: &[usize]) -> bool {
+        self.scopes.iter().any(|x| x.prefix == path)
+    }
+
+    pub fn exists(&self, path: &[usize]) -> bool {
+        if self.find(path.iter()).is_some() {
+            return true;
         }
+        let mut cursor = &self.tree;
+        for space in path {
+            let Some(Node::Redirect(next)) = cursor.get(space) else {
+                return false;
+            };
+            cursor = next;
+        }
+        true
     }
 
-    pub fn allocate(&mut self, path: &[usize], name: usize) -> Option<usize> {
+    pub fn allocate(&mut self, path: &[usize], name: usize, arity: Option<usize>) -> Option<usize> {
         let id = self.id();
         let mut cursor = &mut self.tree;
 
@@ -51,9 +114,16 @@ impl Namespace {
         {
             return None;
         };
+        if let Some(arity) = arity {
+            self.arities.insert(id, arity);
+        }
         Some(id)
     }
 
+    pub fn arity(&self, gp: usize) -> Option<usize> {
+        self.arities.get(&gp).copied()
+    }
+
     pub fn attach(&mut self, path: &[usize], name: usize) -> Option<usize> {
         let id = self.id();
         let mut cursor = &mut self.tree;
@@ -81,7 +151,21 @@ impl Namespace {
         }
     }
 
-    pub fn get<'a>(&self, mut path: impl Iterator<Item = &'a usize>) -> Option<(Pointer, usize)> {
+    pub fn get<'a>(
+        &self,
+        scope: usize,
+        path: impl Iterator<Item = &'a usize>,
+    ) -> Option<(Pointer, usize)> {
+        let path = path.copied().collect::<Vec<_>>();
+        let scope = &self.scopes[scope];
+        if let Some(target) = scope.imports.get(path.first()?) {
+            return self.find(target.iter().chain(&path[1..]));
+        }
+        self.find(scope.prefix.iter().chain(path.iter()))
+            .or_else(|| self.find(path.iter()))
+    }
+
+    fn find<'a>(&self, mut path: impl Iterator<Item = &'a usize>) -> Option<(Pointer, usize)> {
         let mut cursor = &self.tree;
         let mut tmp = None;
 
@@ -105,6 +189,10 @@ impl Namespace {
         tmp
     }
 
+    pub fn reserve(&mut self) -> usize {
+        self.id()
+    }
+
     fn id(&mut self) -> usize {
         let id = self.ids;
         self.ids += 1;
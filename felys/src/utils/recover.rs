@@ -1,6 +1,6 @@
 use crate::philia093::Interner;
 use crate::utils::ast::{
-    AssOp, BinOp, Block, Bool, Chunk, Expr, Impl, Item, Lit, Pat, Stmt, UnaOp,
+    Arm, AssOp, BinOp, Block, Bool, Case, Chunk, Expr, Impl, Item, Lit, Pat, Segment, Stmt, UnaOp,
 };
 use std::fmt::{Display, Formatter, Write};
 
@@ -43,6 +43,22 @@ impl Item {
                 write!(f, "fn main({}) ", interner.resolve(args).unwrap())?;
                 block.recover(f, start, indent, None, interner)
             }
+            Item::Use(path) | Item::Mod(path) => {
+                let keyword = if let Item::Use(_) = self {
+                    "use"
+                } else {
+                    "mod"
+                };
+                write!(f, "{keyword} ")?;
+                let mut iter = path.iter();
+                if let Some(first) = iter.next() {
+                    write!(f, "{}", interner.resolve(first).unwrap())?;
+                }
+                for space in iter {
+                    write!(f, "::{}", interner.resolve(space).unwrap())?;
+                }
+                write!(f, ";")
+            }
         }
     }
 }
@@ -97,7 +113,7 @@ impl Stmt {
                 expr.recover(f, start, indent, interner)?;
                 write!(f, "; ")
             }
-            Stmt::Assign(pat, op, expr) => {
+            Stmt::Assign(_, pat, op, expr) => {
                 pat.recover(f, interner)?;
                 write!(f, " {op} ")?;
                 expr.recover(f, start, indent, interner)?;
@@ -154,6 +170,70 @@ impl Pat {
     }
 }
 
+impl Case {
+    pub fn recover<W: Write>(&self, f: &mut W, interner: &Interner) -> std::fmt::Result {
+        match self {
+            Case::Any => write!(f, "_"),
+            Case::Ident(id) => write!(f, "{}", interner.resolve(id).unwrap()),
+            Case::Lit(lit) => lit.recover(f, interner),
+            Case::Neg(lit) => {
+                write!(f, "-")?;
+                lit.recover(f, interner)
+            }
+            Case::Tuple(cases) => {
+                write!(f, "(")?;
+                let mut iter = cases.iter();
+                if let Some(first) = iter.next() {
+                    first.recover(f, interner)?;
+                }
+                for case in iter {
+                    write!(f, ", ")?;
+                    case.recover(f, interner)?;
+                }
+                write!(f, ")")
+            }
+            Case::Group(_, path, cases) => {
+                let mut iter = path.iter();
+                if let Some(first) = iter.next() {
+                    write!(f, "{}", interner.resolve(first).unwrap())?;
+                }
+                for space in iter {
+                    write!(f, "::{}", interner.resolve(space).unwrap())?;
+                }
+                write!(f, "(")?;
+                let mut iter = cases.iter();
+                if let Some(first) = iter.next() {
+                    first.recover(f, interner)?;
+                }
+                for case in iter {
+                    write!(f, ", ")?;
+                    case.recover(f, interner)?;
+                }
+                write!(f, ")")
+            }
+            Case::List(cases, rest) => {
+                write!(f, "[")?;
+                for (i, case) in cases.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    case.recover(f, interner)?;
+                }
+                if let Some(rest) = rest {
+                    if !cases.is_empty() {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "..")?;
+                    if let Some(id) = rest {
+                        write!(f, "{}", interner.resolve(id).unwrap())?;
+                    }
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
 impl Expr {
     pub fn recover<W: Write>(
         &self,
@@ -163,8 +243,8 @@ impl Expr {
         interner: &Interner,
     ) -> std::fmt::Result {
         match self {
-            Expr::Block(block) => block.recover(f, start, indent, None, interner),
-            Expr::Break(expr) => {
+            Expr::Block(_, block) => block.recover(f, start, indent, None, interner),
+            Expr::Break(_, expr) => {
                 write!(f, "break")?;
                 if let Some(expr) = expr {
                     write!(f, " ")?;
@@ -172,8 +252,8 @@ impl Expr {
                 }
                 Ok(())
             }
-            Expr::Continue => write!(f, "continue"),
-            Expr::For(pat, expr, block) => {
+            Expr::Continue(_) => write!(f, "continue"),
+            Expr::For(_, pat, expr, block) => {
                 write!(f, "for ")?;
                 pat.recover(f, interner)?;
                 write!(f, " in ")?;
@@ -181,7 +261,7 @@ impl Expr {
                 write!(f, " ")?;
                 block.recover(f, start, indent, None, interner)
             }
-            Expr::If(expr, then, otherwise) => {
+            Expr::If(_, expr, then, otherwise) => {
                 write!(f, "if ")?;
                 expr.recover(f, start, indent, interner)?;
                 write!(f, " ")?;
@@ -192,26 +272,57 @@ impl Expr {
                 }
                 Ok(())
             }
-            Expr::Loop(block) => {
+            Expr::Loop(_, block) => {
                 write!(f, "loop ")?;
                 block.recover(f, start, indent, None, interner)
             }
-            Expr::Return(expr) => {
+            Expr::Match(_, expr, arms) => {
+                write!(f, "match ")?;
+                expr.recover(f, start, indent, interner)?;
+                writeln!(f, " {{")?;
+                for Arm(_, case, guard, expr) in arms.iter() {
+                    write!(f, "{start}{}", "    ".repeat(indent + 1))?;
+                    case.recover(f, interner)?;
+                    if let Some(guard) = guard {
+                        write!(f, " if ")?;
+                        guard.recover(f, start, indent + 1, interner)?;
+                    }
+                    write!(f, " => ")?;
+                    expr.recover(f, start, indent + 1, interner)?;
+                    writeln!(f, ",")?;
+                }
+                write!(f, "{start}{}}}", "    ".repeat(indent))
+            }
+            Expr::Return(_, expr) => {
                 write!(f, "return ")?;
                 expr.recover(f, start, indent, interner)
             }
-            Expr::While(expr, block) => {
+            Expr::While(_, expr, block) => {
                 write!(f, "while ")?;
                 expr.recover(f, start, indent, interner)?;
                 write!(f, " ")?;
                 block.recover(f, start, indent, None, interner)
             }
-            Expr::Binary(lhs, op, rhs) => {
+            Expr::Closure(_, params, expr) => {
+                write!(f, "|")?;
+                if let Some(params) = params {
+                    let mut iter = params.iter();
+                    if let Some(first) = iter.next() {
+                        write!(f, "{}", interner.resolve(first).unwrap())?;
+                    }
+                    for param in iter {
+                        write!(f, ", {}", interner.resolve(param).unwrap())?;
+                    }
+                }
+                write!(f, "| ")?;
+                expr.recover(f, start, indent, interner)
+            }
+            Expr::Binary(_, lhs, op, rhs) => {
                 lhs.recover(f, start, indent, interner)?;
                 write!(f, " {op} ")?;
                 rhs.recover(f, start, indent, interner)
             }
-            Expr::Call(expr, args) => {
+            Expr::Call(_, expr, args) => {
                 expr.recover(f, start, indent, interner)?;
                 write!(f, "(")?;
                 if let Some(args) = args {
@@ -226,11 +337,11 @@ impl Expr {
                 }
                 write!(f, ")")
             }
-            Expr::Field(expr, id) => {
+            Expr::Field(_, expr, id) => {
                 expr.recover(f, start, indent, interner)?;
                 write!(f, ".{}", interner.resolve(id).unwrap())
             }
-            Expr::Method(expr, id, args) => {
+            Expr::Method(_, expr, id, args) => {
                 expr.recover(f, start, indent, interner)?;
                 write!(f, ".{}", interner.resolve(id).unwrap())?;
                 write!(f, "(")?;
@@ -246,13 +357,29 @@ impl Expr {
                 }
                 write!(f, ")")
             }
-            Expr::Index(expr, index) => {
+            Expr::With(_, expr, updates) => {
+                expr.recover(f, start, indent, interner)?;
+                write!(f, ".with(")?;
+                for (i, (id, value)) in updates.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{} = ", interner.resolve(id).unwrap())?;
+                    value.recover(f, start, indent, interner)?;
+                }
+                write!(f, ")")
+            }
+            Expr::Try(_, expr) => {
+                expr.recover(f, start, indent, interner)?;
+                write!(f, "?")
+            }
+            Expr::Index(_, expr, index) => {
                 expr.recover(f, start, indent, interner)?;
                 write!(f, "[")?;
                 index.recover(f, start, indent, interner)?;
                 write!(f, "]")
             }
-            Expr::Tuple(args) => {
+            Expr::Tuple(_, args) => {
                 write!(f, "(")?;
                 let mut iter = args.iter();
                 if let Some(first) = iter.next() {
@@ -264,7 +391,7 @@ impl Expr {
                 }
                 write!(f, ")")
             }
-            Expr::List(args) => {
+            Expr::List(_, args) => {
                 write!(f, "[")?;
                 if let Some(args) = args {
                     let mut iter = args.iter();
@@ -278,13 +405,42 @@ impl Expr {
                 }
                 write!(f, "]")
             }
-            Expr::Lit(lit) => lit.recover(f, interner),
-            Expr::Paren(expr) => {
+            Expr::Map(_, entries) => {
+                let Some(entries) = entries else {
+                    return write!(f, "{{:}}");
+                };
+                write!(f, "{{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    key.recover(f, start, indent, interner)?;
+                    write!(f, ": ")?;
+                    value.recover(f, start, indent, interner)?;
+                }
+                write!(f, "}}")
+            }
+            Expr::Lit(_, lit) => lit.recover(f, interner),
+            Expr::Format(_, segments) => {
+                write!(f, "f\"")?;
+                for segment in segments {
+                    match segment {
+                        Segment::Chunk(chunk) => chunk.recover(f, interner)?,
+                        Segment::Expr(expr) => {
+                            write!(f, "{{")?;
+                            expr.recover(f, start, indent, interner)?;
+                            write!(f, "}}")?;
+                        }
+                    }
+                }
+                write!(f, "\"")
+            }
+            Expr::Paren(_, expr) => {
                 write!(f, "(")?;
                 expr.recover(f, start, indent, interner)?;
                 write!(f, ")")
             }
-            Expr::Unary(op, expr) => {
+            Expr::Unary(_, op, expr) => {
                 write!(f, "{op}")?;
                 expr.recover(f, start, indent, interner)
             }
@@ -306,6 +462,8 @@ impl Lit {
     pub fn recover<W: Write>(&self, f: &mut W, interner: &Interner) -> std::fmt::Result {
         match self {
             Lit::Int(x) | Lit::Float(x) => write!(f, "{}", interner.resolve(x).unwrap()),
+            Lit::I64(x) => write!(f, "{}i64", interner.resolve(x).unwrap()),
+            Lit::F64(x) => write!(f, "{}f64", interner.resolve(x).unwrap()),
             Lit::Bool(x) => match x {
                 Bool::True => write!(f, "true"),
                 Bool::False => write!(f, "false"),
@@ -372,6 +530,7 @@ impl Display for UnaOp {
             UnaOp::Not => write!(f, "not "),
             UnaOp::Pos => write!(f, "+"),
             UnaOp::Neg => write!(f, "-"),
+            UnaOp::Fmt => write!(f, "fmt "),
         }
     }
 }
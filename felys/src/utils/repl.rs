@@ -0,0 +1,234 @@
+use crate::elysia::runtime::io::Io;
+use crate::elysia::runtime::object::Object;
+use crate::philia093::PhiLia093;
+use crate::utils::ast::{AssOp, BufVec, Expr, Item, Lit, Pat, Root, Stmt};
+use crate::utils::stages::III;
+use crate::utils::stdlib::registry::Registry;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const MODULE: &str = "<repl>";
+
+const COMMIT: &str = "commit";
+
+pub struct Repl {
+    registry: Registry,
+    limit: usize,
+    items: String,
+    bindings: Vec<(String, Object)>,
+    committed: Rc<RefCell<Option<Vec<Object>>>>,
+}
+
+impl Repl {
+    pub fn new(registry: Registry, limit: usize) -> Self {
+        let committed = Rc::new(RefCell::new(None));
+        let slot = committed.clone();
+        let registry = registry.register(MODULE, COMMIT, move |mut args, _| {
+            let value = args.remove(0);
+            slot.replace(Some(args));
+            Ok(value)
+        });
+        Self {
+            registry,
+            limit,
+            items: String::new(),
+            bindings: Vec::new(),
+            committed,
+        }
+    }
+
+    pub fn eval(&mut self, input: &str, io: &mut Io) -> Result<Option<Object>, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(None);
+        }
+
+        if ["group", "fn", "impl", "use"].contains(&keyword(input)) {
+            let code = format!("{}{input}\nfn main(args) {{ args }}\n", self.items);
+            PhiLia093::from(code)
+                .parse()?
+                .desugar(&self.registry)?
+                .codegen_all(self.limit)?;
+            self.items.push_str(input);
+            self.items.push('\n');
+            return Ok(None);
+        }
+
+        let wrap = |input: &str| format!("{}fn main(args) {{ {input}\n}}\n", self.items);
+        let mut code = wrap(input);
+        if PhiLia093::from(code.clone()).parse().is_err() {
+            let semi = wrap(&format!("{input};"));
+            if PhiLia093::from(semi.clone()).parse().is_ok() {
+                code = semi;
+            }
+        }
+        let (iii, names, value) = match self.compile(&code, true) {
+            Ok(compiled) => compiled,
+            Err(e) => self.compile(&code, false).map_err(|_| e)?,
+        };
+        let args = self.bindings.iter().map(|(_, obj)| obj.clone()).collect();
+        self.committed.replace(None);
+        let obj = iii.run(Object::List(args), io)?;
+
+        let Some(objs) = self.committed.take() else {
+            return Ok(Some(obj));
+        };
+        let bindings = names.into_iter().zip(objs).collect::<Vec<_>>();
+        if let Some((name, _)) = bindings.iter().find(|(_, obj)| closure(obj)) {
+            return Err(format!(
+                "Elysia: `{name}` cannot keep a closure across inputs\n"
+            ));
+        }
+        self.bindings = bindings;
+        Ok(value.then_some(obj))
+    }
+
+    pub fn balanced(input: &str) -> bool {
+        let mut chars = input.chars().peekable();
+        let mut depth = 0;
+        let mut string = None;
+        let mut holes = Vec::new();
+        let mut word = false;
+        while let Some(c) = chars.next() {
+            match (string, c) {
+                (Some(_), '\\') if chars.next() == Some('u') => {
+                    chars.by_ref().find(|x| *x == '}');
+                }
+                (Some(_), '"') => string = None,
+                (Some(true), '{') => {
+                    holes.push(depth);
+                    string = None;
+                }
+                (Some(_), _) => {}
+                (None, '"') => string = Some(false),
+                (None, 'f') if !word && chars.peek() == Some(&'"') => {
+                    chars.next();
+                    string = Some(true);
+                }
+                (None, '/') if chars.peek() == Some(&'/') => {
+                    chars.by_ref().find(|x| *x == '\n');
+                }
+                (None, '}') if holes.last() == Some(&depth) => {
+                    holes.pop();
+                    string = Some(true);
+                }
+                (None, '(' | '[' | '{') => depth += 1,
+                (None, ')' | ']' | '}') => depth -= 1,
+                _ => {}
+            }
+            word = string.is_none() && (c.is_alphanumeric() || c == '_');
+        }
+        string.is_none() && holes.is_empty() && depth <= 0
+    }
+
+    fn compile(&self, code: &str, capture: bool) -> Result<(III, Vec<String>, bool), String> {
+        let mut i = PhiLia093::from(code.to_string()).parse()?;
+        let mut items = i.root.0.vec();
+        let Some(Item::Main(args, block)) = items.last_mut() else {
+            unreachable!()
+        };
+        *args = i.interner.intern("<bindings>");
+
+        let mut cursor = i.source.len();
+        let mut span = || {
+            cursor += 1;
+            cursor
+        };
+        let path = |span, id| Expr::Path(span, BufVec::new([id], vec![]));
+
+        let mut body = Vec::new();
+        for (idx, (name, _)) in self.bindings.iter().enumerate() {
+            let lit = Lit::Int(i.interner.intern(&idx.to_string()));
+            let index = Expr::Index(
+                span(),
+                Rc::new(path(span(), *args)),
+                Rc::new(Expr::Lit(span(), lit)),
+            );
+            let pat = Pat::Ident(i.interner.intern(name));
+            body.push(Stmt::Assign(span(), pat, AssOp::Eq, index));
+        }
+
+        let mut stmts = std::mem::take(&mut block.0);
+        let value = match stmts.pop() {
+            Some(Stmt::Expr(expr)) if capture => Some(expr),
+            Some(Stmt::Expr(expr)) => {
+                stmts.push(Stmt::Semi(expr));
+                None
+            }
+            Some(stmt) => {
+                stmts.push(stmt);
+                None
+            }
+            None => None,
+        };
+
+        let mut names = self
+            .bindings
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        for stmt in stmts.iter() {
+            if let Stmt::Assign(_, pat, _, _) = stmt {
+                pat.bind(&mut names, &|id| {
+                    i.interner.resolve(&id).unwrap().to_string()
+                });
+            }
+        }
+        body.extend(stmts);
+
+        let captured = value.is_some();
+        let first = value.unwrap_or_else(|| path(span(), *args));
+        let rest = names
+            .iter()
+            .map(|name| path(span(), i.interner.intern(name)))
+            .collect();
+        let commit = BufVec::new([i.interner.intern(MODULE)], vec![i.interner.intern(COMMIT)]);
+        let callee = Rc::new(Expr::Path(span(), commit));
+        let call = Expr::Call(span(), callee, Some(BufVec::new([first], rest)));
+        body.push(Stmt::Expr(call));
+        block.0 = body;
+
+        let first = items.remove(0);
+        i.root = Root(BufVec::new([first], items));
+        let iii = i.desugar(&self.registry)?.codegen_all(self.limit)?;
+        Ok((iii, names, captured))
+    }
+}
+
+impl Pat {
+    fn bind(&self, names: &mut Vec<String>, resolve: &dyn Fn(usize) -> String) {
+        match self {
+            Pat::Any => {}
+            Pat::Tuple(pats) => {
+                for pat in pats.iter() {
+                    pat.bind(names, resolve);
+                }
+            }
+            Pat::Ident(id) => {
+                let name = resolve(*id);
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+    }
+}
+
+fn closure(obj: &Object) -> bool {
+    match obj {
+        Object::Closure(_, _) => true,
+        Object::List(objs) | Object::Tuple(objs) | Object::Group(_, objs) => {
+            objs.iter().any(closure)
+        }
+        Object::Map(map) => map.iter().any(|(_, obj)| closure(obj)),
+        Object::Err(obj) => closure(obj),
+        _ => false,
+    }
+}
+
+fn keyword(input: &str) -> &str {
+    let end = input
+        .find(|c: char| !c.is_alphanumeric() && c != '_')
+        .unwrap_or(input.len());
+    &input[..end]
+}
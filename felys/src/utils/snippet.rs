@@ -0,0 +1,53 @@
+use std::fmt::{Display, Formatter};
+
+pub struct Snippet {
+    pub line: String,
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Snippet {
+    pub fn new(data: &str, cursor: usize) -> Self {
+        let row = data[..cursor].chars().filter(|c| *c == '\n').count();
+        let mut col = 0;
+        let mut start = cursor;
+        for ch in data[..cursor].chars().rev() {
+            if ch == '\n' {
+                break;
+            }
+            start -= ch.len_utf8();
+            col += 1;
+        }
+        let mut end = cursor;
+        for ch in data[cursor..].chars() {
+            if ch == '\n' {
+                break;
+            }
+            end += ch.len_utf8();
+        }
+
+        Self {
+            line: data[start..end].to_string(),
+            row,
+            col,
+        }
+    }
+
+    pub fn locate(data: &str, cursor: usize) -> Option<Self> {
+        if cursor < data.len() && data.is_char_boundary(cursor) {
+            Some(Self::new(data, cursor))
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Snippet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let row = self.row + 1;
+        let padding = " ".repeat(row.to_string().len());
+        writeln!(f, " {} |", padding)?;
+        writeln!(f, " {} | {}", row, self.line)?;
+        writeln!(f, " {} | {}^", padding, " ".repeat(self.col))
+    }
+}
@@ -1,22 +1,30 @@
 use crate::philia093::Interner;
 use crate::utils::ast::{Block, Root};
-use crate::utils::bytecode::{Bytecode, Reg};
-use crate::utils::function::Const;
+use crate::utils::bytecode::{Bytecode, Id, Index, Reg};
+use crate::utils::function::{Const, Pointer};
 use crate::utils::group::Group;
 use crate::utils::namespace::Namespace;
+use crate::utils::stdlib::registry::{Native, Registry};
 use std::collections::HashMap;
 
 pub struct I {
     pub root: Root,
+    pub modules: Vec<(Vec<usize>, Root)>,
+    pub externs: Vec<(String, Pointer)>,
     pub interner: Interner,
+    pub source: String,
 }
 
 pub struct II {
     pub namespace: Namespace,
     pub groups: HashMap<usize, Group>,
-    pub functions: HashMap<usize, (Vec<usize>, Block)>,
-    pub main: (usize, Block),
+    pub functions: HashMap<usize, (String, usize, Vec<usize>, Block)>,
+    pub main: Option<(usize, Block)>,
+    pub exports: Vec<(String, Pointer, usize)>,
+    pub imports: Vec<(String, Pointer, usize)>,
     pub interner: Interner,
+    pub registry: Registry,
+    pub source: String,
 }
 
 pub struct III {
@@ -24,6 +32,12 @@ pub struct III {
     pub text: Vec<Callable>,
     pub data: Vec<Const>,
     pub groups: Vec<Group>,
+    pub natives: Vec<(String, Native)>,
+    pub names: Vec<String>,
+    pub symbols: Vec<(Id, String)>,
+    pub exports: Vec<(String, Pointer, Index)>,
+    pub imports: Vec<(String, Pointer, Index)>,
+    pub source: String,
 }
 
 #[derive(Debug)]
@@ -31,4 +45,5 @@ pub struct Callable {
     pub args: Reg,
     pub registers: Reg,
     pub bytecodes: Vec<Bytecode>,
+    pub lines: Vec<(Index, Index)>,
 }
@@ -120,6 +120,17 @@ impl Node {
         }
     }
 
+    pub fn rows(&self) -> Option<usize> {
+        self.tensor.shape.first().copied()
+    }
+
+    pub fn row(&self, i: usize) -> Option<Self> {
+        Some(Self {
+            tensor: self.tensor.row(i)?,
+            op: Operator::Detached,
+        })
+    }
+
     pub fn fixed(&self) -> bool {
         matches!(self.op, Operator::Detached)
     }
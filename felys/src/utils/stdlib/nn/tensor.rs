@@ -161,6 +161,18 @@ impl Tensor {
         }
     }
 
+    pub fn row(&self, i: usize) -> Option<Self> {
+        let (len, rest) = self.shape.split_first()?;
+        if i >= *len {
+            return None;
+        }
+        let stride = rest.iter().product::<usize>();
+        Some(Self {
+            data: self.data[i * stride..(i + 1) * stride].into(),
+            shape: rest.into(),
+        })
+    }
+
     pub fn fill(x: f32, shape: Rc<[usize]>) -> Self {
         let size = shape.iter().product();
         Self {
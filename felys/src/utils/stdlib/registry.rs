@@ -1,23 +1,143 @@
 use crate::Object;
+use crate::elysia::runtime::iter::Iter;
+use crate::elysia::runtime::map::{Key, Map};
+use crate::elysia::runtime::vm::Handle;
+use crate::utils::ast::BinOp;
 use crate::utils::stdlib::nn::operator::Node;
+use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::io::Write;
+use std::num::{ParseFloatError, ParseIntError};
 use std::rc::Rc;
 
 pub type Stdlib<'a> = &'a [(&'static str, &'static str, Signature)];
 
-pub type Signature = fn(Vec<Object>, &mut String) -> Result<Object, String>;
+pub type Signature = fn(Vec<Object>, &mut Handle) -> Result<Object, String>;
+
+pub type Native = Rc<dyn Fn(Vec<Object>, &mut Handle) -> Result<Object, String>>;
+
+#[derive(Clone)]
+pub struct Registry {
+    natives: Vec<(String, Native)>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        let natives = STDLIB
+            .iter()
+            .map(|(sub, inner, f)| (format!("std::{sub}::{inner}"), Rc::new(*f) as Native))
+            .collect();
+        Self { natives }
+    }
+}
+
+impl Registry {
+    pub fn register<F>(mut self, module: &str, function: &str, f: F) -> Self
+    where
+        F: Fn(Vec<Object>, &mut Handle) -> Result<Object, String> + 'static,
+    {
+        let symbol = format!("{module}::{function}");
+        let native = Rc::new(f);
+        match self.natives.iter_mut().find(|(x, _)| *x == symbol) {
+            Some((_, old)) => *old = native,
+            None => self.natives.push((symbol, native)),
+        }
+        self
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&(String, Native)> {
+        self.natives.get(idx)
+    }
+
+    pub fn find(&self, symbol: &str) -> Option<&Native> {
+        self.natives
+            .iter()
+            .find(|(x, _)| x == symbol)
+            .map(|(_, native)| native)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(String, Native)> {
+        self.natives.iter()
+    }
+}
 
 fn extract<const S: usize>(args: Vec<Object>) -> Result<[Object; S], String> {
     args.try_into()
         .map_err(|_| "invalid number of args".to_string())
 }
 
+fn recoverable(result: Result<Object, String>) -> Object {
+    result.unwrap_or_else(|e| failure(&e))
+}
+
+fn failure(msg: &str) -> Object {
+    Object::Err(Object::Str(msg.into()).into())
+}
+
 pub const STDLIB: Stdlib = &[
     ("io", "print", PRINT),
+    ("io", "eprint", EPRINT),
+    ("io", "input", INPUT),
+    ("io", "readline", READLINE),
+    ("io", "flush", FLUSH),
     ("pink", "cyrene", CYRENE),
     ("pink", "elysia", ELYSIA),
     ("pink", "felysneko", FELYSNEKO),
     ("utils", "range", RANGE),
+    ("utils", "done", DONE),
+    ("err", "new", ERR_NEW),
+    ("err", "is", ERR_IS),
+    ("err", "payload", ERR_PAYLOAD),
+    ("err", "map", ERR_MAP),
+    ("err", "or_else", ERR_OR_ELSE),
+    ("str", "len", STR_LEN),
+    ("str", "chars", STR_CHARS),
+    ("str", "bytes", STR_BYTES),
+    ("str", "split", STR_SPLIT),
+    ("str", "join", STR_JOIN),
+    ("str", "find", STR_FIND),
+    ("str", "replace", STR_REPLACE),
+    ("str", "trim", STR_TRIM),
+    ("str", "starts_with", STR_STARTS_WITH),
+    ("str", "ends_with", STR_ENDS_WITH),
+    ("str", "upper", STR_UPPER),
+    ("str", "lower", STR_LOWER),
+    ("str", "int", CONV_INT),
+    ("str", "float", CONV_FLOAT),
+    ("str", "to_string", STR_TO_STRING),
+    ("conv", "int", CONV_INT),
+    ("conv", "float", CONV_FLOAT),
+    ("conv", "i64", CONV_I64),
+    ("conv", "f64", CONV_F64),
+    ("conv", "str", STR_TO_STRING),
+    ("conv", "bool", CONV_BOOL),
+    ("list", "len", LIST_LEN),
+    ("list", "push", LIST_PUSH),
+    ("list", "concat", LIST_CONCAT),
+    ("list", "slice", LIST_SLICE),
+    ("list", "reverse", LIST_REVERSE),
+    ("list", "sort", LIST_SORT),
+    ("list", "map", LIST_MAP),
+    ("list", "filter", LIST_FILTER),
+    ("list", "fold", LIST_FOLD),
+    ("list", "zip", LIST_ZIP),
+    ("list", "enumerate", LIST_ENUMERATE),
+    ("list", "contains", LIST_CONTAINS),
+    ("list", "index_of", LIST_INDEX_OF),
+    ("list", "collect", LIST_COLLECT),
+    ("map", "get", MAP_GET),
+    ("map", "insert", MAP_INSERT),
+    ("map", "remove", MAP_REMOVE),
+    ("map", "keys", MAP_KEYS),
+    ("map", "values", MAP_VALUES),
+    ("map", "contains", MAP_CONTAINS),
+    ("map", "len", MAP_LEN),
+    ("map", "update", MAP_UPDATE),
+    ("map", "filter", MAP_FILTER),
+    ("reflect", "name", REFLECT_NAME),
+    ("reflect", "fields", REFLECT_FIELDS),
+    ("reflect", "values", REFLECT_VALUES),
     ("nn", "tensor", TENSOR),
     ("nn", "relu", RELU),
     ("nn", "ln", LN),
@@ -27,19 +147,55 @@ pub const STDLIB: Stdlib = &[
     ("nn", "init", INIT),
     ("nn", "attach", ATTACH),
     ("nn", "backward", BACKWARD),
+    ("nn", "grad", GRAD),
 ];
 
-const PRINT: Signature = |args, stdout| {
-    let mut iter = args.iter();
-    if let Some(arg) = iter.next() {
-        stdout.push_str(&arg.to_string());
+fn write(args: &[Object], buf: &mut dyn Write) -> Result<Object, String> {
+    let mut line = args
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    line.push('\n');
+    buf.write_all(line.as_bytes()).map_err(|e| e.to_string())?;
+    Ok(Object::Int(args.len() as i32))
+}
+
+const PRINT: Signature = |args, vm| write(&args, vm.io.stdout);
+
+const EPRINT: Signature = |args, vm| write(&args, vm.io.stderr);
+
+const INPUT: Signature = |args, vm| {
+    if args.len() > 1 {
+        return Err("invalid number of args".to_string());
     }
-    for arg in iter {
-        stdout.push(' ');
-        stdout.push_str(&arg.to_string());
+    if let Some(prompt) = args.first() {
+        write!(vm.io.stdout, "{prompt}").map_err(|e| e.to_string())?;
+        vm.io.stdout.flush().map_err(|e| e.to_string())?;
     }
-    stdout.push('\n');
-    Ok(Object::Int(args.len() as i32))
+    let mut line = String::new();
+    match vm.io.stdin.read_line(&mut line) {
+        Ok(0) => return Ok(failure("unexpected end of input")),
+        Ok(_) => {}
+        Err(e) => return Ok(failure(&e.to_string())),
+    }
+    let trimmed = line.strip_suffix('\n').unwrap_or(&line);
+    let trimmed = trimmed.strip_suffix('\r').unwrap_or(trimmed);
+    Ok(Object::Str(trimmed.into()))
+};
+
+const READLINE: Signature = |args, vm| {
+    let [] = extract(args)?;
+    let mut line = String::new();
+    let result = vm.io.stdin.read_line(&mut line).map_err(|e| e.to_string());
+    Ok(recoverable(result.map(|_| Object::Str(line.into()))))
+};
+
+const FLUSH: Signature = |args, vm| {
+    let [] = extract(args)?;
+    vm.io.stdout.flush().map_err(|e| e.to_string())?;
+    vm.io.stderr.flush().map_err(|e| e.to_string())?;
+    Ok(Object::Bool(true))
 };
 
 const CYRENE: Signature = |_, _| Ok(Object::Str("往昔的涟漪♪".into()));
@@ -50,14 +206,441 @@ const FELYSNEKO: Signature = |_, _| Ok(Object::Str("银河猫猫侠♪".into()))
 
 const RANGE: Signature = |args, _| {
     let [start, end] = extract(args)?;
-    let range = (start.int()?..end.int()?).map(Object::Int).collect();
-    Ok(Object::List(range))
+    let range = Iter::Range(start.int()?, end.int()?);
+    Ok(Object::Iter(Rc::new(RefCell::new(range))))
+};
+
+const DONE: Signature = |_, _| Ok(Object::Iter(Rc::new(RefCell::new(Iter::Done))));
+
+const ERR_NEW: Signature = |args, _| {
+    let [payload] = extract(args)?;
+    Ok(Object::Err(payload.into()))
+};
+
+const ERR_IS: Signature = |args, _| {
+    let [object] = extract(args)?;
+    Ok(Object::Bool(matches!(object, Object::Err(_))))
+};
+
+const ERR_PAYLOAD: Signature = |args, _| {
+    let [object] = extract(args)?;
+    let payload = object.err()?;
+    Ok(Object::clone(&payload))
+};
+
+const ERR_MAP: Signature = |args, vm| {
+    let [object, f] = extract(args)?;
+    match object {
+        Object::Err(_) => Ok(object),
+        _ => vm.call(&f, vec![object]),
+    }
+};
+
+const ERR_OR_ELSE: Signature = |args, vm| {
+    let [object, f] = extract(args)?;
+    match object {
+        Object::Err(payload) => vm.call(&f, vec![Object::clone(&payload)]),
+        _ => Ok(object),
+    }
+};
+
+const STR_LEN: Signature = |args, _| {
+    let [s] = extract(args)?;
+    Ok(Object::Int(s.str()?.chars().count() as i32))
+};
+
+const STR_CHARS: Signature = |args, _| {
+    let [s] = extract(args)?;
+    let chars = s.str()?.chars().map(|x| Object::Str(x.to_string().into()));
+    Ok(Object::List(chars.collect()))
+};
+
+const STR_BYTES: Signature = |args, _| {
+    let [s] = extract(args)?;
+    let bytes = s.str()?.bytes().map(|x| Object::Int(x as i32));
+    Ok(Object::List(bytes.collect()))
+};
+
+const STR_SPLIT: Signature = |args, _| {
+    let [s, sep] = extract(args)?;
+    let sep = sep.str()?;
+    if sep.is_empty() {
+        return Ok(failure("empty separator"));
+    }
+    let parts = s.str()?.split(sep).map(|x| Object::Str(x.into()));
+    Ok(Object::List(parts.collect()))
+};
+
+const STR_JOIN: Signature = |args, _| {
+    let [list, sep] = extract(args)?;
+    let parts = list
+        .list()?
+        .iter()
+        .map(|x| x.str().map(str::to_string))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Object::Str(parts.join(sep.str()?).into()))
+};
+
+const STR_FIND: Signature = |args, _| {
+    let [s, pattern] = extract(args)?;
+    let s = s.str()?;
+    let index = match s.find(pattern.str()?) {
+        Some(x) => s[..x].chars().count() as i32,
+        None => -1,
+    };
+    Ok(Object::Int(index))
+};
+
+const STR_REPLACE: Signature = |args, _| {
+    let [s, from, to] = extract(args)?;
+    let from = from.str()?;
+    if from.is_empty() {
+        return Ok(failure("empty pattern"));
+    }
+    Ok(Object::Str(s.str()?.replace(from, to.str()?).into()))
+};
+
+const STR_TRIM: Signature = |args, _| {
+    let [s] = extract(args)?;
+    Ok(Object::Str(s.str()?.trim().into()))
+};
+
+const STR_STARTS_WITH: Signature = |args, _| {
+    let [s, prefix] = extract(args)?;
+    Ok(Object::Bool(s.str()?.starts_with(prefix.str()?)))
+};
+
+const STR_ENDS_WITH: Signature = |args, _| {
+    let [s, suffix] = extract(args)?;
+    Ok(Object::Bool(s.str()?.ends_with(suffix.str()?)))
+};
+
+const STR_UPPER: Signature = |args, _| {
+    let [s] = extract(args)?;
+    Ok(Object::Str(s.str()?.to_uppercase().into()))
+};
+
+const STR_LOWER: Signature = |args, _| {
+    let [s] = extract(args)?;
+    Ok(Object::Str(s.str()?.to_lowercase().into()))
+};
+
+const STR_TO_STRING: Signature = |args, _| {
+    let [object] = extract(args)?;
+    Ok(Object::Str(object.to_string().into()))
+};
+
+fn integral(object: &Object, ty: &str) -> Result<Option<i128>, String> {
+    let value = match object {
+        Object::Int(x) => *x as i128,
+        Object::I64(x) => *x as i128,
+        Object::Bool(x) => *x as i128,
+        Object::Float(x) if x.is_finite() => x.trunc() as i128,
+        Object::F64(x) if x.is_finite() => x.trunc() as i128,
+        Object::Float(_) | Object::F64(_) => return Ok(None),
+        _ => return Err(format!("cannot convert `{object}` to `{ty}`")),
+    };
+    Ok(Some(value))
+}
+
+fn fractional(object: &Object, ty: &str) -> Result<f64, String> {
+    match object {
+        Object::Int(x) => Ok(*x as f64),
+        Object::I64(x) => Ok(*x as f64),
+        Object::Bool(x) => Ok(*x as u8 as f64),
+        Object::Float(x) => Ok(*x as f64),
+        Object::F64(x) => Ok(*x),
+        _ => Err(format!("cannot convert `{object}` to `{ty}`")),
+    }
+}
+
+const CONV_INT: Signature = |args, _| {
+    let [object] = extract(args)?;
+    let int = match &object {
+        Object::Str(x) => x.trim().parse().map_err(|e: ParseIntError| e.to_string()),
+        other => integral(other, "int")?
+            .and_then(|x| x.try_into().ok())
+            .ok_or(format!("`{other}` is out of range for `int`")),
+    };
+    Ok(recoverable(int.map(Object::Int)))
+};
+
+const CONV_FLOAT: Signature = |args, _| {
+    let [object] = extract(args)?;
+    let float = match &object {
+        Object::Str(x) => x.trim().parse().map_err(|e: ParseFloatError| e.to_string()),
+        other => Ok(fractional(other, "float")? as f32),
+    };
+    Ok(recoverable(float.map(Object::Float)))
+};
+
+const CONV_I64: Signature = |args, _| {
+    let [object] = extract(args)?;
+    let int = match &object {
+        Object::Str(x) => x.trim().parse().map_err(|e: ParseIntError| e.to_string()),
+        other => integral(other, "i64")?
+            .and_then(|x| x.try_into().ok())
+            .ok_or(format!("`{other}` is out of range for `i64`")),
+    };
+    Ok(recoverable(int.map(Object::I64)))
+};
+
+const CONV_F64: Signature = |args, _| {
+    let [object] = extract(args)?;
+    let float = match &object {
+        Object::Str(x) => x.trim().parse().map_err(|e: ParseFloatError| e.to_string()),
+        other => fractional(other, "f64"),
+    };
+    Ok(recoverable(float.map(Object::F64)))
+};
+
+const CONV_BOOL: Signature = |args, _| {
+    let [object] = extract(args)?;
+    let value = match &object {
+        Object::Bool(x) => *x,
+        Object::Int(x) => *x != 0,
+        Object::I64(x) => *x != 0,
+        Object::Float(x) => *x != 0.0,
+        Object::F64(x) => *x != 0.0,
+        Object::Str(x) => !x.is_empty(),
+        Object::List(x) | Object::Tuple(x) => !x.is_empty(),
+        Object::Map(x) => !x.is_empty(),
+        other => return Err(format!("cannot convert `{other}` to `bool`")),
+    };
+    Ok(Object::Bool(value))
+};
+
+fn position(list: &[Object], object: &Object) -> Option<usize> {
+    list.iter().position(|x| {
+        x.binary(BinOp::Eq, object)
+            .and_then(|x| x.bool())
+            .unwrap_or(false)
+    })
+}
+
+fn compare(lhs: &Object, rhs: &Object) -> Result<Ordering, String> {
+    if lhs.binary(BinOp::Lt, rhs)?.bool()? {
+        Ok(Ordering::Less)
+    } else if rhs.binary(BinOp::Lt, lhs)?.bool()? {
+        Ok(Ordering::Greater)
+    } else {
+        Ok(Ordering::Equal)
+    }
+}
+
+const LIST_LEN: Signature = |args, _| {
+    let [list] = extract(args)?;
+    Ok(Object::Int(list.list()?.len() as i32))
+};
+
+const LIST_PUSH: Signature = |args, _| {
+    let [list, object] = extract(args)?;
+    let list = list.list()?.iter().cloned().chain([object]).collect();
+    Ok(Object::List(list))
+};
+
+const LIST_CONCAT: Signature = |args, _| {
+    let [lhs, rhs] = extract(args)?;
+    let list = lhs
+        .list()?
+        .iter()
+        .chain(rhs.list()?.iter())
+        .cloned()
+        .collect();
+    Ok(Object::List(list))
+};
+
+const LIST_SLICE: Signature = |args, _| {
+    let [list, start, end] = extract(args)?;
+    let objs = list.list()?;
+    let bound = |int: i32| {
+        let idx = if int >= 0 {
+            Some(int as usize)
+        } else {
+            objs.len().checked_sub(int.unsigned_abs() as usize)
+        };
+        idx.filter(|x| *x <= objs.len())
+            .ok_or(format!("index {int} is out of boundaries for `{list}`"))
+    };
+    let (lo, hi) = (bound(start.int()?)?, bound(end.int()?)?);
+    if lo > hi {
+        return Err(format!("index {end} is out of boundaries for `{list}`"));
+    }
+    Ok(Object::List(objs[lo..hi].into()))
+};
+
+const LIST_REVERSE: Signature = |args, _| {
+    let [list] = extract(args)?;
+    Ok(Object::List(list.list()?.iter().rev().cloned().collect()))
+};
+
+const LIST_SORT: Signature = |args, vm| {
+    let [list, key] = extract(args)?;
+    let mut pairs = Vec::new();
+    for x in list.list()?.iter() {
+        pairs.push((vm.call(&key, vec![x.clone()])?, x.clone()));
+    }
+    let mut error = None;
+    pairs.sort_by(|(x, _), (y, _)| {
+        compare(x, y).unwrap_or_else(|e| {
+            error.get_or_insert(e);
+            Ordering::Equal
+        })
+    });
+    if let Some(e) = error {
+        return Err(e);
+    }
+    Ok(Object::List(pairs.into_iter().map(|(_, x)| x).collect()))
+};
+
+const LIST_MAP: Signature = |args, vm| {
+    let [list, f] = extract(args)?;
+    let mut objs = Vec::new();
+    for x in list.list()?.iter() {
+        objs.push(vm.call(&f, vec![x.clone()])?);
+    }
+    Ok(Object::List(objs.into()))
+};
+
+const LIST_FILTER: Signature = |args, vm| {
+    let [list, f] = extract(args)?;
+    let mut objs = Vec::new();
+    for x in list.list()?.iter() {
+        if vm.call(&f, vec![x.clone()])?.bool()? {
+            objs.push(x.clone());
+        }
+    }
+    Ok(Object::List(objs.into()))
+};
+
+const LIST_FOLD: Signature = |args, vm| {
+    let [list, init, f] = extract(args)?;
+    let mut acc = init;
+    for x in list.list()?.iter() {
+        acc = vm.call(&f, vec![acc, x.clone()])?;
+    }
+    Ok(acc)
+};
+
+const LIST_ZIP: Signature = |args, _| {
+    let [lhs, rhs] = extract(args)?;
+    let pairs = lhs
+        .list()?
+        .iter()
+        .zip(rhs.list()?.iter())
+        .map(|(x, y)| Object::Tuple([x.clone(), y.clone()].into()))
+        .collect();
+    Ok(Object::List(pairs))
+};
+
+const LIST_ENUMERATE: Signature = |args, _| {
+    let [list] = extract(args)?;
+    let pairs = list
+        .list()?
+        .iter()
+        .enumerate()
+        .map(|(i, x)| Object::Tuple([Object::Int(i as i32), x.clone()].into()))
+        .collect();
+    Ok(Object::List(pairs))
+};
+
+const LIST_CONTAINS: Signature = |args, _| {
+    let [list, object] = extract(args)?;
+    Ok(Object::Bool(position(&list.list()?, &object).is_some()))
+};
+
+const LIST_INDEX_OF: Signature = |args, _| {
+    let [list, object] = extract(args)?;
+    let index = position(&list.list()?, &object).map_or(-1, |x| x as i32);
+    Ok(Object::Int(index))
+};
+
+const LIST_COLLECT: Signature = |args, vm| {
+    let [object] = extract(args)?;
+    Ok(Object::List(vm.collect(object)?.into()))
+};
+
+const MAP_GET: Signature = |args, _| {
+    let [map, key, default] = extract(args)?;
+    let value = map.map()?.get(&Key::try_from(&key)?).cloned();
+    Ok(value.unwrap_or(default))
+};
+
+const MAP_INSERT: Signature = |args, _| {
+    let [map, key, value] = extract(args)?;
+    Ok(Object::Map(map.map()?.insert(Key::try_from(&key)?, value)))
+};
+
+const MAP_REMOVE: Signature = |args, _| {
+    let [map, key] = extract(args)?;
+    Ok(Object::Map(map.map()?.remove(&Key::try_from(&key)?)))
+};
+
+const MAP_KEYS: Signature = |args, _| {
+    let [map] = extract(args)?;
+    let keys = map.map()?.iter().map(|(key, _)| key.into()).collect();
+    Ok(Object::List(keys))
+};
+
+const MAP_VALUES: Signature = |args, _| {
+    let [map] = extract(args)?;
+    let values = map.map()?.iter().map(|(_, value)| value.clone()).collect();
+    Ok(Object::List(values))
+};
+
+const MAP_CONTAINS: Signature = |args, _| {
+    let [map, key] = extract(args)?;
+    let contains = map.map()?.get(&Key::try_from(&key)?).is_some();
+    Ok(Object::Bool(contains))
+};
+
+const MAP_LEN: Signature = |args, _| {
+    let [map] = extract(args)?;
+    Ok(Object::Int(map.map()?.len() as i32))
+};
+
+const MAP_UPDATE: Signature = |args, vm| {
+    let [map, key, default, f] = extract(args)?;
+    let (map, key) = (map.map()?, Key::try_from(&key)?);
+    let old = map.get(&key).cloned().unwrap_or(default);
+    let new = vm.call(&f, vec![old])?;
+    Ok(Object::Map(map.insert(key, new)))
+};
+
+const MAP_FILTER: Signature = |args, vm| {
+    let [map, f] = extract(args)?;
+    let mut filtered = Map::default();
+    for (key, value) in map.map()?.iter() {
+        if vm.call(&f, vec![key.into(), value.clone()])?.bool()? {
+            filtered = filtered.insert(key.clone(), value.clone());
+        }
+    }
+    Ok(Object::Map(filtered))
+};
+
+const REFLECT_NAME: Signature = |args, vm| {
+    let [group] = extract(args)?;
+    Ok(Object::Str(vm.name(&group)?.into()))
+};
+
+const REFLECT_FIELDS: Signature = |args, vm| {
+    let [group] = extract(args)?;
+    let fields = vm.fields(&group)?.into_iter();
+    Ok(Object::List(
+        fields.map(|x| Object::Str(x.into())).collect(),
+    ))
+};
+
+const REFLECT_VALUES: Signature = |args, _| {
+    let [group] = extract(args)?;
+    let (_, values) = group.group()?;
+    Ok(Object::List(values))
 };
 
 const TENSOR: Signature = |args, _| {
     let [object] = extract(args)?;
-    let node = Node::try_from(object)?;
-    Ok(Object::Node(node.into()))
+    let node = Node::try_from(object).map(|x| Object::Node(x.into()));
+    Ok(recoverable(node))
 };
 
 const RELU: Signature = |args, _| {
@@ -82,30 +665,28 @@ const SUM: Signature = |args, _| {
     let [object, axes, keepdim] = extract(args)?;
     let mut indices = Vec::new();
     for x in axes.list()?.iter() {
-        let int = x
-            .int()?
-            .try_into()
-            .map_err(|_| "invalid axis".to_string())?;
-        indices.push(int);
+        match x.int()?.try_into() {
+            Ok(int) => indices.push(int),
+            Err(_) => return Ok(failure("invalid axis")),
+        }
     }
     indices.dedup();
-    let node = Node::sum(object.node()?, &indices, keepdim.bool()?)?;
-    Ok(Object::Node(node))
+    let node = Node::sum(object.node()?, &indices, keepdim.bool()?);
+    Ok(recoverable(node.map(Object::Node)))
 };
 
 const MEAN: Signature = |args, _| {
     let [object, axes, keepdim] = extract(args)?;
     let mut indices = Vec::new();
     for x in axes.list()?.iter() {
-        let int = x
-            .int()?
-            .try_into()
-            .map_err(|_| "invalid axis".to_string())?;
-        indices.push(int);
+        match x.int()?.try_into() {
+            Ok(int) => indices.push(int),
+            Err(_) => return Ok(failure("invalid axis")),
+        }
     }
     indices.dedup();
-    let node = Node::mean(object.node()?, &indices, keepdim.bool()?)?;
-    Ok(Object::Node(node))
+    let node = Node::mean(object.node()?, &indices, keepdim.bool()?);
+    Ok(recoverable(node.map(Object::Node)))
 };
 
 const INIT: Signature = |args, _| {
@@ -157,44 +738,53 @@ const INIT: Signature = |args, _| {
     ))
 };
 
-const ATTACH: Signature = |args, _| {
-    fn attacher(lhs: &Object, rhs: &Object) -> Result<Object, String> {
-        match (lhs, rhs) {
-            (Object::Int(lhs), Object::Node(rhs)) => Ok(Object::Node(rhs.attach(*lhs)?.into())),
-            (Object::Group(ln, lst), Object::Group(rn, rst)) if ln == rn => {
-                let body = lst
-                    .iter()
-                    .zip(rst.iter())
-                    .map(|(lhs, rhs)| attacher(lhs, rhs))
-                    .collect::<Result<Vec<_>, _>>()?;
-                Ok(Object::Group(*ln, body.into()))
-            }
-            _ => Err("cannot attach".to_string()),
+fn attacher(lhs: &Object, rhs: &Object) -> Result<Object, String> {
+    match (lhs, rhs) {
+        (Object::Int(lhs), Object::Node(rhs)) => Ok(Object::Node(rhs.attach(*lhs)?.into())),
+        (Object::Group(ln, lst), Object::Group(rn, rst)) if ln == rn => {
+            let body = lst
+                .iter()
+                .zip(rst.iter())
+                .map(|(lhs, rhs)| attacher(lhs, rhs))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Object::Group(*ln, body.into()))
+        }
+        _ => Err("cannot attach".to_string()),
+    }
+}
+
+fn backward(lhs: &Object, gradient: &HashMap<i32, Rc<Node>>) -> Result<Object, String> {
+    match lhs {
+        Object::Group(name, subtree) => {
+            let body = subtree
+                .iter()
+                .map(|x| backward(x, gradient))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Object::Group(*name, body.into()))
         }
+        Object::Int(x) => {
+            let grad = gradient.get(x).cloned().unwrap_or_default();
+            Ok(Object::Node(grad))
+        }
+        _ => Err("invalid nn module".to_string()),
     }
+}
 
+const ATTACH: Signature = |args, _| {
     let [lhs, rhs] = extract(args)?;
     attacher(&lhs, &rhs)
 };
 
 const BACKWARD: Signature = |args, _| {
-    fn backward(lhs: &Object, gradient: &HashMap<i32, Rc<Node>>) -> Result<Object, String> {
-        match lhs {
-            Object::Group(name, subtree) => {
-                let body = subtree
-                    .iter()
-                    .map(|x| backward(x, gradient))
-                    .collect::<Result<Vec<_>, _>>()?;
-                Ok(Object::Group(*name, body.into()))
-            }
-            Object::Int(x) => {
-                let grad = gradient.get(x).cloned().unwrap_or_default();
-                Ok(Object::Node(grad))
-            }
-            _ => Err("invalid nn module".to_string()),
-        }
-    }
     let [lhs, rhs] = extract(args)?;
     let gradient = rhs.node()?.backward()?;
     backward(&lhs, &gradient)
 };
+
+const GRAD: Signature = |args, vm| {
+    let [indices, inference, f] = extract(args)?;
+    let learnable = attacher(&indices, &inference)?;
+    let loss = vm.call(&f, vec![learnable])?;
+    let gradient = loss.node()?.backward()?;
+    Ok(Object::Tuple([loss, backward(&indices, &gradient)?].into()))
+};
@@ -1,7 +1,7 @@
 mod utils;
 
-use crate::utils::exec;
-use felys::Object;
+use crate::utils::{compile, exec, fail};
+use felys::{Key, Map, Object, Registry};
 
 #[test]
 fn object() -> Result<(), String> {
@@ -50,6 +50,30 @@ fn object() -> Result<(), String> {
         ),
         "",
     )?;
+    exec(
+        args.clone(),
+        "",
+        "{\"b\": [1], (1, true): 2, \"a\": 0, \"b\": [3],}",
+        Object::Map(
+            Map::default()
+                .insert(Key::Str("a".into()), Object::Int(0))
+                .insert(Key::Str("b".into()), Object::List([Object::Int(3)].into()))
+                .insert(
+                    Key::Tuple([Key::Int(1), Key::Bool(true)].into()),
+                    Object::Int(2),
+                ),
+        ),
+        "",
+    )?;
+    exec(args.clone(), "", "{:}", Object::Map(Map::default()), "")?;
+    exec(args.clone(), "", "n = 1; { n += 1; n }", Object::Int(2), "")?;
+    exec(
+        args.clone(),
+        "",
+        "m = {1: \"a\", true: \"b\"}; m[1] + m[true]",
+        Object::Str("ab".into()),
+        "",
+    )?;
 
     Ok(())
 }
@@ -89,6 +113,47 @@ fn logical() -> Result<(), String> {
     exec(args.clone(), "", "false or false", Object::Bool(false), "")?;
     exec(args.clone(), "", "not true", Object::Bool(false), "")?;
     exec(args.clone(), "", "not false", Object::Bool(true), "")?;
+    exec(
+        args.clone(),
+        "",
+        "xs = [0]; i = 1; i < 1 and xs[i] == 0",
+        Object::Bool(false),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "",
+        "xs = [0]; i = 1; i == 1 or xs[i] == 0",
+        Object::Bool(true),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "",
+        "false and std::io::print(1) == 1 or true or std::io::print(2) == 1",
+        Object::Bool(true),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "",
+        "x = true; x and std::io::print(3) == 1",
+        Object::Bool(true),
+        "3\n",
+    )?;
+    fail(
+        args.clone(),
+        "",
+        "x = true; x and args",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:30\n",
+            "Elysia: cannot apply `and` to `true` and `[]` at 1:30\n",
+            "   |\n",
+            " 1 |  fn main(args) { x = true; x and args }\n",
+            "   |                              ^\n",
+        ),
+    )?;
 
     Ok(())
 }
@@ -237,6 +302,109 @@ fn loops() -> Result<(), String> {
         Object::Int(6),
         "",
     )?;
+    exec(
+        args.clone(),
+        "",
+        "x = 0; for (k, v) in {3: 1, 1: 2, 2: 3} { x = x * 10 + k * v; } x",
+        Object::Int(263),
+        "",
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn iterators() -> Result<(), String> {
+    let args = Object::List([].into());
+
+    exec(
+        args.clone(),
+        "",
+        "x = 0; for i in std::utils::range(0, 100000) { if i % 2 == 0 { continue; } x += 1; } x",
+        Object::Int(50000),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "",
+        "s = \"\"; for c in \"héllo\" { if c == \"l\" { break; } s = c + s; } s",
+        Object::Str("éh".into()),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "",
+        r#"
+        for row in std::nn::tensor([[1.0, 2.0], [3.0, 4.0]]) { std::io::print(row); }
+        it = std::utils::range(0, 3);
+        for i in it { std::io::print(i); }
+        std::list::collect("ab")
+        "#,
+        Object::List([Object::Str("a".into()), Object::Str("b".into())].into()),
+        "[1.0, 2.0]::Detached\n[3.0, 4.0]::Detached\n0\n1\n2\n",
+    )?;
+    exec(
+        args.clone(),
+        "group Countdown(n); impl Countdown { fn next(self) { if self.n > 0 { (self.n, Countdown(self.n - 1)) } else { std::utils::done() } } }",
+        r#"
+        x = 0;
+        for n in Countdown(4) { x = x * 10 + n; }
+        (x, std::list::collect(Countdown(2)), std::list::collect({1: 2}))
+        "#,
+        Object::Tuple(
+            [
+                Object::Int(4321),
+                Object::List([Object::Int(2), Object::Int(1)].into()),
+                Object::List([Object::Tuple([Object::Int(1), Object::Int(2)].into())].into()),
+            ]
+            .into(),
+        ),
+        "",
+    )?;
+    fail(
+        args.clone(),
+        "group G(x);",
+        "for x in G(1) { } 0",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:29\n",
+            "Elysia: expecting `<1> as 0x00000000` to be `iterable` at 1:29\n",
+            "   |\n",
+            " 1 | group G(x); fn main(args) { for x in G(1) { } 0 }\n",
+            "   |                             ^\n",
+        ),
+    )?;
+
+    let defs = concat!(
+        "group Faulty(n); impl Faulty { fn next(self) { if self.n > 0 { (std::err::new(self.n), Faulty(self.n - 1)) } ",
+        "else if self.n == 0 { std::utils::done() } else { std::err::new(\"broken\") } } }",
+    );
+    exec(
+        args.clone(),
+        defs,
+        "std::list::collect(Faulty(2))",
+        Object::List(
+            [
+                Object::Err(Object::Int(2).into()),
+                Object::Err(Object::Int(1).into()),
+            ]
+            .into(),
+        ),
+        "",
+    )?;
+    fail(
+        args.clone(),
+        defs,
+        "for x in Faulty(-1) { } 0",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:206\n",
+            "Elysia: expecting `Err(broken)` to be `tuple` at 1:206\n",
+            "   |\n",
+            " 1 | group Faulty(n); impl Faulty { fn next(self) { if self.n > 0 { (std::err::new(self.n), Faulty(self.n - 1)) } else if self.n == 0 { std::utils::done() } else { std::err::new(\"broken\") } } } fn main(args) { for x in Faulty(-1) { } 0 }\n",
+            "   |                                                                                                                                                                                                              ^\n",
+        ),
+    )?;
 
     Ok(())
 }
@@ -274,6 +442,672 @@ fn functions() -> Result<(), String> {
         Object::Int(55),
         "",
     )?;
+    exec(
+        args.clone(),
+        "fn f(x) { if x { return 1; } return 2; [x] }",
+        "y = 0; while true { break; y = 1; } (f(true), f(false), y)",
+        Object::Tuple([Object::Int(1), Object::Int(2), Object::Int(0)].into()),
+        "",
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn tails() -> Result<(), String> {
+    let args = Object::List([].into());
+
+    exec(
+        args.clone(),
+        "fn count(n, acc) { if n == 0 { acc } else { count(n - 1, acc + 2) } }",
+        "count(2000, 0)",
+        Object::Int(4000),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "fn even(n) { if n == 0 { true } else { odd(n - 1) } } fn odd(n) { if n == 0 { false } else { even(n - 1) } }",
+        "(even(2000), odd(2001), even(7))",
+        Object::Tuple([Object::Bool(true), Object::Bool(true), Object::Bool(false)].into()),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "group Walker(step); impl Walker { fn walk(self, n, acc) { if n == 0 { acc } else { self.walk(n - 1, acc + self.step) } } }",
+        "Walker(3).walk(2000, 0)",
+        Object::Int(6000),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "fn sum(xs, acc) { match xs { [x, ..rest] => sum(rest, acc + x), _ => acc, } }",
+        "k = 2; f = |g, n| { if n == 0 { k } else { g(g, n - 1) } }; (f(f, 2000), sum(std::list::collect(std::utils::range(0, 2000)), 0))",
+        Object::Tuple([Object::Int(2), Object::Int(1999000)].into()),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "fn len(s) { std::str::len(s) } group P(x); fn make(x) { P(x) }",
+        "(len(\"abc\"), make(1).x)",
+        Object::Tuple([Object::Int(3), Object::Int(1)].into()),
+        "",
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn closures() -> Result<(), String> {
+    let args = Object::List([].into());
+
+    exec(args.clone(), "", "f = || 1; f()", Object::Int(1), "")?;
+    exec(
+        args.clone(),
+        "",
+        "n = 2; f = |x, y| x * n + y; n = 10; f(3, 4)",
+        Object::Int(10),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "fn adder(n) { |x| x + n }",
+        "(adder(1)(2), adder(10)(2))",
+        Object::Tuple([Object::Int(3), Object::Int(12)].into()),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "fn apply(f, x) { f(x) }",
+        "k = 3; apply(|x| apply(|y| x * y + k, x), 4)",
+        Object::Int(19),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "",
+        "a = 1; f = |x| |y| |z| x + y + z + a; f(2)(3)(4)",
+        Object::Int(10),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "",
+        "n = 0; f = |x| { n += x; n }; (f(5), n)",
+        Object::Tuple([Object::Int(5), Object::Int(0)].into()),
+        "",
+    )?;
+    fail(
+        args.clone(),
+        "",
+        "n = 1; f = |x| x + n; f(1, 2)",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:41\n",
+            "Elysia: expected 1 arguments, got 2 at 1:41\n",
+            "   |\n",
+            " 1 |  fn main(args) { n = 1; f = |x| x + n; f(1, 2) }\n",
+            "   |                                         ^\n",
+        ),
+    )?;
+    fail(
+        args.clone(),
+        "",
+        "g = |x| x[1]; g(args)",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:33\n",
+            "  in main::<closure> at 1:27\n",
+            "Elysia: index 1 is out of boundaries for `[]` at 1:27\n",
+            "   |\n",
+            " 1 |  fn main(args) { g = |x| x[1]; g(args) }\n",
+            "   |                           ^\n",
+        ),
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn errors() -> Result<(), String> {
+    let args = Object::List([].into());
+    let defs = "fn half(x) { if x % 2 == 0 { x / 2 } else { std::err::new(x) } }";
+
+    exec(args.clone(), defs, "half(8)? + 1", Object::Int(5), "")?;
+    exec(
+        args.clone(),
+        defs,
+        "std::io::print(1); x = half(7)?; std::io::print(2); x",
+        Object::Err(Object::Int(7).into()),
+        "1\n",
+    )?;
+    exec(
+        args.clone(),
+        defs,
+        "f = |x| half(half(x)?)?; (f(12), f(6), f(5))",
+        Object::Tuple(
+            [
+                Object::Int(3),
+                Object::Err(Object::Int(3).into()),
+                Object::Err(Object::Int(5).into()),
+            ]
+            .into(),
+        ),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        defs,
+        "n = 0; for x in [4, 2, 1] { n += half(x)?; } n",
+        Object::Err(Object::Int(1).into()),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "",
+        "(1, 2)?",
+        Object::Tuple([1.into(), 2.into()].into()),
+        "",
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn matches() -> Result<(), String> {
+    let args = Object::List([].into());
+    let defs = concat!(
+        "group Point(x, y); group Pair(a, b);",
+        "fn f(v) { match v {",
+        "Point(x, 0) => x, Point(x, y) if x > y => x - y, Point(x, y) => x * y,",
+        "Pair(a, _) => a, (1, b) => b, (-1, _, c) => c,",
+        "[] => 0, [first, ..rest] => first + f(rest), _ => -1,",
+        "} }",
+    );
+
+    exec(
+        args.clone(),
+        defs,
+        "(f(Point(3, 0)), f(Point(5, 2)), f(Point(2, 5)), f(Pair(7, 8)))",
+        Object::Tuple([3.into(), 3.into(), 10.into(), 7.into()].into()),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        defs,
+        "(f((1, 9)), f((-1, 0, 4)), f([1, 2, 3]), f((2, 2)), f(\"s\"))",
+        Object::Tuple([9.into(), 4.into(), 6.into(), (-1).into(), (-1).into()].into()),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "",
+        "match args { [x] => x, [..] => \"any\", _ => \"other\" }",
+        Object::Str("any".into()),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "",
+        "x = 1; y = match (2, 3) { (x, y) if x > y => y, (x, y) => { x += 10; x + y }, _ => 0 }; (x, y)",
+        Object::Tuple([1.into(), 15.into()].into()),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "",
+        "match [1, 2] { [_, ..rest] => rest, _ => [] }",
+        Object::List([2.into()].into()),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "",
+        "n = 0; match 3 { 1 => std::io::print(1), 3 => { n = 3; } } n",
+        Object::Int(3),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "",
+        "match args { [x] => x, (1, x) => x, \"a\" => 0, _ => 1 }",
+        Object::Int(1),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "fn g(v) { match v { 1 => 10, \"a\" => 20, true => 30, -2 => 40, _ => 50 } }",
+        "(match \"a\" { 1 => 10, \"a\" => 20, _ => 30 }, g(\"a\"), g(1i64), g(true), g(-2), g([1]), g(false))",
+        Object::Tuple(
+            [
+                20.into(),
+                20.into(),
+                10.into(),
+                30.into(),
+                40.into(),
+                50.into(),
+                50.into(),
+            ]
+            .into(),
+        ),
+        "",
+    )?;
+
+    for (code, expect) in [
+        (
+            "match args { (x, x) => x, _ => 0 }",
+            "Cyrene: this variable is bound more than once",
+        ),
+        (
+            "match args { [x] => 1, _ => x }",
+            "Cyrene: this path does not lead to anywhere",
+        ),
+        (
+            "match args { Missing(x) => x, _ => 0 }",
+            "Cyrene: this path does not lead to anywhere",
+        ),
+        (
+            "y = match args { [x] => x }; y",
+            "Cyrene: this expression does not have a return value",
+        ),
+        (
+            "match P(1, 2) { P(a) => a, _ => 0 }",
+            "Cyrene: this pattern does not match the number of fields in the group",
+        ),
+        (
+            "match P(1, 2) { P(a, b, c) => a, _ => 0 }",
+            "Cyrene: this pattern does not match the number of fields in the group",
+        ),
+    ] {
+        let wrapped = format!("group P(x, y); fn main(args) {{ {code} }}");
+        match compile(&wrapped, 0, &Registry::default()) {
+            Err(e) if e.starts_with(expect) => {}
+            _ => return Err(format!("Expected {expect}")),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn located() -> Result<(), String> {
+    let args = Object::List([].into());
+
+    fail(
+        args.clone(),
+        "fn f(x) { x + 1 }",
+        "f(\"a\")",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:36\n",
+            "  in f at 1:13\n",
+            "Elysia: cannot apply `+` to `a` and `1` at 1:13\n",
+            "   |\n",
+            " 1 | fn f(x) { x + 1 } fn main(args) { f(\"a\") }\n",
+            "   |             ^\n",
+        ),
+    )?;
+    fail(
+        args.clone(),
+        "",
+        "x = [1, 2]; x[5]",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:31\n",
+            "Elysia: index 5 is out of boundaries for `[1, 2]` at 1:31\n",
+            "   |\n",
+            " 1 |  fn main(args) { x = [1, 2]; x[5] }\n",
+            "   |                               ^\n",
+        ),
+    )?;
+    fail(
+        args.clone(),
+        "fn g(a) { a }",
+        "g()",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:32\n",
+            "Elysia: expected 1 arguments, got 0 at 1:32\n",
+            "   |\n",
+            " 1 | fn g(a) { a } fn main(args) { g() }\n",
+            "   |                                ^\n",
+        ),
+    )?;
+    fail(
+        args.clone(),
+        "group G(x); impl G { fn f(self, n) { if n > 0 { self.f(n - 1) + 0 } else { self.x + \"a\" } } }",
+        "G(1).f(3)",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:115\n",
+            "  in G::f at 1:53\n",
+            "  [previous frame repeated 2 more times]\n",
+            "  in G::f at 1:83\n",
+            "Elysia: cannot apply `+` to `1` and `a` at 1:83\n",
+            "   |\n",
+            " 1 | group G(x); impl G { fn f(self, n) { if n > 0 { self.f(n - 1) + 0 } else { self.x + \"a\" } } } fn main(args) { G(1).f(3) }\n",
+            "   |                                                                                   ^\n",
+        ),
+    )?;
+    fail(
+        args.clone(),
+        "group G(x); impl G { fn f(self, n) { if n > 0 { self.f(n - 1) } else { self.x + \"a\" } } }",
+        "G(1).f(3)",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:111\n",
+            "  in G::f at 1:79\n",
+            "Elysia: cannot apply `+` to `1` and `a` at 1:79\n",
+            "   |\n",
+            " 1 | group G(x); impl G { fn f(self, n) { if n > 0 { self.f(n - 1) } else { self.x + \"a\" } } } fn main(args) { G(1).f(3) }\n",
+            "   |                                                                               ^\n",
+        ),
+    )
+}
+
+#[test]
+fn formats() -> Result<(), String> {
+    let args = Object::List([].into());
+    exec(
+        args.clone(),
+        "",
+        "x = 7; f\"x = {x}, y = {x * 2 + 1}, z = {0.5 * 3.0}, list = {[x, (1, \"a\")]}\"",
+        Object::Str("x = 7, y = 15, z = 1.5, list = [7, (1, a)]".into()),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "",
+        "f\"{args}{true}\\{{1 + 2}\\} \\u{48}i\"",
+        Object::Str("[]true{3} Hi".into()),
+        "",
+    )?;
+    exec(args.clone(), "", "f\"\"", Object::Str("".into()), "")?;
+
+    let code = "fn main(args) { f\"a{1 + 2}b{\"c\"}{1.5}\" }";
+    let [iii, _, _] = compile(code, 1, &Registry::default())?;
+    let mut listing = String::new();
+    iii.disassemble(&mut listing).unwrap();
+    if !listing.contains("\"a3bc1.5\"") || listing.contains("unary") {
+        return Err(listing);
+    }
+
+    match compile("fn main(args) { f\"{args\" }", 0, &Registry::default()) {
+        Err(e) if e.starts_with("PhiLia093:") => Ok(()),
+        _ => Err("Expected PhiLia093".to_string()),
+    }
+}
+
+#[test]
+fn updates() -> Result<(), String> {
+    let args = Object::List([].into());
+    let defs = concat!(
+        "group Point(x, y); group Box(with);",
+        "impl Point { fn sum(self) { self.x + self.y } }",
+        "impl Box { fn with(self, n) { self.with + n } }",
+    );
+
+    exec(
+        args.clone(),
+        defs,
+        "p = Point(1, 2); q = p.with(x = 10, y = p.x + 5); (p, q.sum(), q == Point(10, 6))",
+        Object::Tuple(
+            [
+                Object::Group(0, [1.into(), 2.into()].into()),
+                16.into(),
+                true.into(),
+            ]
+            .into(),
+        ),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        defs,
+        "p = Point(1, 2).with(x = 3).with(x = 4, y = 5,); (p.x, p.y)",
+        Object::Tuple([4.into(), 5.into()].into()),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        defs,
+        "b = Box(1); (b.with(2), b.with(with = 5).with)",
+        Object::Tuple([3.into(), 5.into()].into()),
+        "",
+    )?;
+    fail(
+        args.clone(),
+        defs,
+        "Point(1, 2).with(z = 0)",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:158\n",
+            "Elysia: field `z` does not exist in `<1, 2> as 0x00000000` at 1:158\n",
+            "   |\n",
+            " 1 | group Point(x, y); group Box(with);impl Point { fn sum(self) { self.x + self.y } }impl Box { fn with(self, n) { self.with + n } } fn main(args) { Point(1, 2).with(z = 0) }\n",
+            "   |                                                                                                                                                              ^\n",
+        ),
+    )?;
+    fail(
+        args.clone(),
+        defs,
+        "(1, 2).with(x = 0)",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:153\n",
+            "Elysia: expecting `(1, 2)` to be `group` at 1:153\n",
+            "   |\n",
+            " 1 | group Point(x, y); group Box(with);impl Point { fn sum(self) { self.x + self.y } }impl Box { fn with(self, n) { self.with + n } } fn main(args) { (1, 2).with(x = 0) }\n",
+            "   |                                                                                                                                                         ^\n",
+        ),
+    )?;
+    fail(
+        args.clone(),
+        defs,
+        "Point(1, 2).foo()",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:158\n",
+            "Elysia: method `foo` does not exist in `<1, 2> as 0x00000000` at 1:158\n",
+            "   |\n",
+            " 1 | group Point(x, y); group Box(with);impl Point { fn sum(self) { self.x + self.y } }impl Box { fn with(self, n) { self.with + n } } fn main(args) { Point(1, 2).foo() }\n",
+            "   |                                                                                                                                                              ^\n",
+        ),
+    )?;
+
+    let wrapped = format!("{defs} fn main(args) {{ Point(1, 2).with(x = 5, x = 6) }}");
+    match compile(&wrapped, 0, &Registry::default()) {
+        Err(e) if e.starts_with("Cyrene: this field is updated more than once") => Ok(()),
+        Err(e) => Err(format!("Expected a repeated field, got {e}")),
+        Ok(_) => Err("Expected a repeated field".to_string()),
+    }
+}
+
+#[test]
+fn numerics() -> Result<(), String> {
+    let args = Object::List([].into());
+
+    exec(
+        args.clone(),
+        "",
+        "(9223372036854775807i64 - 1i64, 0.1f64 + 0.2f64, 1f64 / 4f64, -7i64 % 2i64, 2.5f64 % 1f64, 7.5 % 2.0)",
+        Object::Tuple(
+            [
+                Object::I64(i64::MAX - 1),
+                Object::F64(0.1 + 0.2),
+                Object::F64(0.25),
+                Object::I64(-1),
+                Object::F64(0.5),
+                Object::Float(1.5),
+            ]
+            .into(),
+        ),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "",
+        "m = {3000000000i64: 1}; (m[3000000000i64], 3i64 > 2i64, -(-5i64), f\"{1i64} {0.5f64}\")",
+        Object::Tuple(
+            [
+                1.into(),
+                true.into(),
+                Object::I64(5),
+                Object::Str("1 0.5".into()),
+            ]
+            .into(),
+        ),
+        "",
+    )?;
+
+    for (body, col, msg) in [
+        ("2147483647 + 1", 29, "integer overflow in `2147483647 + 1`"),
+        (
+            "x = 65536; x * x",
+            31,
+            "integer overflow in `65536 * 65536`",
+        ),
+        (
+            "-(-2147483647 - 1)",
+            18,
+            "integer overflow in `-(-2147483648)`",
+        ),
+        (
+            "-9223372036854775807i64 - 2i64",
+            42,
+            "integer overflow in `-9223372036854775807 - 2`",
+        ),
+        ("1 / 0", 20, "cannot apply `/` to `1` and `0`"),
+        ("7i64 % 0i64", 23, "cannot apply `%` to `7` and `0`"),
+        ("1 @ 2", 20, "cannot apply `@` to `1` and `2`"),
+    ] {
+        let code = format!(" fn main(args) {{ {body} }}");
+        fail(
+            args.clone(),
+            "",
+            body,
+            &format!(
+                "Traceback (most recent call last):\n  in main at 1:{col}\nElysia: {msg} at 1:{col}\n   |\n 1 | {code}\n   | {}^\n",
+                " ".repeat(col - 1)
+            ),
+        )?;
+    }
+
+    match compile(
+        "fn main(args) { 9223372036854775808i64 }",
+        0,
+        &Registry::default(),
+    ) {
+        Err(e) if e.starts_with("Cyrene:") => Ok(()),
+        _ => Err("Expected Cyrene".to_string()),
+    }
+}
+
+#[test]
+fn promotion() -> Result<(), String> {
+    let cases = [
+        (
+            "1 + 0.5",
+            Object::Int(1),
+            Object::Float(0.5),
+            "+",
+            Object::Float(1.5),
+        ),
+        (
+            "2 * 3i64",
+            Object::Int(2),
+            Object::I64(3),
+            "*",
+            Object::I64(6),
+        ),
+        (
+            "1i64 / 4.0",
+            Object::I64(1),
+            Object::Float(4.0),
+            "/",
+            Object::F64(0.25),
+        ),
+        (
+            "0.5 - 1f64",
+            Object::Float(0.5),
+            Object::F64(1.0),
+            "-",
+            Object::F64(-0.5),
+        ),
+        (
+            "7 % 2.5",
+            Object::Int(7),
+            Object::Float(2.5),
+            "%",
+            Object::Float(2.0),
+        ),
+        (
+            "2147483647 + 1i64",
+            Object::Int(i32::MAX),
+            Object::I64(1),
+            "+",
+            Object::I64(2147483648),
+        ),
+        (
+            "0.1 + 1f64",
+            Object::Float(0.1),
+            Object::F64(1.0),
+            "+",
+            Object::F64(0.1f32 as f64 + 1.0),
+        ),
+        (
+            "3 > 2.5",
+            Object::Int(3),
+            Object::Float(2.5),
+            ">",
+            Object::Bool(true),
+        ),
+        (
+            "1 == 1.0f64",
+            Object::Int(1),
+            Object::F64(1.0),
+            "==",
+            Object::Bool(true),
+        ),
+        (
+            "2i64 != 2",
+            Object::I64(2),
+            Object::Int(2),
+            "!=",
+            Object::Bool(false),
+        ),
+    ];
+
+    let folded = cases.iter().map(|(expr, ..)| *expr).collect::<Vec<_>>();
+    let runtime = cases
+        .iter()
+        .enumerate()
+        .map(|(i, (_, _, _, op, _))| format!("args[{}] {op} args[{}]", 2 * i, 2 * i + 1))
+        .collect::<Vec<_>>();
+    let args = cases
+        .iter()
+        .flat_map(|(_, lhs, rhs, ..)| [lhs.clone(), rhs.clone()])
+        .collect::<Vec<_>>();
+    let expect = Object::Tuple(cases.iter().map(|(.., x)| x.clone()).collect());
+
+    let code = format!("fn main(args) {{ ({}) }}", folded.join(", "));
+    let [iii, _, _] = compile(&code, 1, &Registry::default())?;
+    let mut listing = String::new();
+    iii.disassemble(&mut listing).unwrap();
+    if listing.contains("binary") {
+        return Err(listing);
+    }
+
+    for body in [folded.join(", "), runtime.join(", ")] {
+        let code = format!("fn main(args) {{ ({body}) }}");
+        for o in [0, 1, 2, usize::MAX] {
+            for iii in compile(&code, o, &Registry::default())? {
+                let obj = iii.exec(Object::List(args.clone().into()), &mut String::new())?;
+                if format!("{obj:?}") != format!("{expect:?}") {
+                    return Err(format!("Expected {expect:?}, got {obj:?}"));
+                }
+            }
+        }
+    }
 
     Ok(())
 }
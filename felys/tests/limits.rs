@@ -0,0 +1,201 @@
+use crate::utils::compile;
+use felys::{Io, Limits, Object, Registry, Status};
+use std::io;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+mod utils;
+
+const COUNT: &str = "\
+fn main(args) {
+    (i, total) = (0, 0);
+    while i < args {
+        total += i;
+        i += 1;
+    }
+    total
+}";
+
+fn io<'a>(stdin: &'a mut io::Empty, stdout: &'a mut Vec<u8>, stderr: &'a mut io::Sink) -> Io<'a> {
+    Io {
+        stdin,
+        stdout,
+        stderr,
+    }
+}
+
+#[test]
+fn fuel() -> Result<(), String> {
+    let (mut stdin, mut stdout, mut stderr) = (io::empty(), Vec::new(), io::sink());
+    for o in [0, 1, 2, usize::MAX] {
+        for iii in compile(COUNT, o, &Registry::default())? {
+            let limits = Limits {
+                fuel: Some(1000),
+                ..Limits::default()
+            };
+            let mut io = io(&mut stdin, &mut stdout, &mut stderr);
+            let mut process = iii.spawn(Object::Int(10), limits.clone())?;
+            assert!(matches!(
+                process.resume(None, &mut io)?,
+                Status::Exited(Object::Int(45))
+            ));
+
+            let mut process = iii.spawn(Object::Int(1000), limits)?;
+            let e = process.resume(None, &mut io).err().unwrap();
+            assert!(e.starts_with("Traceback (most recent call last):\n  in main "));
+            assert!(e.contains("Elysia: ran out of fuel after 1000 instructions"));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn callback() -> Result<(), String> {
+    let code = "fn main(args) { std::list::map([1, 2], |x| { while x < args { x += 1; } x }) }";
+    let (mut stdin, mut stdout, mut stderr) = (io::empty(), Vec::new(), io::sink());
+    for o in [0, 1, 2, usize::MAX] {
+        for iii in compile(code, o, &Registry::default())? {
+            let limits = Limits {
+                fuel: Some(1000),
+                ..Limits::default()
+            };
+            let mut io = io(&mut stdin, &mut stdout, &mut stderr);
+            let mut process = iii.spawn(Object::Int(1000000), limits)?;
+            let e = process.resume(None, &mut io).err().unwrap();
+            assert!(e.contains("ran out of fuel after 1000 instructions"));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn objects() -> Result<(), String> {
+    let code = "\
+group Pair(a, b);
+
+fn main(args) {
+    (i, xs) = (0, []);
+    while i < args {
+        xs = [Pair(i, (i, i)), xs];
+        i += 1;
+    }
+    (i, xs)
+}";
+    let (mut stdin, mut stdout, mut stderr) = (io::empty(), Vec::new(), io::sink());
+    for o in [0, 1, 2, usize::MAX] {
+        for iii in compile(code, o, &Registry::default())? {
+            let limits = Limits {
+                objects: Some(30),
+                ..Limits::default()
+            };
+            let mut io = io(&mut stdin, &mut stdout, &mut stderr);
+            let mut process = iii.spawn(Object::Int(10), limits.clone())?;
+            let e = process.resume(None, &mut io).err().unwrap();
+            assert!(e.contains("Elysia: allocated more than 30 objects"));
+
+            let mut process = iii.spawn(Object::Int(7), limits)?;
+            assert!(matches!(
+                process.resume(None, &mut io)?,
+                Status::Exited(Object::Tuple(_))
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn natives() -> Result<(), String> {
+    let code = "\
+fn main(args) {
+    (i, s, xs) = (0, \"\", []);
+    while i < args {
+        s = s + \"a\";
+        xs = std::list::push(xs, i);
+        i += 1;
+    }
+    (s, xs)
+}";
+    let (mut stdin, mut stdout, mut stderr) = (io::empty(), Vec::new(), io::sink());
+    for o in [0, 1, 2, usize::MAX] {
+        for iii in compile(code, o, &Registry::default())? {
+            let limits = Limits {
+                objects: Some(30),
+                ..Limits::default()
+            };
+            let mut io = io(&mut stdin, &mut stdout, &mut stderr);
+            let mut process = iii.spawn(Object::Int(20), limits.clone())?;
+            let e = process.resume(None, &mut io).err().unwrap();
+            assert!(e.contains("Elysia: allocated more than 30 objects"));
+
+            let mut process = iii.spawn(Object::Int(10), limits)?;
+            assert!(matches!(
+                process.resume(None, &mut io)?,
+                Status::Exited(Object::Tuple(_))
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn interrupt() -> Result<(), String> {
+    let flag = Arc::new(AtomicBool::new(false));
+    let setter = flag.clone();
+    let registry = Registry::default().register("host", "cancel", move |_, _| {
+        setter.store(true, Ordering::Relaxed);
+        Ok(Object::Bool(true))
+    });
+    let code = "fn main(args) { host::cancel(); args + 1 }";
+
+    let (mut stdin, mut stdout, mut stderr) = (io::empty(), Vec::new(), io::sink());
+    for o in [0, 1, 2, usize::MAX] {
+        for iii in compile(code, o, &registry)? {
+            flag.store(false, Ordering::Relaxed);
+            let limits = Limits {
+                interrupt: Some(flag.clone()),
+                ..Limits::default()
+            };
+            let mut io = io(&mut stdin, &mut stdout, &mut stderr);
+            let e = iii.spawn(Object::Int(1), limits)?.resume(None, &mut io);
+            assert!(e.err().unwrap().contains("Elysia: interrupted by the host"));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn resume() -> Result<(), String> {
+    let (mut stdin, mut stdout, mut stderr) = (io::empty(), Vec::new(), io::sink());
+    for o in [0, 1, 2, usize::MAX] {
+        for iii in compile(COUNT, o, &Registry::default())? {
+            let mut io = io(&mut stdin, &mut stdout, &mut stderr);
+            let mut process = iii.spawn(Object::Int(100), Limits::default())?;
+            let mut slices = 0;
+            let obj = loop {
+                match process.resume(Some(10), &mut io)? {
+                    Status::Exited(obj) => break obj,
+                    Status::Paused => slices += 1,
+                }
+            };
+            assert_eq!(obj.to_string(), "4950");
+            assert!(slices > 10);
+            let e = process.resume(Some(10), &mut io).err().unwrap();
+            assert_eq!(e, "Elysia: program has already terminated\n");
+
+            let limits = Limits {
+                fuel: Some(500),
+                ..Limits::default()
+            };
+            let mut process = iii.spawn(Object::Int(100), limits)?;
+            let e = loop {
+                match process.resume(Some(10), &mut io) {
+                    Ok(Status::Exited(obj)) => panic!("expected an error, got {obj}"),
+                    Ok(Status::Paused) => {}
+                    Err(e) => break e,
+                }
+            };
+            assert!(e.contains("ran out of fuel after 500 instructions"));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,219 @@
+mod utils;
+
+use crate::utils::{import, object, roundtrip};
+use felys::{Object, PhiLia093, Registry};
+
+#[test]
+fn modules() -> Result<(), String> {
+    let modules = [
+        (
+            "geo",
+            "group Point(x, y); impl Point { fn sum(self) { self.x + self.y } } fn norm(p) { p.x * p.x + p.y * p.y }",
+        ),
+        (
+            "geo::shapes",
+            "use geo::Point; use geo::norm; fn square(n) { norm(Point(n, 0)) } fn origin() { Point(0, 0) }",
+        ),
+        ("extra", "mod geo; fn twice(x) { x * 2 }"),
+    ];
+    let code = concat!(
+        "mod geo; mod geo::shapes; mod extra; use geo::Point; use geo::shapes; use std::str;",
+        "fn main(args) { p = Point(1, 2); (geo::norm(p), p.sum(), shapes::square(3), ",
+        "extra::twice(str::len(\"ab\")), shapes::origin() == Point(0, 0), std::reflect::name(p)) }",
+    );
+    let expect = Object::Tuple(
+        [
+            5.into(),
+            3.into(),
+            9.into(),
+            4.into(),
+            true.into(),
+            "geo::Point".to_string().into(),
+        ]
+        .into(),
+    );
+    for o in [0, 1, usize::MAX] {
+        for iii in import(code, &modules, o)? {
+            let obj = iii.exec(Object::List([].into()), &mut String::new())?;
+            if format!("{obj:?}") != format!("{expect:?}") {
+                return Err(format!("Expected {expect}, got {obj}"));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn errors() -> Result<(), String> {
+    for (code, modules, expect) in [
+        (
+            "mod a; fn main(args) { 0 }",
+            vec![("a", "mod b; fn f() { 0 }"), ("b", "mod a;")],
+            "Cyrene: these modules import each other in a cycle\n>>> | a -> b -> a\n",
+        ),
+        (
+            "mod a; fn main(args) { 0 }",
+            vec![],
+            "Cyrene: this module cannot be loaded\n>>> | a\n",
+        ),
+        (
+            "mod a; fn main(args) { 0 }",
+            vec![("a", "fn f( { 0 }")],
+            "a: PhiLia093:",
+        ),
+        (
+            "mod a; use a::g; fn main(args) { 0 }",
+            vec![("a", "fn f() { 0 }")],
+            "Cyrene: this path does not lead to anywhere",
+        ),
+        (
+            "mod a; use a::f; fn f() { 1 } fn main(args) { 0 }",
+            vec![("a", "fn f() { 0 }")],
+            "Cyrene: this item is redeclared",
+        ),
+        (
+            "mod a; fn main(args) { a::f() }",
+            vec![("a", "fn f() { 0 } fn main(args) { 0 }")],
+            "Cyrene: this item is redeclared",
+        ),
+        (
+            "mod a; fn main(args) { f() }",
+            vec![("a", "fn f() { 0 }")],
+            "Cyrene: this path does not lead to anywhere",
+        ),
+    ] {
+        match import(code, &modules, 0) {
+            Err(e) if e.starts_with(expect) => {}
+            Err(e) => return Err(format!("Expected {expect}, got {e}")),
+            Ok(_) => return Err(format!("Expected {expect}")),
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn linking() -> Result<(), String> {
+    let registry = Registry::default();
+    let library = concat!(
+        "group Point(x, y); impl Point { fn sum(self) { self.x + self.y } }",
+        "fn norm(p) { p.x * p.x + p.y * p.y } fn scale(p, k) { Point(p.x * k, p.y * k) }",
+        "fn label(p) { f\"({p.x}, {p.y})\" }",
+    );
+    let code = concat!(
+        "use std::str; fn main(args) { p = scale(Point(1, 2), 2); ",
+        "(norm(p), p.sum(), label(p), str::len(label(p)), p == Point(2, 4)) }",
+    );
+    let expect = Object::Tuple(
+        [
+            20.into(),
+            6.into(),
+            "(2, 4)".to_string().into(),
+            6.into(),
+            true.into(),
+        ]
+        .into(),
+    );
+    for o in [0, 1, usize::MAX] {
+        for k in 0..3 {
+            let [lib, ..] = object(library, None, o, &registry)?;
+            for unit in object(code, Some(&lib), o, &registry)? {
+                let lib = object(library, None, o, &registry)?
+                    .into_iter()
+                    .nth(k)
+                    .unwrap();
+                match unit.exec(Object::List([].into()), &mut String::new()) {
+                    Err(e) if e.starts_with("Elysia: symbol `") => {}
+                    Err(e) => return Err(format!("Expected an unlinked error, got {e}")),
+                    Ok(obj) => return Err(format!("Expected an unlinked error, got {obj}")),
+                }
+                let iii = unit.link(vec![lib]).map_err(|e| e.to_string())?;
+                for iii in roundtrip(iii, &registry)? {
+                    let obj = iii.exec(Object::List([].into()), &mut String::new())?;
+                    if format!("{obj:?}") != format!("{expect:?}") {
+                        return Err(format!("Expected {expect}, got {obj}"));
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn symbols() -> Result<(), String> {
+    let registry = Registry::default();
+    let [lib, other, again] = object("fn f() { 1 }", None, 0, &registry)?;
+    let [g, ..] = object("fn g() { 2 }", None, 0, &registry)?;
+    let [x, y, z] = object("fn main(args) { f() }", Some(&lib), 0, &registry)?;
+    for (unit, units, expect) in [
+        (
+            x,
+            vec![other, again],
+            "symbol `f` is defined more than once",
+        ),
+        (y, vec![g], "symbol `f` is not defined"),
+        (z, vec![], "symbol `f` is not defined"),
+    ] {
+        match unit.link(units) {
+            Err(e) if e.to_string() == expect => {}
+            Err(e) => return Err(format!("Expected {expect}, got {e}")),
+            Ok(_) => return Err(format!("Expected {expect}")),
+        }
+    }
+    match PhiLia093::from("fn main(args) { f() }".to_string())
+        .parse()?
+        .declare(&lib)
+        .declare(&lib)
+        .desugar(&registry)
+    {
+        Err(e) if e == "Cyrene: this external symbol is redeclared\n>>> | f\n" => Ok(()),
+        Err(e) => Err(format!("Expected a redeclared symbol, got {e}")),
+        Ok(_) => Err("Expected a redeclared symbol".to_string()),
+    }
+}
+
+#[test]
+fn entry() -> Result<(), String> {
+    let registry = Registry::default();
+    let library = "group Point(x, y); impl Point { fn origin() { Point(0, 0) } } fn f() { 1 }";
+    let code = "fn main(args) { p = Point::origin(); p.x + f() }";
+    for lib in object(library, None, 0, &registry)? {
+        match lib.exec(Object::List([].into()), &mut String::new()) {
+            Err(e) if e == "Elysia: program entry not found\n" => {}
+            Err(e) => return Err(format!("Expected a missing entry, got {e}")),
+            Ok(obj) => return Err(format!("Expected a missing entry, got {obj}")),
+        }
+    }
+
+    let [lib, other, again] = object(library, None, 0, &registry)?;
+    let [g, ..] = object("fn g() { 2 }", None, 0, &registry)?;
+    for (unit, units) in [(lib, vec![]), (other, vec![g])] {
+        match unit.link(units) {
+            Err(e) if e.to_string() == "program entry not found" => {}
+            Err(e) => return Err(format!("Expected a missing entry, got {e}")),
+            Ok(_) => return Err("Expected a missing entry".to_string()),
+        }
+    }
+    for unit in object(code, Some(&again), 0, &registry)? {
+        let [lib, ..] = object(library, None, 0, &registry)?;
+        let iii = lib.link(vec![unit]).map_err(|e| e.to_string())?;
+        for iii in roundtrip(iii, &registry)? {
+            match iii.exec(Object::List([].into()), &mut String::new())? {
+                Object::Int(1) => {}
+                obj => return Err(format!("Expected 1, got {obj}")),
+            }
+        }
+    }
+
+    match PhiLia093::from("impl Point { fn f(self) { 0 } } fn main(args) { 0 }".to_string())
+        .parse()?
+        .declare(&again)
+        .desugar(&registry)
+    {
+        Err(e) if e.starts_with("Cyrene: this method cannot be attached to an external group") => {
+            Ok(())
+        }
+        Err(e) => Err(format!("Expected an external impl, got {e}")),
+        Ok(_) => Err("Expected an external impl".to_string()),
+    }
+}
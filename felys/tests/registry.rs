@@ -0,0 +1,69 @@
+use crate::utils::compile;
+use felys::{III, Object, Registry};
+use std::cell::Cell;
+use std::rc::Rc;
+
+mod utils;
+
+#[test]
+fn host() -> Result<(), String> {
+    let counter = Rc::new(Cell::new(0));
+    let tick = counter.clone();
+    let registry = Registry::default()
+        .register("host", "tick", move |_, _| {
+            tick.set(tick.get() + 1);
+            Ok(Object::Int(tick.get()))
+        })
+        .register("host", "greet", |args, vm| {
+            writeln!(vm.io.stdout, "hello, {}!", args[0]).map_err(|e| e.to_string())?;
+            Ok(Object::Bool(true))
+        });
+    let code = "fn main(args) { host::tick(); host::greet(\"felys\"); host::tick() }";
+
+    for o in [0, 1, 2, usize::MAX] {
+        for iii in compile(code, o, &registry)? {
+            let before = counter.get();
+            let mut stdout = String::new();
+            let obj = iii.exec(Object::List([].into()), &mut stdout)?;
+            assert_eq!(obj.to_string(), (before + 2).to_string());
+            assert_eq!(stdout, "hello, felys!\n");
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn conflict() {
+    let shadow = Registry::default().register("std", "io", |_, _| Ok(Object::Int(0)));
+    let nested = Registry::default().register("std::io::print", "inner", |_, _| Ok(Object::Int(0)));
+    for (registry, symbol) in [(shadow, "std::io"), (nested, "std::io::print::inner")] {
+        match compile("fn main(args) { 0 }", 1, &registry) {
+            Ok(_) => panic!("`{symbol}` should conflict with `std::io::print`"),
+            Err(e) => assert_eq!(
+                e,
+                format!(
+                    "Cyrene: this native symbol conflicts with another native\n>>> | {symbol}\n"
+                )
+            ),
+        }
+    }
+}
+
+#[test]
+fn link() -> Result<(), String> {
+    let registry = Registry::default().register("host", "answer", |_, _| Ok(Object::Int(42)));
+    let [iii, _, _] = compile("fn main(args) { host::answer() }", 1, &registry)?;
+    let mut binary = Vec::new();
+    iii.dump(&mut binary).unwrap();
+
+    let loaded = III::load(&mut binary.as_slice(), &registry).unwrap();
+    let mut stdout = String::new();
+    let obj = loaded.exec(Object::List([].into()), &mut stdout)?;
+    assert_eq!(obj.to_string(), "42");
+
+    match III::load(&mut binary.as_slice(), &Registry::default()) {
+        Ok(_) => panic!("`host::answer` should not link against the default registry"),
+        Err(e) => assert_eq!(e.to_string(), "native `host::answer` not found"),
+    }
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use felys::{Io, Object, Registry, Repl};
+
+#[test]
+fn session() {
+    let mut repl = Repl::new(Registry::default(), 1);
+    let inputs = [
+        ("x = 1", Ok(None)),
+        ("fn bad(a) { a + x }", Err("Cyrene: ")),
+        ("bad(2)", Err("Cyrene: ")),
+        ("m = {1: 2}; x + 1", Ok(Some("2"))),
+        ("[5, 6]", Ok(Some("[5, 6]"))),
+        ("x + 41", Ok(Some("42"))),
+        ("fn sq(n) { n * n }", Ok(None)),
+        ("group P(a, b);", Ok(None)),
+        ("(p, y) = (P(2, 3), sq(x + 1));", Ok(None)),
+        ("p.b + y", Ok(Some("7"))),
+        ("while x < 3 { x += 1; }", Ok(None)),
+        ("x", Ok(Some("3"))),
+        ("return x * 2", Ok(Some("6"))),
+        ("x = 10; return x", Ok(Some("10"))),
+        ("if x > 0 { return 1; } x", Ok(Some("1"))),
+        ("break", Err("Cyrene: ")),
+        ("x", Ok(Some("3"))),
+        ("std::io::print(x, y)", Ok(Some("2"))),
+        ("z + 1", Err("Cyrene: ")),
+        ("x +", Err("PhiLia093: ")),
+        ("[x][1]", Err("Traceback (most recent call last):\n")),
+        ("fn sq(n) { n }", Err("Cyrene: ")),
+        ("args", Err("Cyrene: ")),
+        ("sq(x) + y", Ok(Some("13"))),
+        ("(f, g) = (sq, std::io::print);", Ok(None)),
+        ("fn cube(n) { n * sq(n) }", Ok(None)),
+        ("impl P { fn sum(self) { self.a + self.b } }", Ok(None)),
+        ("g(f(2), cube(2), p.sum())", Ok(Some("3"))),
+        ("h = |n| n + x;", Err("Elysia: ")),
+        ("(|n| n + x)(1)", Ok(Some("4"))),
+    ];
+
+    let mut stdout = Vec::new();
+    for (input, expect) in inputs {
+        let mut stdin = "".as_bytes();
+        let mut io = Io {
+            stdin: &mut stdin,
+            stdout: &mut stdout,
+            stderr: &mut Vec::new(),
+        };
+        let result = repl.eval(input, &mut io);
+        match (result, expect) {
+            (Ok(obj), Ok(value)) => {
+                assert_eq!(obj.map(|x| x.to_string()).as_deref(), value, "{input}")
+            }
+            (Err(e), Err(prefix)) => assert!(e.starts_with(prefix), "{input}: {e}"),
+            (result, _) => panic!("unexpected result for `{input}`: {result:?}"),
+        }
+    }
+    assert_eq!(String::from_utf8_lossy(&stdout), "3 4\n4 8 5\n");
+}
+
+#[test]
+fn object() {
+    let mut repl = Repl::new(Registry::default(), 1);
+    let mut stdin = "".as_bytes();
+    let mut io = Io {
+        stdin: &mut stdin,
+        stdout: &mut Vec::new(),
+        stderr: &mut Vec::new(),
+    };
+    let obj = repl.eval("[1, (2.5, \"three\")]", &mut io).unwrap();
+    assert!(matches!(obj, Some(Object::List(_))));
+    assert_eq!(obj.unwrap().to_string(), "[1, (2.5, three)]");
+}
+
+#[test]
+fn balanced() {
+    for (input, expect) in [
+        ("s = \"(\";", true),
+        ("s = \"\\\"(\";", true),
+        ("s = f\"{x} [\";", true),
+        ("s = f\"{ (1, \"}\") }\";", true),
+        ("s = \"\\u{28}\";", true),
+        ("x = 1; // (", true),
+        ("if\"{\" == x { 1 }", true),
+        ("fn f() {", false),
+        ("s = f\"{ (1,", false),
+        ("s = \"(", false),
+        ("fn f() { \"}\"", false),
+    ] {
+        assert_eq!(Repl::balanced(input), expect, "{input}");
+    }
+}
@@ -1,5 +1,5 @@
-use crate::utils::exec;
-use felys::Object;
+use crate::utils::{compile, exec, fail};
+use felys::{Io, Key, Map, Object, Registry};
 
 mod utils;
 
@@ -15,6 +15,30 @@ fn io() -> Result<(), String> {
         "42\n",
     )?;
 
+    let code = r#"fn main(args) {
+        name = std::io::input("name? ");
+        std::io::eprint("hello", name);
+        line = std::io::readline();
+        std::io::print(line);
+        std::io::readline() == "" and std::io::flush()
+    }"#;
+    for o in [0, 1, 2, usize::MAX] {
+        for iii in compile(code, o, &Registry::default())? {
+            let mut stdin = "felys\r\nelysia\n".as_bytes();
+            let mut stdout = Vec::new();
+            let mut stderr = Vec::new();
+            let mut io = Io {
+                stdin: &mut stdin,
+                stdout: &mut stdout,
+                stderr: &mut stderr,
+            };
+            let obj = iii.run(Object::List([].into()), &mut io)?;
+            assert_eq!(obj.to_string(), "true");
+            assert_eq!(String::from_utf8_lossy(&stdout), "name? elysia\n\n");
+            assert_eq!(String::from_utf8_lossy(&stderr), "hello felys\n");
+        }
+    }
+
     Ok(())
 }
 
@@ -46,3 +70,401 @@ fn pink() -> Result<(), String> {
 
     Ok(())
 }
+
+#[test]
+fn err() -> Result<(), String> {
+    let args = Object::List([].into());
+
+    exec(
+        args.clone(),
+        "",
+        r#"
+        e = std::err::new("bad");
+        std::io::print(e, std::err::is(e), std::err::is("bad"), std::err::payload(e));
+        t = std::nn::tensor([[1.0], [2.0, 3.0]]);
+        s = std::nn::sum(std::nn::tensor([1.0, 2.0]), [3], false);
+        std::io::print(t, s);
+        e == std::err::new("bad")
+        "#,
+        Object::Bool(true),
+        "Err(bad) true false bad\nErr(tensor conversion error) Err(axes must be less than rank)\n",
+    )?;
+    exec(
+        args.clone(),
+        "",
+        r#"
+        e = std::err::new("bad");
+        std::io::print(std::err::map(1, |x| x + 1), std::err::map(e, |x| x + 1));
+        std::io::print(std::err::or_else(1, std::str::len), std::err::or_else(e, std::str::len));
+        std::err::or_else(std::str::int("x"), |msg| 0)
+        "#,
+        Object::Int(0),
+        "2 Err(bad)\n1 3\n",
+    )?;
+    let code = "fn main(args) { std::io::input()? }";
+    for o in [0, 1, 2, usize::MAX] {
+        for iii in compile(code, o, &Registry::default())? {
+            let mut stdin = "".as_bytes();
+            let mut io = Io {
+                stdin: &mut stdin,
+                stdout: &mut Vec::new(),
+                stderr: &mut Vec::new(),
+            };
+            let obj = iii.run(Object::List([].into()), &mut io)?;
+            assert_eq!(obj.to_string(), "Err(unexpected end of input)");
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn list() -> Result<(), String> {
+    let args = Object::List([].into());
+
+    exec(
+        args.clone(),
+        "use std::list; fn square(x) { x * x } group Point(x, y);",
+        r#"
+        xs = [3, 1, 2];
+        std::io::print(list::len(xs), list::push(xs, 4), list::concat(xs, [5]), list::reverse(xs), xs);
+        std::io::print(list::slice(xs, 1, 3), list::slice(xs, -2, 3), list::slice(xs, 3, 3));
+        std::io::print(list::sort(xs, |x| x), list::sort(["bb", "a", "ccc"], |s| std::str::len(s)), list::sort([], |x| x));
+        std::io::print(list::map(xs, square), list::filter(xs, |x| x % 2 == 1), list::fold(xs, 10, |acc, x| acc - x));
+        std::io::print(list::zip(xs, ["a", "b"]), list::enumerate(["x", "y"]));
+        std::io::print(list::contains(xs, 2), list::contains(xs, "2"), list::index_of(xs, 1), list::index_of(xs, 9));
+        k = 2;
+        points = list::map(xs, |x| Point(x, x * k));
+        list::fold(list::sort(points, |p| -p.y), [], |acc, p| list::push(acc, p.x))
+        "#,
+        Object::List([Object::Int(3), Object::Int(2), Object::Int(1)].into()),
+        concat!(
+            "3 [3, 1, 2, 4] [3, 1, 2, 5] [2, 1, 3] [3, 1, 2]\n",
+            "[1, 2] [1, 2] []\n",
+            "[1, 2, 3] [a, bb, ccc] []\n",
+            "[9, 1, 4] [3, 1] 4\n",
+            "[(3, a), (1, b)] [(0, x), (1, y)]\n",
+            "true false 1 -1\n",
+        ),
+    )?;
+    fail(
+        args.clone(),
+        "",
+        "std::list::slice([1], 0, 2)",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:34\n",
+            "Elysia: index 2 is out of boundaries for `[1]` at 1:34\n",
+            "   |\n",
+            " 1 |  fn main(args) { std::list::slice([1], 0, 2) }\n",
+            "   |                                  ^\n",
+        ),
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn map() -> Result<(), String> {
+    let args = Object::List([].into());
+
+    exec(
+        args.clone(),
+        "",
+        r#"
+        m = std::map::insert({"a": 1}, "b", 2);
+        n = std::map::remove(m, "a");
+        std::io::print(m, n, std::map::remove(n, "z"));
+        std::io::print(std::map::keys(m), std::map::values(m), std::map::len(n));
+        std::io::print(std::map::contains(m, "a"), std::map::contains(n, "a"));
+        std::map::get(m, "b", 0) + std::map::get(n, "a", 40)
+        "#,
+        Object::Int(42),
+        "{a: 1, b: 2} {b: 2} {b: 2}\n[a, b] [1, 2] 1\ntrue false\n",
+    )?;
+    exec(
+        args.clone(),
+        "",
+        r#"
+        m = {"a": 1, "b": 2, "c": 3};
+        counts = std::list::fold(["x", "y", "x"], {:}, |acc, k| std::map::update(acc, k, 0, |n| n + 1));
+        std::io::print(counts, std::map::update(m, "a", 0, |n| n * 10));
+        std::map::filter(m, |k, v| k != "b" and v > 1)
+        "#,
+        Object::Map(Map::default().insert(Key::Str("c".into()), Object::Int(3))),
+        "{x: 2, y: 1} {a: 10, b: 2, c: 3}\n",
+    )?;
+    exec(
+        args.clone(),
+        "",
+        "m = {(1, \"x\"): 1}; std::map::insert(m, (1, \"x\"), 2) == {(1, \"x\"): 2} and m[(1, \"x\")] == 1",
+        Object::Bool(true),
+        "",
+    )?;
+    exec(
+        args.clone(),
+        "",
+        "m = std::map::insert({1: \"a\", 2i64: \"b\"}, 1i64, \"c\"); (m[1i64], m[2], std::map::len(m), std::map::keys(m))",
+        Object::Tuple(
+            [
+                Object::Str("c".into()),
+                Object::Str("b".into()),
+                Object::Int(2),
+                Object::List([Object::Int(1), Object::I64(2)].into()),
+            ]
+            .into(),
+        ),
+        "",
+    )?;
+    fail(
+        args.clone(),
+        "",
+        "{\"a\": 1}[\"b\"]",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:26\n",
+            "Elysia: key `b` does not exist in `{a: 1}` at 1:26\n",
+            "   |\n",
+            " 1 |  fn main(args) { {\"a\": 1}[\"b\"] }\n",
+            "   |                          ^\n",
+        ),
+    )?;
+    fail(
+        args.clone(),
+        "",
+        "{[1]: 2}",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:18\n",
+            "Elysia: expecting `[1]` to be `key` at 1:18\n",
+            "   |\n",
+            " 1 |  fn main(args) { {[1]: 2} }\n",
+            "   |                  ^\n",
+        ),
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn str() -> Result<(), String> {
+    let args = Object::List([].into());
+
+    exec(
+        args.clone(),
+        "",
+        r#"
+        s = "  你好, felys  ";
+        t = std::str::trim(s);
+        std::io::print(std::str::len(s), std::str::len(t), std::str::chars("héy"), std::str::bytes("é"));
+        parts = std::str::split("a,b,,c", ",");
+        std::io::print(parts, std::str::join(parts, "-"), std::str::split("abc", ""));
+        std::io::print(std::str::find(t, "felys"), std::str::find(t, "x"), std::str::replace(t, "felys", "elysia"));
+        std::io::print(std::str::starts_with(t, "你好"), std::str::ends_with(t, "好"));
+        std::io::print(std::str::upper("Felys"), std::str::lower("Felys"), std::str::to_string((1, [2.5])));
+        std::io::print(std::str::int(" 42 "), std::str::float("0.5"), std::str::int("4x"));
+        std::str::int("40")? + 2
+        "#,
+        Object::Int(42),
+        concat!(
+            "13 9 [h, é, y] [195, 169]\n",
+            "[a, b, , c] a-b--c Err(empty separator)\n",
+            "4 -1 你好, elysia\n",
+            "true false\n",
+            "FELYS felys (1, [2.5])\n",
+            "42 0.5 Err(invalid digit found in string)\n",
+        ),
+    )?;
+    exec(
+        args.clone(),
+        "",
+        r#"s = "你好世界"; (s[1], s[-1], s[(1, 3)], s[(-2, 4)], s[(2, 2)])"#,
+        Object::Tuple(
+            [
+                Object::Str("好".into()),
+                Object::Str("界".into()),
+                Object::Str("好世".into()),
+                Object::Str("世界".into()),
+                Object::Str("".into()),
+            ]
+            .into(),
+        ),
+        "",
+    )?;
+    fail(
+        args.clone(),
+        "",
+        "\"abc\"[(2, 1)]",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:23\n",
+            "Elysia: index 1 is out of boundaries for `abc` at 1:23\n",
+            "   |\n",
+            " 1 |  fn main(args) { \"abc\"[(2, 1)] }\n",
+            "   |                       ^\n",
+        ),
+    )?;
+    fail(
+        args.clone(),
+        "",
+        "\"abc\"[3]",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:23\n",
+            "Elysia: index 3 is out of boundaries for `abc` at 1:23\n",
+            "   |\n",
+            " 1 |  fn main(args) { \"abc\"[3] }\n",
+            "   |                       ^\n",
+        ),
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn conv() -> Result<(), String> {
+    let args = Object::List([].into());
+
+    exec(
+        args.clone(),
+        "use std::conv;",
+        r#"
+        std::io::print(conv::int(2.9), conv::int(-2.9f64), conv::int(true), conv::int(" 7 "), conv::int(3000000000i64));
+        std::io::print(conv::float(3), conv::float(1i64), conv::float("0.25"), conv::float("x"));
+        std::io::print(conv::i64(3000000000.0f64), conv::i64(7), conv::f64(2) / 4, conv::int(0.0f64 / 0.0f64));
+        std::io::print(conv::str(1.5), conv::str([1, "a"]), conv::bool(0), conv::bool(0.5), conv::bool(""), conv::bool([0]));
+        total = 0.0;
+        for x in [1.5, 2.0, 2.5] { total = total + x; }
+        total / std::str::len("abc")
+        "#,
+        Object::Float(2.0),
+        concat!(
+            "2 -2 1 7 Err(`3000000000` is out of range for `int`)\n",
+            "3 1 0.25 Err(invalid float literal)\n",
+            "3000000000 7 0.5 Err(`NaN` is out of range for `int`)\n",
+            "1.5 [1, a] false true false true\n",
+        ),
+    )?;
+    fail(
+        args.clone(),
+        "",
+        "std::conv::int([1])",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:32\n",
+            "Elysia: cannot convert `[1]` to `int` at 1:32\n",
+            "   |\n",
+            " 1 |  fn main(args) { std::conv::int([1]) }\n",
+            "   |                                ^\n",
+        ),
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn callbacks() -> Result<(), String> {
+    let args = Object::List([].into());
+
+    exec(
+        args.clone(),
+        "use std::nn; group Model(w); fn square(m) { nn::sum(m.w * m.w, [0], false) }",
+        r#"
+        (indices, inference) = (Model(0), Model(nn::tensor([1.0, 2.0, 3.0])));
+        (loss, grad) = nn::grad(indices, inference, square);
+        std::io::print(loss, grad);
+        std::list::map([(1, 2)], Model)
+        "#,
+        Object::List(
+            [Object::Group(
+                0,
+                [Object::Tuple([Object::Int(1), Object::Int(2)].into())].into(),
+            )]
+            .into(),
+        ),
+        "14::Sum <[2.0, 4.0, 6.0]::Detached> as 0x00000000\n",
+    )?;
+    fail(
+        args.clone(),
+        "fn check(x) { 1 / x }",
+        "std::list::map([1, 0], |x| check(x) + 0)",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:53\n",
+            "  in main::<closure> at 1:71\n",
+            "  in check at 1:17\n",
+            "Elysia: cannot apply `/` to `1` and `0` at 1:17\n",
+            "   |\n",
+            " 1 | fn check(x) { 1 / x } fn main(args) { std::list::map([1, 0], |x| check(x) + 0) }\n",
+            "   |                 ^\n",
+        ),
+    )?;
+    fail(
+        args.clone(),
+        "fn check(x) { 1 / x }",
+        "std::list::map([1, 0], |x| check(x))",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:53\n",
+            "  in check at 1:17\n",
+            "Elysia: cannot apply `/` to `1` and `0` at 1:17\n",
+            "   |\n",
+            " 1 | fn check(x) { 1 / x } fn main(args) { std::list::map([1, 0], |x| check(x)) }\n",
+            "   |                 ^\n",
+        ),
+    )?;
+    fail(
+        args.clone(),
+        "fn deep(n) { 1 + deep(n + 1) }",
+        "std::list::map([0], deep)",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:62\n",
+            "  in deep at 1:22\n",
+            "  [previous frame repeated 1023 more times]\n",
+            "Elysia: stack overflow, max depth set to 1024 at 1:22\n",
+            "   |\n",
+            " 1 | fn deep(n) { 1 + deep(n + 1) } fn main(args) { std::list::map([0], deep) }\n",
+            "   |                      ^\n",
+        ),
+    )?;
+    fail(
+        args.clone(),
+        "fn nest(n) { std::list::map([n], nest) }",
+        "nest(0)",
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main at 1:62\n",
+            "  in nest at 1:28\n",
+            "  [previous frame repeated 64 more times]\n",
+            "Elysia: native callbacks nested too deeply, max depth set to 64 at 1:28\n",
+            "   |\n",
+            " 1 | fn nest(n) { std::list::map([n], nest) } fn main(args) { nest(0) }\n",
+            "   |                            ^\n",
+        ),
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn reflect() -> Result<(), String> {
+    let args = Object::List([].into());
+    let defs = "use std::reflect; group Point(x, y); group Unit(value);";
+
+    exec(
+        args.clone(),
+        defs,
+        r#"
+        p = Point(1, "a");
+        std::io::print(reflect::name(p), reflect::fields(p), reflect::values(p));
+        u = Unit(p).with(value = p.with(y = 2));
+        std::io::print(reflect::name(u), reflect::fields(u), reflect::values(u));
+        reflect::fields(u)[0]
+        "#,
+        Object::Str("value".into()),
+        "Point [x, y] [1, a]\nUnit [value] [<1, 2> as 0x00000000]\n",
+    )?;
+
+    Ok(())
+}
@@ -1,4 +1,7 @@
-use felys::{BinOp, III, Object, PhiLia093};
+#![allow(dead_code)]
+
+use felys::{III, Object, PhiLia093, Registry};
+use std::collections::HashMap;
 
 pub fn exec(
     args: Object,
@@ -9,11 +12,11 @@ pub fn exec(
 ) -> Result<(), String> {
     let wrapped = format!("{defs} fn main(args) {{ {body} }}");
     for o in [0, 1, 2, usize::MAX] {
-        for iii in compile(wrapped.as_str(), o)? {
+        for iii in compile(wrapped.as_str(), o, &Registry::default())? {
             let mut out = String::new();
             let obj = iii.exec(args.clone(), &mut out)?;
 
-            if obj.clone().binary(BinOp::Ne, &expect)?.bool()? {
+            if format!("{obj:?}") != format!("{expect:?}") {
                 return Err(format!("Expected {}, got {}", expect, obj));
             } else if out != stdout {
                 return Err(format!("Expected {}, got {}", stdout, out));
@@ -24,15 +27,84 @@ pub fn exec(
     Ok(())
 }
 
-fn compile(code: &str, o: usize) -> Result<[III; 2], String> {
+pub fn fail(args: Object, defs: &str, body: &str, expect: &str) -> Result<(), String> {
+    let wrapped = format!("{defs} fn main(args) {{ {body} }}");
+    for o in [0, 1, 2, usize::MAX] {
+        let [iii, loaded, _] = compile(wrapped.as_str(), o, &Registry::default())?;
+        for iii in [iii, loaded] {
+            let mut out = String::new();
+            match iii.exec(args.clone(), &mut out) {
+                Ok(obj) => return Err(format!("Expected an error, got {}", obj)),
+                Err(e) if e != expect => return Err(format!("Expected {}, got {}", expect, e)),
+                Err(_) => {}
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn asm(args: Object, listing: &str, expect: Object, stdout: &str) -> Result<(), String> {
+    let registry = Registry::default();
+    let iii = III::assemble(listing, &registry)?;
+    for iii in roundtrip(iii, &registry)? {
+        let mut out = String::new();
+        let obj = iii.exec(args.clone(), &mut out)?;
+
+        if format!("{obj:?}") != format!("{expect:?}") {
+            return Err(format!("Expected {}, got {}", expect, obj));
+        } else if out != stdout {
+            return Err(format!("Expected {}, got {}", stdout, out));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn compile(code: &str, o: usize, registry: &Registry) -> Result<[III; 3], String> {
+    let iii = PhiLia093::from(code.to_string())
+        .parse()?
+        .desugar(registry)?
+        .codegen(o)?;
+    roundtrip(iii, registry)
+}
+
+pub fn import(code: &str, modules: &[(&str, &str)], o: usize) -> Result<[III; 3], String> {
+    let mut loader = modules
+        .iter()
+        .map(|(path, code)| (path.to_string(), code.to_string()))
+        .collect::<HashMap<_, _>>();
+    let registry = Registry::default();
     let iii = PhiLia093::from(code.to_string())
         .parse()?
-        .desugar()?
+        .import(&mut loader)?
+        .desugar(&registry)?
         .codegen(o)?;
+    roundtrip(iii, &registry)
+}
+
+pub fn object(
+    code: &str,
+    unit: Option<&III>,
+    o: usize,
+    registry: &Registry,
+) -> Result<[III; 3], String> {
+    let mut i = PhiLia093::from(code.to_string()).parse()?;
+    if let Some(unit) = unit {
+        i = i.declare(unit);
+    }
+    let iii = i.desugar(registry)?.object(o)?;
+    roundtrip(iii, registry)
+}
+
+pub fn roundtrip(iii: III, registry: &Registry) -> Result<[III; 3], String> {
+    let mut listing = String::new();
+    iii.disassemble(&mut listing).unwrap();
+    let assembled = III::assemble(&listing, registry)?;
 
     let mut binary = Vec::with_capacity(256);
     iii.dump(&mut binary).unwrap();
-    let loaded = III::load(&mut binary.as_slice()).unwrap();
+    let loaded = III::load(&mut binary.as_slice(), registry).unwrap();
 
-    Ok([iii, loaded])
+    Ok([iii, loaded, assembled])
 }
@@ -0,0 +1,290 @@
+use crate::utils::{asm, compile};
+use felys::{III, Object, Registry};
+
+mod utils;
+
+#[test]
+fn arithmetic() -> Result<(), String> {
+    asm(
+        Object::List([].into()),
+        r#"
+.main
+    args 1
+    registers 3
+    0000  load     r0, d0
+    0001  load     r1, d1
+    0002  binary   r2, r0, *, r1
+    0003  unary    r2, -, r2
+    0004  return   r2
+
+.data
+    d0    int 6
+    d1    int 7
+"#,
+        Object::Int(-42),
+        "",
+    )
+}
+
+#[test]
+fn control() -> Result<(), String> {
+    asm(
+        Object::Int(5),
+        r#"
+.main
+    args 1
+    registers 5
+    0000  arg      r0, 0
+    0001  load     r1, d0                  ; 0
+    0002  load     r2, d1                  ; 1
+    0003  copy     r3, r1
+    0004  binary   r4, r1, <, r0
+    0005  branch   r4, @0006, @0009
+    0006  binary   r3, r3, +, r1
+    0007  binary   r1, r1, +, r2
+    0008  jump     @0004
+    0009  return   r3
+
+.data
+    d0    int 0
+    d1    int 1
+"#,
+        Object::Int(10),
+        "",
+    )
+}
+
+#[test]
+fn calling() -> Result<(), String> {
+    asm(
+        Object::List([].into()),
+        r#"
+.main
+    args 1
+    registers 4
+    0000  pointer  r0, group 0
+    0001  load     r1, d0
+    0002  load     r2, d1
+    0003  call     r3, r0, [r1, r2]
+    0004  method   r0, r3, #7, []
+    0005  pointer  r1, rust 0
+    0006  call     r1, r1, [r0]
+    0007  field    r1, r3, #5
+    0008  return   r1
+
+.text 0
+    args 1
+    registers 3
+    0000  arg      r0, 0
+    0001  field    r1, r0, #4
+    0002  field    r2, r0, #5
+    0003  tuple    r0, [r2, r1]
+    0004  return   r0
+
+.data
+    d0    str "felys"
+    d1    str "\u{1F431}; \"neko\""
+
+.native
+    n0    std::io::print
+
+.group 0
+    field #4 0
+    field #5 1
+    method #7 fn 0
+"#,
+        Object::Str("🐱; \"neko\"".into()),
+        "(🐱; \"neko\", felys)\n",
+    )
+}
+
+#[test]
+fn tail() -> Result<(), String> {
+    asm(
+        Object::List([].into()),
+        r#"
+.main
+    args 1
+    registers 3
+    0000  pointer  r0, fn 0
+    0001  load     r1, d0
+    0002  tcall    r2, r0, [r1]
+    0003  return   r2
+
+.text 0
+    args 1
+    registers 3
+    0000  arg      r0, 0
+    0001  load     r1, d1
+    0002  binary   r2, r0, ==, r1
+    0003  branch   r2, @0008, @0004
+    0004  load     r1, d2
+    0005  binary   r0, r0, -, r1
+    0006  pointer  r1, fn 0
+    0007  tcall    r0, r1, [r0]
+    0008  return   r0
+
+.data
+    d0    int 5000
+    d1    int 0
+    d2    int 1
+"#,
+        Object::Int(0),
+        "",
+    )
+}
+
+#[test]
+fn failure() {
+    let iii = III::assemble(
+        r#"
+.main
+    args 1
+    registers 2
+    pointer  r0, fn 0
+    call     r1, r0, []
+    return   r1
+
+.text 0 boom
+    args 0
+    registers 1
+    load     r0, d0
+    index    r0, r0, r0
+    return   r0
+
+.data
+    d0    int 0
+"#,
+        &Registry::default(),
+    )
+    .unwrap();
+    let mut stdout = String::new();
+    let e = iii.exec(Object::List([].into()), &mut stdout).unwrap_err();
+    assert_eq!(
+        e,
+        concat!(
+            "Traceback (most recent call last):\n",
+            "  in main @0001\n",
+            "  in boom @0001\n",
+            "Elysia: expecting `0` to be `list`\n",
+        )
+    );
+}
+
+#[test]
+fn malformed() {
+    let cases = [
+        ("return r0", "expecting a section at line 1"),
+        (
+            ".main\n    jump r0",
+            "expecting `@<number>`, found `r0` at line 2",
+        ),
+        (".main\n    0001  return r0", "expecting pc 0 at line 2"),
+        (".main\n    return r0 r1", "unexpected `r1` at line 2"),
+        (".main\n    halt", "unknown instruction `halt` at line 2"),
+        (".text 1", "expecting index 0, found 1 at line 1"),
+        (".data\n    d0 str \"oops", "invalid string at line 2"),
+        (".data", "section `.main` not found"),
+        (
+            ".native\n    n0 host::missing",
+            "native `host::missing` not found at line 2",
+        ),
+        (
+            ".main\n    args 1\n    load r0, d0",
+            "main does not end with a terminator",
+        ),
+        (
+            ".main\n    args 1\n    registers 1\n    return r1",
+            "register r1 is out of bounds in main @0000",
+        ),
+        (
+            ".main\n    args 1\n    registers 1\n    load r0, d0\n    return r0",
+            "data 0 is out of bounds in main @0000",
+        ),
+        (
+            ".main\n    args 1\n    registers 1\n    jump @0001",
+            "target @0001 is out of bounds in main @0000",
+        ),
+        (
+            ".main\n    args 1\n    registers 2\n    load r0, d0\n    map r1, [r0]\n    return r1\n.data\n    d0 int 1",
+            "map has an unpaired key in main @0001",
+        ),
+        (
+            ".main\n    args 1\n    registers 1\n    return r0",
+            "register r0 is read before it is defined in main @0000",
+        ),
+        (
+            ".main\n    args 1\n    registers 2\n    arg r0, 0\n    branch r0, @0002, @0003\n    arg r1, 0\n    return r1",
+            "register r1 is read before it is defined in main @0003",
+        ),
+        (
+            ".main\n    args 1\n    registers 1\n    arg r0, 0\n    return r0\n.text 0\n    args 1\n    registers 1\n    arg r0, 1\n    return r0",
+            "arg 1 is out of bounds in text 0 @0000",
+        ),
+    ];
+    for (listing, msg) in cases {
+        match III::assemble(listing, &Registry::default()) {
+            Ok(_) => panic!("`{listing}` should not assemble"),
+            Err(e) => assert_eq!(e, format!("Elysia: {msg}\n")),
+        }
+    }
+}
+
+#[test]
+fn corrupted() -> Result<(), String> {
+    let registry = Registry::default();
+    let [iii, _, _] = compile("fn main(args) { args + 1 }", 1, &registry)?;
+    let mut binary = Vec::new();
+    iii.dump(&mut binary).unwrap();
+    let seal = |mut binary: Vec<u8>| {
+        let hash = binary[12..].iter().fold(0x811C9DC5u32, |hash, byte| {
+            (hash ^ *byte as u32).wrapping_mul(0x01000193)
+        });
+        binary[8..12].copy_from_slice(&hash.to_be_bytes());
+        binary
+    };
+    assert!(III::load(&mut seal(binary.clone()).as_slice(), &registry).is_ok());
+
+    let mut magic = binary.clone();
+    magic[0] = b'X';
+    let mut version = binary.clone();
+    version[7] = 8;
+    let mut flipped = binary.clone();
+    *flipped.last_mut().unwrap() ^= 0xFF;
+    let truncated = seal(binary[..binary.len() - 1].to_vec());
+    let mut register = binary.clone();
+    let pc = 12 + 4 + 2 + 4;
+    assert_eq!(register[pc], 0x0);
+    register[pc + 1] = 0xFF;
+    let mut tag = binary.clone();
+    tag[pc] = 0xFF;
+
+    let cases = [
+        (magic, "not a felys binary".to_string()),
+        (
+            version,
+            "unsupported format version 8, expecting 7".to_string(),
+        ),
+        (flipped, "checksum mismatch".to_string()),
+        (
+            truncated,
+            "section `source` does not match its length".to_string(),
+        ),
+        (
+            seal(register),
+            "register r255 is out of bounds in main @0000".to_string(),
+        ),
+        (seal(tag), "invalid bytecode tag 0xff".to_string()),
+        (
+            binary[..6].to_vec(),
+            "failed to fill whole buffer".to_string(),
+        ),
+    ];
+    for (binary, msg) in cases {
+        match III::load(&mut binary.as_slice(), &registry) {
+            Ok(_) => panic!("`{msg}` should not load"),
+            Err(e) => assert!(e.to_string().starts_with(&msg), "{e}"),
+        }
+    }
+    Ok(())
+}